@@ -1,3 +1,4 @@
+use scrypto::blueprints::account::DefaultDepositRule;
 use scrypto::prelude::*;
 
 #[blueprint]
@@ -26,5 +27,44 @@ mod incentives_vester {
         pub fn withdraw(&mut self, address: ResourceAddress, amount: Decimal) -> Bucket {
             self.account.withdraw(address, amount)
         }
+
+        pub fn reject_deposits(&mut self) {
+            self.account
+                .set_default_deposit_rule(DefaultDepositRule::Reject)
+        }
+    }
+}
+
+#[blueprint]
+mod dummy_unwrapper {
+    struct DummyUnwrapper {
+        tokens: FungibleVault,
+        wrapped_tokens: FungibleVault,
+    }
+
+    impl DummyUnwrapper {
+        pub fn instantiate_unwrapper(
+            tokens: FungibleBucket,
+        ) -> (Global<DummyUnwrapper>, FungibleBucket) {
+            let wrapped = ResourceBuilder::new_fungible(OwnerRole::None)
+                .divisibility(DIVISIBILITY_MAXIMUM)
+                .mint_initial_supply(tokens.amount());
+
+            let component = Self {
+                tokens: FungibleVault::with_bucket(tokens),
+                wrapped_tokens: FungibleVault::new(wrapped.resource_address()),
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::Fixed(rule!(allow_all)))
+            .globalize();
+
+            (component, wrapped)
+        }
+
+        pub fn unwrap(&mut self, wrapped: FungibleBucket) -> FungibleBucket {
+            let tokens = self.tokens.take(wrapped.amount());
+            self.wrapped_tokens.put(wrapped);
+            tokens
+        }
     }
 }
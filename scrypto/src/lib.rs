@@ -1,34 +1,712 @@
 use scrypto::prelude::*;
 
+/// The longest vest duration accepted, in days (100 years). This keeps the
+/// vesting math well within the range of `Decimal`.
+pub const MAX_VEST_DURATION_DAYS: i64 = 36_500;
+
+/// The shortest pre-claim period accepted, in seconds (one hour). A pre-claim
+/// period of zero would let LP tokens be redeemed as soon as they are claimed,
+/// removing the protection the pre-claim period offers.
+pub const MIN_PRE_CLAIM_DURATION_SECONDS: i64 = 3_600;
+
+/// The method `create_pool_units_wrapped` calls on the unwrap component. It
+/// must take a bucket of the wrapped token and return a bucket of the token
+/// to vest: `fn unwrap(&mut self, wrapped: FungibleBucket) -> FungibleBucket`.
+pub const UNWRAP_METHOD_NAME: &str = "unwrap";
+
+/// How far the token accounting checked by `verify_conservation` may drift
+/// before it counts as violated. Scaling the schedule on treasury forfeits
+/// rounds at the last decimal place, so exact equality is too strict.
+pub const CONSERVATION_TOLERANCE: Decimal = dec!("0.000001");
+
+/// The conditions under which the vester aborts.
+///
+/// A failing method aborts the whole transaction, so it cannot hand an error
+/// value back to the calling component. Instead, it panics with the error's
+/// `message`, which stays stable across versions, so downstream components
+/// and off-ledger tooling can match on it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VesterError {
+    /// `finish_setup` has not been called yet.
+    SetupNotFinished,
+    /// Setup is finished, but the pre-claim period has not ended yet.
+    StillInPreClaim,
+    /// `finish_setup` has already been called, so the setup phase is over.
+    SetupAlreadyFinished,
+    /// A bucket that must contain tokens is empty.
+    EmptyBucket,
+    /// Redemptions are paused.
+    RedemptionsPaused,
+    /// The initial tokens passed to `instantiate_with_funds` are not the token to vest.
+    InitialTokensNotTokenToVest,
+    /// The vest duration is zero or negative.
+    NonPositiveVestDuration,
+    /// The vest duration exceeds `MAX_VEST_DURATION_DAYS`.
+    VestDurationTooLong,
+    /// The initial vested fraction is not between 0 and 1.
+    InvalidInitialVestedFraction,
+    /// The pre-claim period is shorter than `MIN_PRE_CLAIM_DURATION_SECONDS`.
+    PreClaimTooShort,
+    /// The cliff duration is negative.
+    NegativeCliff,
+    /// The cliff is longer than the vest.
+    CliffLongerThanVest,
+    /// The maximum claim per account is zero or negative.
+    NonPositiveMaxClaim,
+    /// The redemption fee fraction is not between 0 and 1.
+    InvalidRedemptionFee,
+    /// The minimum redemption amount is negative.
+    NegativeMinRedemptionAmount,
+    /// The minimum redemption value is zero or negative.
+    NonPositiveMinRedemptionValue,
+    /// The claim grace period is negative.
+    NegativeClaimGracePeriod,
+    /// The maximum total to vest is zero or negative.
+    NonPositiveMaxTotalToVest,
+    /// Adding the tokens would take the total to vest above its maximum.
+    MaxTotalToVestExceeded,
+    /// `finish_setup` was called before any tokens were deposited.
+    NothingToVest,
+    /// `finish_setup` was called without any LP tokens left to claim.
+    NoLpTokensCreated,
+    /// Unwrapping the wrapped tokens did not yield the token to vest.
+    UnwrappedTokensNotTokenToVest,
+    /// The vest start cannot be moved because vesting has already started.
+    VestStartCannotMove,
+    /// The new vest start is in the past.
+    NewVestStartInPast,
+    /// The new vest start is not earlier than the current one.
+    NewVestStartNotEarlier,
+    /// An LP token amount is zero or negative.
+    NonPositiveLpAmount,
+    /// A weighted batch claim was given no recipients.
+    NoRecipients,
+    /// A recipient of a weighted batch claim has a zero or negative weight.
+    NonPositiveWeight,
+    /// The LP vault holds fewer LP tokens than a batch claim hands out.
+    NotEnoughLpTokensForBatch,
+    /// A claim would take the account above the maximum claim per account.
+    ClaimLimitExceeded,
+    /// The account is not on the claim allowlist.
+    AccountNotAllowed,
+    /// The claim deadline has passed.
+    ClaimDeadlinePassed,
+    /// Claims have no deadline, so unclaimed LP tokens cannot be reclaimed.
+    NoClaimDeadline,
+    /// The claim deadline has not passed yet.
+    ClaimDeadlineNotPassed,
+    /// The expiry of a new reservation is not in the future.
+    ReservationExpiryNotInFuture,
+    /// The account already has an open reservation.
+    ReservationAlreadyOpen,
+    /// The account has no open reservation.
+    NoOpenReservation,
+    /// The reservation has expired.
+    ReservationExpired,
+    /// The reservation has not expired yet.
+    ReservationNotExpired,
+    /// An amount to withdraw is zero or negative.
+    NonPositiveAmount,
+    /// An amount to withdraw exceeds the LP tokens in the vault.
+    AmountExceedsLpVault,
+    /// An amount to withdraw exceeds the locked tokens.
+    AmountExceedsLockedTokens,
+    /// No stray tokens of the resource have been deposited.
+    NoStrayTokens,
+    /// The token to vest or the LP token was passed to `deposit_stray`.
+    UseDedicatedDeposit,
+    /// `refill_to` was called with an instant in the future.
+    FutureRefill,
+    /// The bucket passed to `redeem` does not hold the LP token.
+    NotLpToken,
+    /// The LP bucket is below the minimum redemption amount.
+    BelowMinRedemptionAmount,
+    /// The redemption value per LP token is below the minimum redemption value.
+    RedemptionValueBelowFloor,
+    /// A redemption yields less than the requested minimum amount out.
+    MinAmountOutNotMet,
+    /// An amount to redeem exceeds the LP tokens in the bucket.
+    AmountExceedsLpBucket,
+    /// The fraction to simulate redeeming now is not between 0 and 1.
+    InvalidFractionRedeemedNow,
+    /// The bucket does not hold LP tokens of this component.
+    NotLpTokensOfComponent,
+    /// The migration target is this vester.
+    MigrationToSelf,
+    /// The migration source is this vester.
+    MigrationFromSelf,
+    /// `migrate` was called without a migration target set.
+    NoMigrationAuthorized,
+    /// Migrated tokens were sent without a migration source accepted.
+    NoMigrationSource,
+    /// The migrated tokens are not the token to vest.
+    MigratedTokensNotTokenToVest,
+    /// No vesting track with the given id has been created.
+    UnknownTrack,
+}
+
+impl VesterError {
+    /// Returns the message the vester panics with for this error.
+    pub const fn message(&self) -> &'static str {
+        match self {
+            VesterError::SetupNotFinished => "Vesting not set up yet.",
+            VesterError::StillInPreClaim => "Still in pre-claim period. Vesting not started yet.",
+            VesterError::SetupAlreadyFinished => "Vesting has already started",
+            VesterError::EmptyBucket => "Bucket must contain some amount.",
+            VesterError::RedemptionsPaused => "Redemptions are paused.",
+            VesterError::InitialTokensNotTokenToVest => {
+                "Initial tokens must be of the token to vest."
+            }
+            VesterError::NonPositiveVestDuration => "Vest duration must be positive",
+            VesterError::VestDurationTooLong => "Vest duration must not exceed 100 years.",
+            VesterError::InvalidInitialVestedFraction => {
+                "initial_vested_fraction must be between 0 and 1"
+            }
+            VesterError::PreClaimTooShort => "Pre-claim period must last at least one hour.",
+            VesterError::NegativeCliff => "Cliff must not have negative duration.",
+            VesterError::CliffLongerThanVest => "Cliff must not be longer than the vest duration.",
+            VesterError::NonPositiveMaxClaim => "Max claim per account must be positive.",
+            VesterError::InvalidRedemptionFee => "redemption_fee_fraction must be between 0 and 1",
+            VesterError::NegativeMinRedemptionAmount => {
+                "Minimum redemption amount must not be negative."
+            }
+            VesterError::NonPositiveMinRedemptionValue => {
+                "Minimum redemption value must be positive."
+            }
+            VesterError::NegativeClaimGracePeriod => "Claim grace period must not be negative.",
+            VesterError::NonPositiveMaxTotalToVest => "Maximum total to vest must be positive.",
+            VesterError::MaxTotalToVestExceeded => "Total to vest would exceed the maximum.",
+            VesterError::NothingToVest => {
+                "Nothing to vest. Deposit tokens with create_pool_units first."
+            }
+            VesterError::NoLpTokensCreated => "No LP tokens created; cannot finish setup.",
+            VesterError::UnwrappedTokensNotTokenToVest => {
+                "Unwrapped tokens are not the token to vest."
+            }
+            VesterError::VestStartCannotMove => {
+                "Vesting has already started, its start cannot be moved."
+            }
+            VesterError::NewVestStartInPast => "New vest start must not be in the past.",
+            VesterError::NewVestStartNotEarlier => {
+                "New vest start must be earlier than the current vest start."
+            }
+            VesterError::NonPositiveLpAmount => "LP token amount must be greater than zero",
+            VesterError::NoRecipients => "No recipients given.",
+            VesterError::NonPositiveWeight => "Weight must be greater than zero",
+            VesterError::NotEnoughLpTokensForBatch => {
+                "Not enough LP tokens in the vault for this batch."
+            }
+            VesterError::ClaimLimitExceeded => "Claim exceeds the maximum claim per account.",
+            VesterError::AccountNotAllowed => "Account is not on the allowlist.",
+            VesterError::ClaimDeadlinePassed => "The claim deadline has passed.",
+            VesterError::NoClaimDeadline => "Claims have no deadline.",
+            VesterError::ClaimDeadlineNotPassed => "The claim deadline has not passed yet.",
+            VesterError::ReservationExpiryNotInFuture => {
+                "Reservation expiry must be in the future."
+            }
+            VesterError::ReservationAlreadyOpen => "Account already has an open reservation.",
+            VesterError::NoOpenReservation => "Account has no open reservation.",
+            VesterError::ReservationExpired => "Reservation has expired.",
+            VesterError::ReservationNotExpired => "Reservation has not expired yet.",
+            VesterError::NonPositiveAmount => "Amount must be positive.",
+            VesterError::AmountExceedsLpVault => "Amount exceeds the LP tokens in the vault.",
+            VesterError::AmountExceedsLockedTokens => "Amount exceeds the locked tokens.",
+            VesterError::NoStrayTokens => "No stray tokens of this resource.",
+            VesterError::UseDedicatedDeposit => {
+                "Use the dedicated methods to deposit the token to vest or LP tokens."
+            }
+            VesterError::FutureRefill => "Cannot refill to a future instant.",
+            VesterError::NotLpToken => "Bucket is not the LP token.",
+            VesterError::BelowMinRedemptionAmount => {
+                "LP bucket is below the minimum redemption amount."
+            }
+            VesterError::RedemptionValueBelowFloor => {
+                "Redemption value per LP token is below the floor; redemptions are halted."
+            }
+            VesterError::MinAmountOutNotMet => {
+                "Redemption yields less than the minimum amount out."
+            }
+            VesterError::AmountExceedsLpBucket => "Amount exceeds the LP tokens in the bucket.",
+            VesterError::InvalidFractionRedeemedNow => {
+                "fraction_redeemed_now must be between 0 and 1"
+            }
+            VesterError::NotLpTokensOfComponent => {
+                "Bucket does not contain LP tokens of this component."
+            }
+            VesterError::MigrationToSelf => "Cannot migrate to the same vester.",
+            VesterError::MigrationFromSelf => "Cannot migrate from the same vester.",
+            VesterError::NoMigrationAuthorized => "No migration authorized.",
+            VesterError::NoMigrationSource => "No migration source accepted.",
+            VesterError::MigratedTokensNotTokenToVest => {
+                "Migrated tokens must be of the token to vest."
+            }
+            VesterError::UnknownTrack => "No track with this id.",
+        }
+    }
+}
+
+/// The shape of the vesting curve applied to the progress through the vest.
+///
+/// The curve only shapes the part that is not vested immediately; the
+/// `initial_vested_fraction` is always available at `vest_start`.
+#[derive(ScryptoSbor, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VestingCurve {
+    /// Tokens unlock evenly over the vest.
+    Linear,
+    /// Back-loaded: the effective progress is `progress^2`.
+    Quadratic,
+    /// Front-loaded: the effective progress is `progress^0.5`.
+    SquareRoot,
+}
+
+/// What happens to the unvested tokens forfeited by an early redemption.
+#[derive(ScryptoSbor, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForfeitPolicy {
+    /// The forfeited tokens keep vesting for the remaining LP holders,
+    /// raising their maturity value.
+    Redistribute,
+    /// The forfeited tokens are moved to the treasury, leaving the maturity
+    /// value of the remaining LP holders unchanged.
+    Treasury,
+}
+
+/// The step in which vesting progresses. The time elapsed since `vest_start`
+/// is rounded down to a whole number of steps before the vesting curve is
+/// applied, so tokens unlock in discrete steps rather than continuously.
+#[derive(ScryptoSbor, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VestGranularity {
+    /// Tokens unlock every second, i.e. continuously.
+    Second,
+    /// Tokens unlock at the start of every minute of the vest.
+    Minute,
+    /// Tokens unlock at the start of every hour of the vest.
+    Hour,
+}
+
+impl VestGranularity {
+    /// Returns the length of one step in seconds.
+    pub fn seconds(&self) -> i64 {
+        match self {
+            VestGranularity::Second => 1,
+            VestGranularity::Minute => 60,
+            VestGranularity::Hour => 3600,
+        }
+    }
+}
+
+/// The limits an incentives vester enforces on claims, redemptions and
+/// contributions, as passed to `instantiate`. The default enforces none of
+/// them.
+#[derive(ScryptoSbor, Debug, Clone, PartialEq, Eq, Default)]
+pub struct VesterLimits {
+    /// The maximum cumulative amount of LP tokens any single account can be
+    /// claimed, or `None` for no limit. Must be positive if set.
+    pub max_claim_per_account: Option<Decimal>,
+    /// The minimum amount of LP tokens a single redemption must contain.
+    /// Must not be negative, 0 meaning no minimum.
+    pub min_redeem_lp: Decimal,
+    /// The redemption value of 1 LP token below which redemptions are
+    /// halted, or `None` for no floor. Must be positive if set.
+    pub min_redemption_value: Option<Decimal>,
+    /// The maximum amount of tokens the component may ever vest, or `None`
+    /// for no cap. Must be positive if set.
+    pub max_total_to_vest: Option<Decimal>,
+}
+
+/// The full vesting configuration of an incentives vester, as returned by
+/// `get_config`.
+#[derive(ScryptoSbor, Debug, Clone, PartialEq, Eq)]
+pub struct VesterConfig {
+    /// The instant when vesting begins, `None` until setup is finished.
+    pub vest_start: Option<Instant>,
+    /// The instant when all tokens are fully vested, `None` until setup is
+    /// finished.
+    pub vest_end: Option<Instant>,
+    /// The instant when the cliff ends, `None` until setup is finished.
+    pub cliff_end: Option<Instant>,
+    /// The instant from which claims are rejected, `None` until setup is
+    /// finished or if claims never close.
+    pub claim_deadline: Option<Instant>,
+    /// The duration of the vesting period in days.
+    pub vest_duration_days: i64,
+    /// The duration of the pre-claim period in seconds.
+    pub pre_claim_duration_seconds: i64,
+    /// The duration of the cliff in days.
+    pub cliff_duration_days: i64,
+    /// The fraction of tokens that is vested immediately at `vest_start`.
+    pub initial_vested_fraction: Decimal,
+    /// The curve applied to the vesting progress.
+    pub vesting_curve: VestingCurve,
+    /// The rounding mode used when moving tokens between vaults.
+    pub rounding_mode: RoundingMode,
+    /// The maximum amount of LP tokens a single account can be claimed, if
+    /// any.
+    pub max_claim_per_account: Option<Decimal>,
+    /// The fraction of the tokens of an early redemption that goes to the
+    /// treasury.
+    pub redemption_fee_fraction: Decimal,
+    /// The minimum amount of LP tokens a single redemption must contain.
+    pub min_redeem_lp: Decimal,
+    /// The floor on the redemption value of 1 LP token below which
+    /// redemptions are halted, if any.
+    pub min_redemption_value: Option<Decimal>,
+    /// What happens to the tokens forfeited by early redemptions.
+    pub forfeit_policy: ForfeitPolicy,
+    /// The number of days after `vest_end` during which claims stay open, if
+    /// claims close at all.
+    pub claim_grace_days: Option<i64>,
+    /// The step in which vesting progresses.
+    pub vest_granularity: VestGranularity,
+    /// The cap on `total_tokens_to_vest`, if any.
+    pub max_total_to_vest: Option<Decimal>,
+    /// The resource address of the token being vested.
+    pub token_to_vest: ResourceAddress,
+    /// The resource address of the LP tokens (pool units).
+    pub pool_unit: ResourceAddress,
+}
+
+/// The boundary instants of the pre-claim and vesting periods, as returned
+/// by `get_timeline`.
+#[derive(ScryptoSbor, Debug, Clone, PartialEq, Eq)]
+pub struct Timeline {
+    /// The instant when `finish_setup` was called and the pre-claim period
+    /// began, `None` until setup is finished.
+    pub setup_finished_at: Option<Instant>,
+    /// The instant when the pre-claim period ends and vesting begins, `None`
+    /// until setup is finished.
+    pub vest_start: Option<Instant>,
+    /// The instant when all tokens are fully vested, `None` until setup is
+    /// finished.
+    pub vest_end: Option<Instant>,
+}
+
+/// The shape of the vesting schedule, independent of when it starts, as
+/// returned by `get_schedule_descriptor`.
+#[derive(ScryptoSbor, Debug, Clone, PartialEq, Eq)]
+pub struct ScheduleDescriptor {
+    /// The fraction of tokens that is vested immediately at `vest_start`.
+    pub initial_fraction: Decimal,
+    /// The curve applied to the vesting progress.
+    pub curve: VestingCurve,
+    /// The duration of the vesting period in days.
+    pub duration_days: i64,
+    /// The duration of the cliff in days.
+    pub cliff_days: i64,
+    /// The step in which vesting progresses.
+    pub granularity: VestGranularity,
+}
+
+/// The vault amounts and vesting totals of an incentives vester at a single
+/// instant, as returned by `get_state_snapshot`.
+#[derive(ScryptoSbor, Debug, Clone, PartialEq, Eq)]
+pub struct StateSnapshot {
+    /// The amount of vested tokens in the pool, available for redemption.
+    pub pool_amount: Decimal,
+    /// The amount of tokens still locked (not yet vested).
+    pub locked_amount: Decimal,
+    /// The amount of LP tokens not yet claimed by users.
+    pub unclaimed_lp: Decimal,
+    /// The cumulative amount of tokens vested so far.
+    pub vested_tokens: Decimal,
+    /// The total amount of tokens to vest.
+    pub total_tokens_to_vest: Decimal,
+}
+
+/// A summary of what a given amount of LP tokens is worth, as returned by
+/// `get_position`.
+#[derive(ScryptoSbor, Debug, Clone, PartialEq, Eq)]
+pub struct UserPosition {
+    /// The amount of tokens `redeem` would return right now, after the
+    /// redemption fee.
+    pub redeemable_now: Decimal,
+    /// The amount of tokens the LP tokens are worth at full maturity.
+    pub value_at_maturity: Decimal,
+    /// The amount of tokens forfeited by redeeming now instead of at full
+    /// maturity.
+    pub forfeit_if_redeem_now: Decimal,
+    /// The fraction of tokens that is vested right now.
+    pub vested_fraction: Decimal,
+}
+
+/// The recipient's personal unlock schedule, optionally recorded on the
+/// receipt NFTs of a claim for secondary display.
+#[derive(ScryptoSbor, Debug, Clone, PartialEq, Eq)]
+pub struct ReceiptData {
+    /// The cohort the recipient belongs to.
+    pub cohort: String,
+    /// The multiplier applied to the recipient's rewards.
+    pub multiplier: Decimal,
+}
+
+/// The schedule of an additional vesting track, as passed to `create_track`.
+#[derive(ScryptoSbor, Debug, Clone, PartialEq, Eq)]
+pub struct TrackParams {
+    /// The duration of the track's vest in days, counted from `vest_start`.
+    pub vest_duration_days: i64,
+    /// The fraction of the track's tokens available once its cliff ends.
+    pub initial_vested_fraction: Decimal,
+    /// The duration of the track's cliff in days, counted from `vest_start`.
+    pub cliff_duration_days: i64,
+}
+
+/// An additional vesting track created with `create_track`.
+///
+/// Every track has its own pool and LP token, as the value of an LP token
+/// follows the schedule of its own pool, and its own locked vault.
+#[derive(ScryptoSbor)]
+pub struct VestingTrack {
+    /// The schedule of the track.
+    pub params: TrackParams,
+    /// The pool the track's LP tokens are redeemed against.
+    pub pool: Global<OneResourcePool>,
+    /// The track's tokens that have not vested yet.
+    pub locked_tokens_vault: FungibleVault,
+    /// The track's LP tokens that have not been claimed yet.
+    pub lp_tokens_vault: FungibleVault,
+    /// The total amount of tokens the track vests.
+    pub total_tokens_to_vest: Decimal,
+    /// The cumulative amount of tokens moved from the track's locked vault
+    /// into its pool.
+    pub vested_tokens: Decimal,
+}
+
+/// The state of an additional vesting track, as returned by `get_track`.
+#[derive(ScryptoSbor, Debug, Clone, PartialEq, Eq)]
+pub struct TrackInfo {
+    /// The schedule of the track.
+    pub params: TrackParams,
+    /// The address of the track's LP token.
+    pub pool_unit_resource_address: ResourceAddress,
+    /// The amount of tokens in the track's pool.
+    pub pool_vault_amount: Decimal,
+    /// The amount of tokens in the track's locked vault.
+    pub locked_vault_amount: Decimal,
+    /// The total amount of tokens the track vests.
+    pub total_tokens_to_vest: Decimal,
+    /// The cumulative amount of tokens vested so far.
+    pub vested_tokens: Decimal,
+}
+
+/// LP tokens set aside for an account by `reserve_claim`, as returned by
+/// `get_reservation`.
+#[derive(ScryptoSbor, Debug, Clone, PartialEq, Eq)]
+pub struct ClaimReservation {
+    /// The amount of LP tokens reserved.
+    pub lp_amount: Decimal,
+    /// The instant from which the reservation can no longer be finalized and
+    /// can be released instead.
+    pub expires_at: Instant,
+}
+
+/// The data of the receipt NFT minted for every claim.
+#[derive(ScryptoSbor, NonFungibleData)]
+pub struct ClaimReceipt {
+    /// The amount of LP tokens that were claimed.
+    pub lp_amount: Decimal,
+    /// The instant of the claim.
+    pub claimed_at: Instant,
+    /// The incentives season the claim belongs to.
+    pub season: u32,
+    /// The recipient's unlock schedule, if one was given. A later claim with
+    /// new data for the same account updates all of its receipts.
+    #[mutable]
+    pub receipt_data: Option<ReceiptData>,
+}
+
+/// Emitted when `finish_setup` is called and the pre-claim period starts.
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct SetupFinishedEvent {
+    /// The instant when vesting begins, at the end of the pre-claim period.
+    pub vest_start: Instant,
+    /// The instant when all tokens are fully vested.
+    pub vest_end: Instant,
+    /// The total amount of tokens that will be vested.
+    pub total_tokens_to_vest: Decimal,
+}
+
+/// Emitted when LP tokens are claimed for an account.
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct ClaimEvent {
+    /// The account the LP tokens were stored for.
+    pub account: Global<Account>,
+    /// The amount of LP tokens stored for the account.
+    pub lp_amount: Decimal,
+}
+
+/// Emitted when `refill` moves vested tokens from the locked vault into
+/// the pool.
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct RefillEvent {
+    /// The amount of tokens moved into the pool by this refill.
+    pub newly_vested: Decimal,
+    /// The cumulative amount of tokens vested so far.
+    pub cumulative_vested: Decimal,
+}
+
+/// Emitted when LP tokens are redeemed for vested tokens.
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct RedeemEvent {
+    /// The amount of LP tokens burned by the pool.
+    pub lp_burned: Decimal,
+    /// The amount of vested tokens returned to the redeemer.
+    pub tokens_returned: Decimal,
+    /// The amount of vested tokens kept as redemption fee in the treasury.
+    pub fee: Decimal,
+}
+
+/// Emitted when the super admin removes locked tokens with
+/// `remove_locked_tokens`, shrinking the vesting schedule.
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct EmergencyWithdrawalEvent {
+    /// The resource address of the withdrawn tokens.
+    pub resource: ResourceAddress,
+    /// The amount of tokens withdrawn from the locked vault.
+    pub amount: Decimal,
+    /// The amount of tokens left in the locked vault afterwards.
+    pub remaining_locked: Decimal,
+}
+
+/// Emitted when the super admin adds tokens to or removes tokens from the
+/// locked vault, changing the vesting schedule.
+#[derive(ScryptoSbor, ScryptoEvent)]
+pub struct LockedVaultChangedEvent {
+    /// The change of the locked vault: positive for a deposit, negative for
+    /// a withdrawal.
+    pub delta: Decimal,
+    /// The amount of tokens in the locked vault afterwards.
+    pub new_balance: Decimal,
+    /// The method that changed the locked vault, e.g. `add_tranche`.
+    pub caller_action: String,
+}
+
 #[blueprint]
+#[events(
+    SetupFinishedEvent,
+    ClaimEvent,
+    RefillEvent,
+    RedeemEvent,
+    EmergencyWithdrawalEvent,
+    LockedVaultChangedEvent
+)]
 mod incentives_vester {
 
     enable_method_auth! {
         roles {
             super_admin => updatable_by: [];
             admin => updatable_by: [super_admin];
+            recoverer => updatable_by: [];
+            guardian => updatable_by: [super_admin];
         },
         methods {
             // Public methods
             refill => PUBLIC;
+            refill_to => PUBLIC;
             redeem => PUBLIC;
+            redeem_amount => PUBLIC;
+            redeem_to => PUBLIC;
+            redeem_with_min_out => PUBLIC;
+            deposit_stray => PUBLIC;
             get_maturity_value => PUBLIC;
+            simulate_maturity_after_redemption => PUBLIC;
             get_lp_token_amount => PUBLIC;
+            get_lp_total_supply => PUBLIC;
+            get_distributed_lp => PUBLIC;
             get_pool_vault_amount => PUBLIC;
             get_locked_vault_amount => PUBLIC;
+            get_state_snapshot => PUBLIC;
+            get_position => PUBLIC;
             get_pool_unit_resource_address => PUBLIC;
             get_pool_redemption_value => PUBLIC;
+            get_redemption_value_current => PUBLIC;
             get_vested_tokens => PUBLIC;
+            get_realized_rate => PUBLIC;
             get_total_tokens_to_vest => PUBLIC;
+            get_vested_fraction => PUBLIC;
+            projected_vested_at => PUBLIC;
+            get_daily_emission => PUBLIC;
+            get_pre_claim_seconds_remaining => PUBLIC;
+            get_vesting_seconds_remaining => PUBLIC;
+            get_elapsed_seconds => PUBLIC;
+            is_fully_vested => PUBLIC;
+            get_cliff_end => PUBLIC;
+            get_config => PUBLIC;
+            get_timeline => PUBLIC;
+            get_schedule_descriptor => PUBLIC;
+            get_forfeited_tokens => PUBLIC;
+            get_total_redeemed => PUBLIC;
+            get_treasury_amount => PUBLIC;
+            verify_conservation => PUBLIC;
+            get_receipt_resource_address => PUBLIC;
+            get_admin_badges => PUBLIC;
+            get_receipt_data => PUBLIC;
+            is_redeemable_resource => PUBLIC;
+            get_pool_address => PUBLIC;
+            get_locker_address => PUBLIC;
+            is_paused => PUBLIC;
+            is_auto_refill_on_read => PUBLIC;
+            is_allowlist_enabled => PUBLIC;
+            is_allowed_account => PUBLIC;
+            get_claimed_amount => PUBLIC;
+            has_claimed_at_least => PUBLIC;
+            get_locker_balance => PUBLIC;
+            get_claimants => PUBLIC;
+            get_claimant_count => PUBLIC;
+            get_track_count => PUBLIC;
+            get_track => PUBLIC;
+            refill_track => PUBLIC;
+            redeem_track => PUBLIC;
+            can_claim => PUBLIC;
+            preview_redeem => PUBLIC;
+            migrate => PUBLIC;
+            accept_migrated_tokens => PUBLIC;
+            get_migration_target => PUBLIC;
+            get_total_redeemable_now => PUBLIC;
+            get_reservation => PUBLIC;
+            get_pending_claims => PUBLIC;
+            release_expired => PUBLIC;
             // Admin methods
             claim => restrict_to: [super_admin, admin];
+            claim_up_to => restrict_to: [super_admin, admin];
+            claim_bucket => restrict_to: [super_admin, admin];
+            claim_batch => restrict_to: [super_admin, admin];
+            claim_weighted => restrict_to: [super_admin, admin];
+            claim_idempotent => restrict_to: [super_admin, admin];
+            reserve_claim => restrict_to: [super_admin, admin];
+            claim_track => restrict_to: [super_admin, admin];
+            finalize_claim => restrict_to: [super_admin, admin];
+            // Recovery methods
+            recover_from_locker => restrict_to: [recoverer];
             // Super admin methods
             finish_setup => restrict_to: [super_admin];
+            set_vest_duration_days => restrict_to: [super_admin];
+            set_initial_vested_fraction => restrict_to: [super_admin];
+            accelerate_vest_start => restrict_to: [super_admin];
+            add_tranche => restrict_to: [super_admin];
+            add_vesting_funds => restrict_to: [super_admin];
             create_pool_units => restrict_to: [super_admin];
+            create_pool_units_wrapped => restrict_to: [super_admin];
+            create_track => restrict_to: [super_admin];
+            create_track_pool_units => restrict_to: [super_admin];
+            fund_and_reserve => restrict_to: [super_admin];
             remove_lp => restrict_to: [super_admin];
+            remove_lp_amount => restrict_to: [super_admin];
             put_lp => restrict_to: [super_admin];
+            reclaim_unclaimed => restrict_to: [super_admin];
             put_locked_tokens => restrict_to: [super_admin];
             remove_locked_tokens => restrict_to: [super_admin];
+            remove_locked_tokens_amount => restrict_to: [super_admin];
+            configure_lp_metadata => restrict_to: [super_admin];
+            set_dapp_definition => restrict_to: [super_admin];
+            withdraw_treasury => restrict_to: [super_admin];
+            rescue_tokens => restrict_to: [super_admin];
+            pause => restrict_to: [super_admin, guardian];
+            unpause => restrict_to: [super_admin, guardian];
+            set_allowlist_enabled => restrict_to: [super_admin];
+            set_auto_refill_on_read => restrict_to: [super_admin];
+            add_allowed_account => restrict_to: [super_admin];
+            remove_allowed_account => restrict_to: [super_admin];
+            authorize_migration => restrict_to: [super_admin];
+            accept_migration_from => restrict_to: [super_admin];
         }
     }
 
@@ -52,10 +730,13 @@ mod incentives_vester {
     ///    their LP tokens before vesting begins.
     ///
     /// 3. **Vesting Period**: After the pre-claim period ends, tokens gradually
-    ///    unlock over the configured duration (e.g., 1 year). An initial fraction
-    ///    (e.g., 20%) is immediately available. The remaining tokens unlock linearly
-    ///    based on elapsed time. Users can redeem their LP tokens at any time,
-    ///    receiving the vested portion and forfeiting the unvested portion.
+    ///    unlock over the configured duration (e.g., 1 year). An optional cliff
+    ///    (e.g., 30 days) can keep all tokens locked at the start of the vest.
+    ///    Once the cliff has passed, an initial fraction (e.g., 20%) is available.
+    ///    The remaining tokens unlock along the configured vesting curve
+    ///    (linear by default) based on elapsed time. Users can
+    ///    redeem their LP tokens at any time, receiving the vested portion and
+    ///    forfeiting the unvested portion.
     ///
     /// The component uses an AccountLocker to deliver LP tokens to user accounts
     /// that may have deposit restrictions. The AccountLocker acts as a mailbox
@@ -63,9 +744,10 @@ mod incentives_vester {
     /// users to claim them when ready.
     ///
     /// When users redeem early (before full vesting), they forfeit their unvested
-    /// portion. This forfeited amount remains in the pool and increases the maturity
-    /// value for remaining LP token holders, creating an incentive to hold until
-    /// full vesting.
+    /// portion. Under the `Redistribute` forfeit policy this forfeited amount
+    /// remains in the component and increases the maturity value for remaining LP
+    /// token holders, creating an incentive to hold until full vesting. Under the
+    /// `Treasury` policy it is moved to the treasury instead.
     struct IncentivesVester {
         /// The account locker component used to deliver LP tokens to user accounts
         /// during the claim process. This circumvents accounts that have deposit
@@ -89,9 +771,36 @@ mod incentives_vester {
         /// vesting schedule.
         locked_tokens_vault: FungibleVault,
 
-        /// The total amount of tokens that will be vested over the entire vesting
-        /// period. This is set during the setup phase when tokens are deposited
-        /// via `create_pool_units` and remains constant throughout vesting.
+        /// The address of the admin badge, which can claim LP tokens for
+        /// users. It is only stored for `get_admin_badges`; access is enforced
+        /// by the component's roles.
+        admin_badge_address: ResourceAddress,
+
+        /// The address of the super admin badge, which owns the component. It
+        /// is only stored for `get_admin_badges`; access is enforced by the
+        /// component's roles.
+        super_admin_badge_address: ResourceAddress,
+
+        /// The resource manager of the receipt NFTs minted alongside every
+        /// claim. Only this component can mint them.
+        receipt_resource_manager: NonFungibleResourceManager,
+
+        /// The receipt NFTs minted for every account, in order of minting, so
+        /// that their `receipt_data` can be updated on a later claim.
+        receipts_per_account: KeyValueStore<Global<Account>, Vec<NonFungibleLocalId>>,
+
+        /// The incentives season this component distributes rewards for. It
+        /// is recorded on every receipt NFT. This is set during instantiation
+        /// and cannot be changed.
+        season: u32,
+
+        /// The total amount of tokens the schedule vests, i.e. the amount the
+        /// vested fraction is applied to when refilling the pool. It grows as
+        /// tokens are deposited during setup and with `add_tranche`,
+        /// `add_vesting_funds` and `accept_migrated_tokens` after setup.
+        /// Locked withdrawals, redemptions under the `Treasury` forfeit policy
+        /// and `migrate` scale it down along with `vested_tokens`, and
+        /// `put_locked_tokens` scales it back up.
         total_tokens_to_vest: Decimal,
 
         /// The cumulative amount of tokens that have been vested so far, meaning
@@ -100,6 +809,113 @@ mod incentives_vester {
         /// `total_tokens_to_vest` as vesting completes.
         vested_tokens: Decimal,
 
+        /// The second of the last `refill`. Another `refill` within the same
+        /// second returns right away, as the schedule cannot have moved. It is
+        /// cleared when tokens are added to the schedule.
+        last_refill_at: Option<Instant>,
+
+        /// The cumulative amount of tokens forfeited by early redemptions. A
+        /// redeemer gives up the difference between their proportional share
+        /// of all remaining tokens (pool and locked) and what they receive.
+        /// The forfeited tokens stay behind for the remaining LP holders.
+        forfeited_tokens: Decimal,
+
+        /// The cumulative amount of vested tokens paid out by `redeem`. Together
+        /// with `vested_tokens` this gives the tokens still owed from the pool
+        /// without replaying every `RedeemEvent`.
+        total_redeemed: Decimal,
+
+        /// The cumulative amount of unvested tokens moved from the locked
+        /// vault into the treasury by redemptions under the `Treasury` forfeit
+        /// policy.
+        forfeited_to_treasury: Decimal,
+
+        /// The cumulative amount by which treasury forfeits, locked
+        /// withdrawals and migrations shrank `total_tokens_to_vest` beyond the
+        /// tokens that left the vest (negative where it grew by more than the
+        /// tokens that came in). As `vested_tokens` is scaled along with the
+        /// total, the total changes by more than the locked tokens moved, and
+        /// `verify_conservation` needs this to balance.
+        schedule_reduction: Decimal,
+
+        /// The cumulative amount of LP tokens claimed for every account. This
+        /// gives the backend an on-ledger record of who has already received
+        /// their LP tokens, e.g. to resume an interrupted distribution.
+        claimed_per_account: KeyValueStore<Global<Account>, Decimal>,
+
+        /// Every account that has been claimed for, indexed in order of their
+        /// first claim. Unlike `claimed_per_account` this can be enumerated,
+        /// e.g. for audits. Being a `KeyValueStore`, it does not grow the
+        /// component state with every new recipient.
+        claimants: KeyValueStore<u64, Global<Account>>,
+
+        /// The number of accounts in `claimants`, i.e. the next free index.
+        claimant_count: u64,
+
+        /// The maximum cumulative amount of LP tokens a single account can be
+        /// claimed, enforced on-ledger so that even a compromised admin badge
+        /// cannot exceed the policy limit for any account. `None` means there
+        /// is no limit. This is set during instantiation and cannot be changed.
+        max_claim_per_account: Option<Decimal>,
+
+        /// A vault holding the redemption fees taken from early redemptions,
+        /// until the super admin withdraws them with `withdraw_treasury`.
+        treasury_vault: FungibleVault,
+
+        /// Vaults holding tokens of unrelated resources that were sent to the
+        /// component by mistake through `deposit_stray`, until the super admin
+        /// takes them back out with `rescue_tokens`.
+        stray_vaults: KeyValueStore<ResourceAddress, Vault>,
+
+        /// The fraction of the tokens of a redemption before `vest_end` that
+        /// is kept as a fee in the treasury. This is separate from the
+        /// forfeited unvested portion. This is set during instantiation and
+        /// cannot be changed.
+        redemption_fee_fraction: Decimal,
+
+        /// The minimum amount of LP tokens a single `redeem` must burn, which
+        /// keeps dust redemptions from bloating the ledger and the forfeiture
+        /// accounting. Zero means there is no minimum. This is set during
+        /// instantiation and cannot be changed.
+        min_redeem_lp: Decimal,
+
+        /// The floor on the redemption value of 1 LP token. If the value ever
+        /// drops below it after a refill, `redeem` aborts instead of paying
+        /// out, as this indicates an accounting bug or a drain of the pool.
+        /// `None` means there is no floor. This is set during instantiation
+        /// and cannot be changed.
+        min_redemption_value: Option<Decimal>,
+
+        /// What happens to the unvested tokens forfeited by early redemptions.
+        /// With `Redistribute` they stay in the locked vault and vest for the
+        /// remaining LP holders. With `Treasury` `redeem` moves them into the
+        /// treasury vault instead. This is set during instantiation and cannot
+        /// be changed.
+        forfeit_policy: ForfeitPolicy,
+
+        /// The number of days after `vest_end` during which claims stay open.
+        /// After that, claims are rejected and the super admin can take the
+        /// unclaimed LP tokens back with `reclaim_unclaimed`. `None` means
+        /// claims never close. This is set during instantiation and cannot be
+        /// changed.
+        claim_grace_days: Option<i64>,
+
+        /// The step in which vesting progresses. `refill` rounds the time
+        /// elapsed since `vest_start` down to a whole number of steps, so
+        /// tokens unlock on step boundaries only. This is set during
+        /// instantiation and cannot be changed.
+        vest_granularity: VestGranularity,
+
+        /// The cap on `total_tokens_to_vest`. Contributions and top-ups that
+        /// would take the total above it are rejected, guarding against an
+        /// admin over-contributing by mistake. `None` means no cap. This is
+        /// set during instantiation and cannot be changed.
+        max_total_to_vest: Option<Decimal>,
+
+        /// The instant when `finish_setup` was called, i.e. when the pre-claim
+        /// period began. It remains `None` until setup is complete.
+        setup_finished_at: Option<Instant>,
+
         /// The instant when vesting begins. This is set when `finish_setup` is
         /// called and equals the current time plus the pre-claim duration. It
         /// remains `None` until setup is complete.
@@ -110,9 +926,21 @@ mod incentives_vester {
         /// when `finish_setup` is called. It remains `None` until setup is complete.
         vest_end: Option<Instant>,
 
+        /// The instant when the cliff ends. This is calculated as `vest_start`
+        /// plus `cliff_duration_days` and is set when `finish_setup` is called.
+        /// It remains `None` until setup is complete.
+        cliff_end: Option<Instant>,
+
+        /// The instant from which claims are rejected. This is calculated as
+        /// `vest_end` plus `claim_grace_days` and is set when `finish_setup`
+        /// is called. It remains `None` until setup is complete, or for good
+        /// if `claim_grace_days` is `None`.
+        claim_deadline: Option<Instant>,
+
         /// The duration of the vesting period in days. After this period from
         /// `vest_start`, all tokens will be fully vested (100% available). This
-        /// is set during instantiation and cannot be changed.
+        /// is set during instantiation and can only be changed with
+        /// `set_vest_duration_days` until setup is finished.
         vest_duration_days: i64,
 
         /// The duration of the pre-claim period in seconds. This is the time
@@ -121,12 +949,87 @@ mod incentives_vester {
         /// be redeemed yet. This is set during instantiation and cannot be changed.
         pre_claim_duration_seconds: i64,
 
+        /// The duration of the cliff in days, counted from `vest_start`. During
+        /// the cliff nothing is vested, not even the `initial_vested_fraction`.
+        /// Once the cliff ends, everything that would have vested without a
+        /// cliff becomes available at once. This must be between 0 and
+        /// `vest_duration_days`, where 0 means there is no cliff. This is set
+        /// during instantiation and cannot be changed.
+        cliff_duration_days: i64,
+
         /// The fraction of tokens that are immediately vested when the vesting
         /// period begins (at `vest_start`). This must be between 0 and 1. For
         /// example, 0.1 means 10% of tokens are immediately accessible when
         /// vesting starts. The remaining tokens vest linearly over the vesting
-        /// duration. This is set during instantiation and cannot be changed.
+        /// duration. This is set during instantiation and can only be changed
+        /// with `set_initial_vested_fraction` until setup is finished.
         initial_vested_fraction: Decimal,
+
+        /// The curve that turns the elapsed fraction of the vest into the
+        /// effective progress used for the vested fraction. `Linear` vests
+        /// evenly, `Quadratic` back-loads and `SquareRoot` front-loads the
+        /// emission. This is set during instantiation and cannot be changed.
+        vesting_curve: VestingCurve,
+
+        /// The rounding mode used to fit token amounts to the divisibility of
+        /// the vested token, both when `finish_setup` moves the tokens out of
+        /// the pool and when `refill` moves vested tokens back in. Whatever is
+        /// rounded off is caught up by a later refill, and `vest_end` sweeps
+        /// the locked vault entirely. This is set during instantiation and
+        /// cannot be changed.
+        rounding_mode: RoundingMode,
+
+        /// The vester that LP holders can migrate to with `migrate`, once the
+        /// super admin has authorized it with `authorize_migration`.
+        migration_target: Option<Global<IncentivesVester>>,
+
+        /// The vester whose LP holders may migrate to this one, once the
+        /// super admin has accepted it with `accept_migration_from`. Only
+        /// that vester can hand over tokens via `accept_migrated_tokens`.
+        migration_source: Option<Global<IncentivesVester>>,
+
+        /// The open claim reservations, at most one per account. The reserved
+        /// LP tokens are held in `reserved_lp_vault` until the reservation is
+        /// finalized or released.
+        reservations: KeyValueStore<Global<Account>, ClaimReservation>,
+
+        /// A vault holding the LP tokens of all open reservations, so that
+        /// other claims cannot hand them out in the meantime.
+        reserved_lp_vault: FungibleVault,
+
+        /// The claims recorded by `fund_and_reserve` during the setup phase,
+        /// in order. They are delivered by `finish_setup`.
+        pending_claims: Vec<(Global<Account>, Decimal)>,
+
+        /// A vault holding the LP tokens of the pending claims until
+        /// `finish_setup` delivers them.
+        pending_claims_vault: FungibleVault,
+
+        /// Whether redemptions are currently halted. The super admin can pause
+        /// redemptions in an emergency without touching the vesting schedule.
+        /// Refills and getters keep working while paused.
+        paused: bool,
+
+        /// Whether claims may only be delivered to accounts in
+        /// `allowed_accounts`. The super admin can switch this at any time.
+        allowlist_enabled: bool,
+
+        /// The accounts claims may be delivered to while the allowlist is
+        /// enabled. Maintained by the super admin.
+        allowed_accounts: KeyValueStore<Global<Account>, ()>,
+
+        /// Whether the vault and vesting getters call `refill` first, so they
+        /// never show stale values. Off by default to keep reads cheap; the
+        /// super admin can switch it at any time.
+        auto_refill_on_read: bool,
+
+        /// The additional vesting tracks created with `create_track`, keyed
+        /// by their id from 1 on. The component's own schedule is track 0.
+        tracks: KeyValueStore<u32, VestingTrack>,
+
+        /// The number of additional vesting tracks, i.e. the id of the last
+        /// one created.
+        track_count: u32,
     }
 
     impl IncentivesVester {
@@ -143,7 +1046,8 @@ mod incentives_vester {
         /// duration. For example, with `initial_vested_fraction = 0.1` and
         /// `vest_duration_days = 365`, users will have access to 10% of their
         /// tokens immediately when vesting starts, and the remaining 90% will
-        /// unlock linearly over 365 days.
+        /// unlock linearly over 365 days. A non-linear `vesting_curve` changes
+        /// how that remainder is spread over the duration.
         ///
         /// # Arguments
         ///
@@ -155,9 +1059,18 @@ mod incentives_vester {
         ///   the super admin badge resource. Holders of this badge have full
         ///   control over the component, including depositing tokens, finishing
         ///   setup, and withdrawing tokens if needed.
+        /// - `recovery_badge_address`: [`ResourceAddress`] - The address of
+        ///   the recovery badge resource. Holders of this badge can recover LP
+        ///   tokens stuck in the AccountLocker via `recover_from_locker`. This
+        ///   is kept separate from the super admin badge.
+        /// - `guardian_badge_address`: [`ResourceAddress`] - The address of the
+        ///   guardian badge resource. Holders of this badge can only `pause`
+        ///   and `unpause` redemptions, e.g. a security team that must not be
+        ///   able to distribute tokens.
         /// - `vest_duration_days`: [`i64`] - The duration of the vesting period
         ///   in days. After this period from `vest_start`, all tokens will be
-        ///   fully vested. Must be positive.
+        ///   fully vested. Must be positive and at most
+        ///   `MAX_VEST_DURATION_DAYS` (100 years).
         /// - `initial_vested_fraction`: [`Decimal`] - The fraction of tokens
         ///   that are immediately vested when the vesting period begins. Must
         ///   be between 0 and 1. For example, 0.2 means 20% of tokens are
@@ -166,11 +1079,36 @@ mod incentives_vester {
         ///   pre-claim period in seconds. This is the time between when
         ///   `finish_setup` is called and when vesting actually begins. During
         ///   this period, LP tokens can be distributed but not redeemed. Must
-        ///   be non-negative.
+        ///   be at least `MIN_PRE_CLAIM_DURATION_SECONDS` (one hour).
+        /// - `cliff_duration_days`: [`i64`] - The duration of the cliff in
+        ///   days, starting at `vest_start`. Nothing vests during the cliff.
+        ///   Must be between 0 and `vest_duration_days`, 0 meaning no cliff.
+        /// - `vesting_curve`: [`VestingCurve`] - The curve applied to the
+        ///   vesting progress, e.g. `Linear`.
+        /// - `rounding_mode`: [`RoundingMode`] - The rounding mode used to fit
+        ///   token amounts to the token's divisibility, typically `ToZero`.
+        ///   Any divisibility is supported, including 0: refills then move
+        ///   whole units and catch up on whatever rounding held back.
         /// - `token_to_vest`: [`ResourceAddress`] - The address of the fungible
         ///   token resource that will be vested to users.
         /// - `dapp_def_address`: [`ComponentAddress`] - The dapp definition
         ///   address for metadata purposes.
+        /// - `season`: [`u32`] - The incentives season, recorded on the
+        ///   receipt NFT minted for every claim.
+        /// - `redemption_fee_fraction`: [`Decimal`] - The fraction of the
+        ///   tokens of a redemption before `vest_end` that is kept as a fee in
+        ///   the treasury. Must be between 0 and 1, 0 meaning no fee.
+        /// - `forfeit_policy`: [`ForfeitPolicy`] - Whether the tokens forfeited
+        ///   by early redemptions are redistributed to the remaining LP
+        ///   holders or moved to the treasury.
+        /// - `claim_grace_days`: [`Option<i64>`] - The number of days after
+        ///   `vest_end` during which claims stay open, or `None` for claims
+        ///   that never close. Must not be negative if set.
+        /// - `vest_granularity`: [`VestGranularity`] - The step in which
+        ///   vesting progresses, e.g. `Hour` for hourly checkpoints.
+        /// - `limits`: [`VesterLimits`] - The caps on claims and the total to
+        ///   vest and the floors on redemptions, e.g.
+        ///   `VesterLimits::default()` for none of them.
         ///
         /// # Returns
         ///
@@ -180,30 +1118,242 @@ mod incentives_vester {
         /// # Panics
         ///
         /// This function will panic if:
-        /// - `vest_duration_days` is not positive
+        /// - `vest_duration_days` is not positive or exceeds
+        ///   `MAX_VEST_DURATION_DAYS`
         /// - `initial_vested_fraction` is not between 0 and 1
-        /// - `pre_claim_duration_seconds` is negative
+        /// - `pre_claim_duration_seconds` is below
+        ///   `MIN_PRE_CLAIM_DURATION_SECONDS`
+        /// - `cliff_duration_days` is negative or exceeds `vest_duration_days`
+        /// - `redemption_fee_fraction` is not between 0 and 1
+        /// - `claim_grace_days` is set but negative
+        /// - `limits.max_claim_per_account` is set but not positive
+        /// - `limits.min_redeem_lp` is negative
+        /// - `limits.min_redemption_value` is set but not positive
+        /// - `limits.max_total_to_vest` is set but not positive
+        #[allow(clippy::too_many_arguments)]
         pub fn instantiate(
             admin_badge_address: ResourceAddress,
             super_admin_badge_address: ResourceAddress,
+            recovery_badge_address: ResourceAddress,
+            guardian_badge_address: ResourceAddress,
+            vest_duration_days: i64,
+            initial_vested_fraction: Decimal,
+            pre_claim_duration_seconds: i64,
+            cliff_duration_days: i64,
+            vesting_curve: VestingCurve,
+            rounding_mode: RoundingMode,
+            token_to_vest: ResourceAddress,
+            dapp_def_address: ComponentAddress,
+            season: u32,
+            redemption_fee_fraction: Decimal,
+            forfeit_policy: ForfeitPolicy,
+            claim_grace_days: Option<i64>,
+            vest_granularity: VestGranularity,
+            limits: VesterLimits,
+        ) -> Global<IncentivesVester> {
+            Self::instantiate_internal(
+                admin_badge_address,
+                super_admin_badge_address,
+                recovery_badge_address,
+                guardian_badge_address,
+                vest_duration_days,
+                initial_vested_fraction,
+                pre_claim_duration_seconds,
+                cliff_duration_days,
+                vesting_curve,
+                rounding_mode,
+                token_to_vest,
+                dapp_def_address,
+                season,
+                redemption_fee_fraction,
+                forfeit_policy,
+                claim_grace_days,
+                vest_granularity,
+                limits,
+                None,
+            )
+        }
+
+        /// Instantiates a new incentives vester component and funds it in the
+        /// same transaction.
+        ///
+        /// This is `instantiate` followed by a first `create_pool_units` with
+        /// `initial_tokens`, done atomically so the component never exists
+        /// unfunded. Further tokens can still be added with
+        /// `create_pool_units` until `finish_setup` is called.
+        ///
+        /// # Arguments
+        ///
+        /// Takes the same arguments as `instantiate`, plus:
+        ///
+        /// - `initial_tokens`: [`FungibleBucket`] - A bucket containing the
+        ///   first tokens to vest. Must be of the `token_to_vest` resource.
+        ///
+        /// # Returns
+        ///
+        /// - [`Global<IncentivesVester>`] - The global address of the component
+        ///   that was instantiated through this function.
+        ///
+        /// # Panics
+        ///
+        /// This function will panic for the same reasons as `instantiate`, or
+        /// if `initial_tokens` is not of the `token_to_vest` resource.
+        #[allow(clippy::too_many_arguments)]
+        pub fn instantiate_with_funds(
+            admin_badge_address: ResourceAddress,
+            super_admin_badge_address: ResourceAddress,
+            recovery_badge_address: ResourceAddress,
+            guardian_badge_address: ResourceAddress,
+            vest_duration_days: i64,
+            initial_vested_fraction: Decimal,
+            pre_claim_duration_seconds: i64,
+            cliff_duration_days: i64,
+            vesting_curve: VestingCurve,
+            rounding_mode: RoundingMode,
+            token_to_vest: ResourceAddress,
+            dapp_def_address: ComponentAddress,
+            season: u32,
+            redemption_fee_fraction: Decimal,
+            forfeit_policy: ForfeitPolicy,
+            claim_grace_days: Option<i64>,
+            vest_granularity: VestGranularity,
+            limits: VesterLimits,
+            initial_tokens: FungibleBucket,
+        ) -> Global<IncentivesVester> {
+            assert!(
+                initial_tokens.resource_address() == token_to_vest,
+                "{}",
+                VesterError::InitialTokensNotTokenToVest.message()
+            );
+
+            Self::instantiate_internal(
+                admin_badge_address,
+                super_admin_badge_address,
+                recovery_badge_address,
+                guardian_badge_address,
+                vest_duration_days,
+                initial_vested_fraction,
+                pre_claim_duration_seconds,
+                cliff_duration_days,
+                vesting_curve,
+                rounding_mode,
+                token_to_vest,
+                dapp_def_address,
+                season,
+                redemption_fee_fraction,
+                forfeit_policy,
+                claim_grace_days,
+                vest_granularity,
+                limits,
+                Some(initial_tokens),
+            )
+        }
+
+        /// Shared implementation of `instantiate` and `instantiate_with_funds`.
+        ///
+        /// If `initial_tokens` is given, they are contributed to the pool
+        /// before the component is globalized, exactly as `create_pool_units`
+        /// would do.
+        #[allow(clippy::too_many_arguments)]
+        fn instantiate_internal(
+            admin_badge_address: ResourceAddress,
+            super_admin_badge_address: ResourceAddress,
+            recovery_badge_address: ResourceAddress,
+            guardian_badge_address: ResourceAddress,
             vest_duration_days: i64,
             initial_vested_fraction: Decimal,
             pre_claim_duration_seconds: i64,
+            cliff_duration_days: i64,
+            vesting_curve: VestingCurve,
+            rounding_mode: RoundingMode,
             token_to_vest: ResourceAddress,
             dapp_def_address: ComponentAddress,
+            season: u32,
+            redemption_fee_fraction: Decimal,
+            forfeit_policy: ForfeitPolicy,
+            claim_grace_days: Option<i64>,
+            vest_granularity: VestGranularity,
+            limits: VesterLimits,
+            initial_tokens: Option<FungibleBucket>,
         ) -> Global<IncentivesVester> {
+            let VesterLimits {
+                max_claim_per_account,
+                min_redeem_lp,
+                min_redemption_value,
+                max_total_to_vest,
+            } = limits;
+
             let (address_reservation, component_address) =
                 Runtime::allocate_component_address(IncentivesVester::blueprint_id());
 
-            assert!(vest_duration_days > 0, "Vest duration must be positive");
+            assert!(
+                vest_duration_days > 0,
+                "{}",
+                VesterError::NonPositiveVestDuration.message()
+            );
+            assert!(
+                vest_duration_days <= MAX_VEST_DURATION_DAYS,
+                "{}",
+                VesterError::VestDurationTooLong.message()
+            );
             assert!(
                 initial_vested_fraction >= Decimal::ZERO && initial_vested_fraction <= Decimal::ONE,
-                "initial_vested_fraction must be between 0 and 1"
+                "{}",
+                VesterError::InvalidInitialVestedFraction.message()
+            );
+            assert!(
+                pre_claim_duration_seconds >= MIN_PRE_CLAIM_DURATION_SECONDS,
+                "{}",
+                VesterError::PreClaimTooShort.message()
+            );
+            assert!(
+                cliff_duration_days >= 0,
+                "{}",
+                VesterError::NegativeCliff.message()
+            );
+            assert!(
+                cliff_duration_days <= vest_duration_days,
+                "{}",
+                VesterError::CliffLongerThanVest.message()
+            );
+            if let Some(max_claim) = max_claim_per_account {
+                assert!(
+                    max_claim > Decimal::ZERO,
+                    "{}",
+                    VesterError::NonPositiveMaxClaim.message()
+                );
+            }
+            assert!(
+                redemption_fee_fraction >= Decimal::ZERO && redemption_fee_fraction <= Decimal::ONE,
+                "{}",
+                VesterError::InvalidRedemptionFee.message()
             );
             assert!(
-                pre_claim_duration_seconds >= 0,
-                "Pre-claim period must not have negative duration."
+                min_redeem_lp >= Decimal::ZERO,
+                "{}",
+                VesterError::NegativeMinRedemptionAmount.message()
             );
+            if let Some(min_value) = min_redemption_value {
+                assert!(
+                    min_value > Decimal::ZERO,
+                    "{}",
+                    VesterError::NonPositiveMinRedemptionValue.message()
+                );
+            }
+            if let Some(grace_days) = claim_grace_days {
+                assert!(
+                    grace_days >= 0,
+                    "{}",
+                    VesterError::NegativeClaimGracePeriod.message()
+                );
+            }
+            if let Some(max_total) = max_total_to_vest {
+                assert!(
+                    max_total > Decimal::ZERO,
+                    "{}",
+                    VesterError::NonPositiveMaxTotalToVest.message()
+                );
+            }
 
             let admin_access_rule = rule!(require(admin_badge_address));
 
@@ -212,18 +1362,48 @@ mod incentives_vester {
             );
             let super_admin_owner_role = OwnerRole::Fixed(super_admin_access_rule.clone());
 
+            let recoverer_access_rule = rule!(require(recovery_badge_address));
+
+            let guardian_access_rule = rule!(require(guardian_badge_address));
+
+            // Storing stays with the super admin (and this component), while
+            // recovery from the locker is reserved for the recovery badge
+            let locker_recoverer_rule =
+                rule!(require(recovery_badge_address) || require(global_caller(component_address)));
+
             let locker = Blueprint::<AccountLocker>::instantiate(
                 super_admin_owner_role.clone(),
                 super_admin_access_rule.clone(),
                 super_admin_access_rule.clone(),
-                super_admin_access_rule.clone(),
-                super_admin_access_rule.clone(),
+                locker_recoverer_rule,
+                recoverer_access_rule.clone(),
                 None,
             );
 
+            // The pool only accepts contributions from this component once it
+            // is global. To contribute the initial tokens before that, a
+            // one-off badge is minted, used once below and then burned.
+            let funding_badge = initial_tokens.as_ref().map(|_| {
+                ResourceBuilder::new_fungible(OwnerRole::None)
+                    .divisibility(DIVISIBILITY_NONE)
+                    .burn_roles(burn_roles! {
+                        burner => rule!(allow_all);
+                        burner_updater => rule!(deny_all);
+                    })
+                    .mint_initial_supply(1)
+            });
+            let pool_manager_rule = match &funding_badge {
+                Some(badge) => rule!(
+                    require(super_admin_badge_address)
+                        || require(global_caller(component_address))
+                        || require(badge.resource_address())
+                ),
+                None => super_admin_access_rule.clone(),
+            };
+
             let pool = Blueprint::<OneResourcePool>::instantiate(
                 super_admin_owner_role.clone(),
-                super_admin_access_rule,
+                pool_manager_rule,
                 token_to_vest,
                 None,
             );
@@ -233,11 +1413,27 @@ mod incentives_vester {
             let pool_unit_resource_address =
                 ResourceAddress::try_from(pool_unit_global_address).unwrap();
 
-            // We can set the metadata of the pool unit here immediately.
-            // But we would need to pass the super_admin_badge at instantiation to allow that.
-            // Let's not for now.
+            let receipt_resource_manager = ResourceBuilder::new_ruid_non_fungible::<ClaimReceipt>(
+                super_admin_owner_role.clone(),
+            )
+            .metadata(metadata! {
+                init {
+                    "name" => "Incentives Claim Receipt".to_string(), updatable;
+                }
+            })
+            .mint_roles(mint_roles! {
+                minter => rule!(require(global_caller(component_address)));
+                minter_updater => rule!(deny_all);
+            })
+            .non_fungible_data_update_roles(non_fungible_data_update_roles! {
+                non_fungible_data_updater => rule!(require(global_caller(component_address)));
+                non_fungible_data_updater_updater => rule!(deny_all);
+            })
+            .create_with_no_initial_supply();
+
+            // The metadata of the pool unit is set later via configure_lp_metadata.
 
-            Self {
+            let mut vester = Self {
                 locker,
                 pool,
 
@@ -247,13 +1443,55 @@ mod incentives_vester {
                 // Vault that will be filled with tokens to vest (that are still unvested)
                 locked_tokens_vault: FungibleVault::new(token_to_vest),
 
+                // Badges controlling the component, for display
+                admin_badge_address,
+                super_admin_badge_address,
+
+                // Receipts minted for every claim
+                receipt_resource_manager,
+                receipts_per_account: KeyValueStore::new(),
+                season,
+
                 // Already vested amount = initial immediate vest
                 vested_tokens: Decimal::ZERO,
+                last_refill_at: None,
                 total_tokens_to_vest: Decimal::ZERO,
+                forfeited_tokens: Decimal::ZERO,
+                total_redeemed: Decimal::ZERO,
+                forfeited_to_treasury: Decimal::ZERO,
+                schedule_reduction: Decimal::ZERO,
+
+                // Nothing has been claimed yet
+                claimed_per_account: KeyValueStore::new(),
+                claimants: KeyValueStore::new(),
+                claimant_count: 0,
+                // Policy limit per account, if any
+                max_claim_per_account,
+
+                // Vault that will collect the redemption fees
+                treasury_vault: FungibleVault::new(token_to_vest),
+                // Catch-all for tokens sent to the component by mistake
+                stray_vaults: KeyValueStore::new(),
+                redemption_fee_fraction,
+                // Dust floor for redemptions, if any
+                min_redeem_lp,
+                // Circuit breaker on the redemption value, if any
+                min_redemption_value,
+                // Where forfeited tokens go
+                forfeit_policy,
+                // How long claims stay open after the vest, if they close
+                claim_grace_days,
+                // Step in which tokens unlock
+                vest_granularity,
+                // Cap on the total to vest, if any
+                max_total_to_vest,
 
                 // Vest will only start once all lp tokens have been created. This will them turn into a Some.
+                setup_finished_at: None,
                 vest_start: None,
                 vest_end: None,
+                cliff_end: None,
+                claim_deadline: None,
 
                 // Vesting parameters
 
@@ -261,23 +1499,66 @@ mod incentives_vester {
                 vest_duration_days,
                 // Pre-claim duration in seconds
                 pre_claim_duration_seconds,
+                // Cliff duration in days, during which nothing vests
+                cliff_duration_days,
                 // Amount of tokens users can immediately access from the start of the vest.
                 initial_vested_fraction,
+                // Shape of the vesting curve
+                vesting_curve,
+                // Rounding of token amounts to the token's divisibility
+                rounding_mode,
+
+                // No migration until both super admins agree on one
+                migration_target: None,
+                migration_source: None,
+
+                // No reservations yet
+                reservations: KeyValueStore::new(),
+                reserved_lp_vault: FungibleVault::new(pool_unit_resource_address),
+                pending_claims: Vec::new(),
+                pending_claims_vault: FungibleVault::new(pool_unit_resource_address),
+
+                // Redemptions are allowed until the super admin pauses them
+                paused: false,
+
+                // Claims can go to any account until the super admin enables
+                // the allowlist
+                allowlist_enabled: false,
+                allowed_accounts: KeyValueStore::new(),
+
+                // Getters return the stored values until the super admin
+                // enables refilling on read
+                auto_refill_on_read: false,
+
+                // Only the component's own schedule until the super admin
+                // creates more tracks
+                tracks: KeyValueStore::new(),
+                track_count: 0,
+            };
+
+            if let (Some(tokens), Some(badge)) = (initial_tokens, funding_badge) {
+                let lp_tokens = badge.authorize_with_all(|| vester.contribute(tokens));
+                vester.lp_tokens_vault.put(lp_tokens);
+                badge.burn();
             }
-            .instantiate()
-            .prepare_to_globalize(super_admin_owner_role)
-            .roles(roles! {
-                super_admin => OWNER;
-                admin => admin_access_rule;
-            })
-            .with_address(address_reservation)
-            .metadata(metadata! {
-                init {
-                    "name" => "Incentives Vester".to_string(), updatable;
-                    "dapp_definition" => dapp_def_address, updatable;
-                }
-            })
-            .globalize()
+
+            vester
+                .instantiate()
+                .prepare_to_globalize(super_admin_owner_role)
+                .roles(roles! {
+                    super_admin => OWNER;
+                    admin => admin_access_rule;
+                    recoverer => recoverer_access_rule;
+                    guardian => guardian_access_rule;
+                })
+                .with_address(address_reservation)
+                .metadata(metadata! {
+                    init {
+                        "name" => "Incentives Vester".to_string(), updatable;
+                        "dapp_definition" => dapp_def_address, updatable;
+                    }
+                })
+                .globalize()
         }
 
         // region:Super Admin Methods
@@ -294,6 +1575,9 @@ mod incentives_vester {
         ///
         /// The amount of tokens deposited is tracked in `total_tokens_to_vest` and
         /// determines the total amount that will be vested over the vesting period.
+        /// It is measured as the increase of the pool's vault rather than taken
+        /// from the bucket, so that `total_tokens_to_vest` never overstates what
+        /// the pool actually received.
         ///
         /// # Arguments
         ///
@@ -302,17 +1586,99 @@ mod incentives_vester {
         ///
         /// # Panics
         ///
-        /// This method will panic if called after `finish_setup` has been called,
-        /// as setup can only occur before the vesting process begins.
+        /// This method will panic if:
+        /// - Called after `finish_setup` has been called, as setup can only
+        ///   occur before the vesting process begins
+        /// - The total to vest would exceed `max_total_to_vest`
         pub fn create_pool_units(&mut self, tokens_to_vest: FungibleBucket) {
-            assert!(self.vest_start.is_none(), "Vesting has already started");
+            let lp_tokens = self.contribute(tokens_to_vest);
+            self.lp_tokens_vault.put(lp_tokens);
+        }
 
-            // Track the actual amount of tokens contributed
-            let amount = tokens_to_vest.amount();
-            self.total_tokens_to_vest += amount;
+        /// Deposits tokens into the pool like `create_pool_units`, but sets
+        /// the LP tokens aside for an account instead of keeping them
+        /// unclaimed.
+        ///
+        /// This lets airdrops fund and assign every recipient's share in one
+        /// call. The LP tokens are recorded as a pending claim and delivered
+        /// by `finish_setup` through the locker, with a receipt NFT, just like
+        /// `claim`. As `finish_setup` delivers all pending claims at once, keep
+        /// their number within what a single transaction can deliver, as for
+        /// `claim_batch`.
+        ///
+        /// # Arguments
+        ///
+        /// - `tokens`: [`FungibleBucket`] - A bucket containing the tokens to
+        ///   add to the vesting pool.
+        /// - `account_address`: [`Global<Account>`] - The account the LP
+        ///   tokens are for.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if:
+        /// - Called after `finish_setup` has been called
+        /// - The account's pending claims would exceed `max_claim_per_account`
+        /// - The total to vest would exceed `max_total_to_vest`
+        pub fn fund_and_reserve(
+            &mut self,
+            tokens: FungibleBucket,
+            account_address: Global<Account>,
+        ) {
+            let lp_tokens = self.contribute(tokens);
+            let lp_amount = lp_tokens.amount();
 
-            let lp_tokens = self.pool.contribute(tokens_to_vest);
-            self.lp_tokens_vault.put(lp_tokens);
+            if let Some(max_claim) = self.max_claim_per_account {
+                let pending: Decimal = self
+                    .pending_claims
+                    .iter()
+                    .filter(|(account, _)| *account == account_address)
+                    .map(|(_, amount)| *amount)
+                    .fold(Decimal::ZERO, |total, amount| total + amount);
+                assert!(
+                    pending + lp_amount <= max_claim,
+                    "{}",
+                    VesterError::ClaimLimitExceeded.message()
+                );
+            }
+
+            self.pending_claims_vault.put(lp_tokens);
+            self.pending_claims.push((account_address, lp_amount));
+        }
+
+        /// Unwraps tokens and deposits them into the pool like
+        /// `create_pool_units`.
+        ///
+        /// This allows funding the pool with a wrapped version of the token to
+        /// vest. The wrapped tokens are handed to `unwrap_component`, whose
+        /// `UNWRAP_METHOD_NAME` method must return the token to vest, which
+        /// is then contributed to the pool as usual.
+        ///
+        /// # Arguments
+        ///
+        /// - `wrapped`: [`FungibleBucket`] - A bucket containing the wrapped
+        ///   tokens.
+        /// - `unwrap_component`: [`Global<AnyComponent>`] - The component that
+        ///   unwraps them.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if:
+        /// - Called after `finish_setup` has been called
+        /// - The unwrapped tokens are not the token to vest
+        pub fn create_pool_units_wrapped(
+            &mut self,
+            wrapped: FungibleBucket,
+            unwrap_component: Global<AnyComponent>,
+        ) {
+            let tokens: FungibleBucket =
+                unwrap_component.call_raw(UNWRAP_METHOD_NAME, scrypto_args!(wrapped));
+            assert!(
+                tokens.resource_address() == self.locked_tokens_vault.resource_address(),
+                "{}",
+                VesterError::UnwrappedTokensNotTokenToVest.message()
+            );
+
+            self.create_pool_units(tokens);
         }
 
         /// Finalizes the setup phase and begins the pre-claim period.
@@ -321,6 +1687,10 @@ mod incentives_vester {
         /// pre-claim period. It moves all tokens from the pool into the locked
         /// vault and sets the vesting start and end times.
         ///
+        /// The tokens of the additional vesting tracks are moved into their
+        /// locked vaults as well, and the tracks start vesting at the same
+        /// `vest_start`.
+        ///
         /// After this method is called:
         /// - The pre-claim period begins, lasting `pre_claim_duration_seconds`
         /// - During the pre-claim period, LP tokens can be claimed by users via
@@ -328,34 +1698,251 @@ mod incentives_vester {
         /// - When the pre-claim period ends, vesting begins and users can start
         ///   redeeming their LP tokens for the vested portion
         /// - No more tokens can be added via `create_pool_units`
+        /// - The claims recorded by `fund_and_reserve` are delivered
         ///
         /// The vesting schedule is configured as follows:
         /// - `vest_start` = current_time + `pre_claim_duration_seconds`
         /// - `vest_end` = `vest_start` + `vest_duration_days`
+        /// - `cliff_end` = `vest_start` + `cliff_duration_days`
+        /// - `claim_deadline` = `vest_end` + `claim_grace_days`, if set
         ///
         /// # Panics
         ///
-        /// This method will panic if called more than once, as setup can only
-        /// be finalized once.
+        /// This method will panic if:
+        /// - Called more than once, as setup can only be finalized once
+        /// - No tokens have been deposited via `create_pool_units` or
+        ///   `fund_and_reserve`
+        /// - The component holds no LP tokens, e.g. because they have all
+        ///   been taken out with `remove_lp`
         pub fn finish_setup(&mut self) {
-            assert!(self.vest_start.is_none(), "Vesting has already started");
-
+            assert!(
+                self.vest_start.is_none(),
+                "{}",
+                VesterError::SetupAlreadyFinished.message()
+            );
+            assert!(
+                self.total_tokens_to_vest > Decimal::ZERO,
+                "{}",
+                VesterError::NothingToVest.message()
+            );
+            assert!(
+                self.lp_tokens_vault.amount() + self.pending_claims_vault.amount() > Decimal::ZERO,
+                "{}",
+                VesterError::NoLpTokensCreated.message()
+            );
+
             let current_time = Clock::current_time_rounded_to_seconds();
             let pre_claim_end = current_time
                 .add_seconds(self.pre_claim_duration_seconds)
                 .unwrap();
 
+            self.setup_finished_at = Some(current_time);
             self.vest_start = Some(pre_claim_end);
             self.vest_end = Some(pre_claim_end.add_days(self.vest_duration_days).unwrap());
+            self.cliff_end = Some(pre_claim_end.add_days(self.cliff_duration_days).unwrap());
+            self.claim_deadline = self.claim_deadline_after(self.vest_end.unwrap());
 
             let tokens_to_unvest = self.pool.get_vault_amount();
 
             let unvested_tokens = self.pool.protected_withdraw(
                 tokens_to_unvest,
-                WithdrawStrategy::Rounded(RoundingMode::ToZero),
+                WithdrawStrategy::Rounded(self.rounding_mode),
             );
 
             self.locked_tokens_vault.put(unvested_tokens);
+
+            for track_id in 1..=self.track_count {
+                let mut track = self.tracks.get_mut(&track_id).unwrap();
+                let tokens_to_unvest = track.pool.get_vault_amount();
+                if tokens_to_unvest > Decimal::ZERO {
+                    let unvested_tokens = track.pool.protected_withdraw(
+                        tokens_to_unvest,
+                        WithdrawStrategy::Rounded(self.rounding_mode),
+                    );
+                    track.locked_tokens_vault.put(unvested_tokens);
+                }
+            }
+
+            // Deliver the claims recorded by fund_and_reserve
+            for (account_address, lp_amount) in std::mem::take(&mut self.pending_claims) {
+                let lp_tokens = self.pending_claims_vault.take(lp_amount);
+                self.deliver_lp_tokens(lp_tokens, account_address, None);
+            }
+
+            Runtime::emit_event(SetupFinishedEvent {
+                vest_start: self.vest_start.unwrap(),
+                vest_end: self.vest_end.unwrap(),
+                total_tokens_to_vest: self.total_tokens_to_vest,
+            });
+        }
+
+        /// Changes the duration of the vesting period before it is scheduled.
+        ///
+        /// The new duration is picked up by `finish_setup` when it computes
+        /// `vest_end`. Once setup is finished the schedule is fixed, so this
+        /// method can only be called during the setup phase.
+        ///
+        /// # Arguments
+        ///
+        /// - `days`: [`i64`] - The new duration of the vesting period in days.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if:
+        /// - Called after `finish_setup` has been called
+        /// - `days` is not positive or exceeds `MAX_VEST_DURATION_DAYS`
+        /// - `days` is shorter than `cliff_duration_days`
+        pub fn set_vest_duration_days(&mut self, days: i64) {
+            assert!(
+                self.vest_start.is_none(),
+                "{}",
+                VesterError::SetupAlreadyFinished.message()
+            );
+            assert!(
+                days > 0,
+                "{}",
+                VesterError::NonPositiveVestDuration.message()
+            );
+            assert!(
+                days <= MAX_VEST_DURATION_DAYS,
+                "{}",
+                VesterError::VestDurationTooLong.message()
+            );
+            assert!(
+                self.cliff_duration_days <= days,
+                "{}",
+                VesterError::CliffLongerThanVest.message()
+            );
+
+            self.vest_duration_days = days;
+        }
+
+        /// Changes the initial vested fraction before the schedule is fixed.
+        ///
+        /// The new fraction is used by every later `refill`. Once setup is
+        /// finished the schedule is fixed, so this method can only be called
+        /// during the setup phase.
+        ///
+        /// # Arguments
+        ///
+        /// - `fraction`: [`Decimal`] - The new fraction of tokens that is
+        ///   vested immediately at `vest_start`.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if:
+        /// - Called after `finish_setup` has been called
+        /// - `fraction` is not between 0 and 1
+        pub fn set_initial_vested_fraction(&mut self, fraction: Decimal) {
+            assert!(
+                self.vest_start.is_none(),
+                "{}",
+                VesterError::SetupAlreadyFinished.message()
+            );
+            assert!(
+                fraction >= Decimal::ZERO && fraction <= Decimal::ONE,
+                "{}",
+                VesterError::InvalidInitialVestedFraction.message()
+            );
+
+            self.initial_vested_fraction = fraction;
+        }
+
+        /// Brings `vest_start` forward, shortening the pre-claim period.
+        ///
+        /// This allows a program to start vesting earlier than scheduled, e.g.
+        /// when the pre-claim period is no longer needed. `vest_end`,
+        /// `cliff_end` and `claim_deadline` move along with `vest_start`, so
+        /// the vest still lasts `vest_duration_days` and the cliff
+        /// `cliff_duration_days`.
+        ///
+        /// # Arguments
+        ///
+        /// - `new_start`: [`Instant`] - The new instant when vesting begins.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if:
+        /// - Called before `finish_setup` has been called
+        /// - Vesting has already started
+        /// - `new_start` is in the past
+        /// - `new_start` is not earlier than the current `vest_start`
+        pub fn accelerate_vest_start(&mut self, new_start: Instant) {
+            let vest_start = self
+                .vest_start
+                .unwrap_or_else(|| panic!("{}", VesterError::SetupNotFinished.message()));
+            assert!(
+                !self.vesting_started(),
+                "{}",
+                VesterError::VestStartCannotMove.message()
+            );
+            assert!(
+                Clock::current_time_is_at_or_before(new_start, TimePrecision::Second),
+                "{}",
+                VesterError::NewVestStartInPast.message()
+            );
+            assert!(
+                new_start < vest_start,
+                "{}",
+                VesterError::NewVestStartNotEarlier.message()
+            );
+
+            self.vest_start = Some(new_start);
+            self.vest_end = Some(new_start.add_days(self.vest_duration_days).unwrap());
+            self.cliff_end = Some(new_start.add_days(self.cliff_duration_days).unwrap());
+            self.claim_deadline = self.claim_deadline_after(self.vest_end.unwrap());
+        }
+
+        /// Adds a further tranche of tokens to an already scheduled vest.
+        ///
+        /// `create_pool_units` is only available during the setup phase. Once
+        /// setup is finished, this method deposits additional tokens into the
+        /// locked vault and adds them to `total_tokens_to_vest`. The tranche
+        /// follows the same schedule as the original tokens: the next `refill`
+        /// releases the part of it that would already have vested had it been
+        /// there from the start, and the rest vests along with everything else.
+        ///
+        /// No new LP tokens are minted. The tranche is therefore shared by all
+        /// existing LP tokens, claimed or not, and raises their maturity value
+        /// by `tranche / lp_total_supply`.
+        ///
+        /// # Arguments
+        ///
+        /// - `tokens`: [`FungibleBucket`] - A bucket containing the tokens to
+        ///   add to the vest.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if:
+        /// - Called before `finish_setup` has been called
+        /// - The bucket is empty
+        /// - The total to vest would exceed `max_total_to_vest`
+        pub fn add_tranche(&mut self, tokens: FungibleBucket) {
+            self.add_to_vest(tokens, "add_tranche");
+        }
+
+        /// Adds new tokens to the vest, boosting the maturity value of every
+        /// existing LP token.
+        ///
+        /// This works exactly like `add_tranche`: the tokens are deposited
+        /// into the locked vault and added to `total_tokens_to_vest`, so they
+        /// are new funds shared by all LP tokens. In contrast,
+        /// `put_locked_tokens` is a pure restore of tokens taken out with
+        /// `remove_locked_tokens`, which only undoes the removal.
+        ///
+        /// # Arguments
+        ///
+        /// - `tokens`: [`FungibleBucket`] - A bucket containing the tokens to
+        ///   add to the vest.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if:
+        /// - Called before `finish_setup` has been called
+        /// - The bucket is empty
+        /// - The total to vest would exceed `max_total_to_vest`
+        pub fn add_vesting_funds(&mut self, tokens: FungibleBucket) {
+            self.add_to_vest(tokens, "add_vesting_funds");
         }
 
         /// Removes all LP tokens from the component's internal vault.
@@ -375,6 +1962,39 @@ mod incentives_vester {
             self.lp_tokens_vault.take_all()
         }
 
+        /// Removes the given amount of LP tokens from the component's internal
+        /// vault.
+        ///
+        /// This works like `remove_lp`, but leaves the rest of the unclaimed
+        /// LP tokens in place, so they can still be distributed.
+        ///
+        /// # Arguments
+        ///
+        /// - `amount`: [`Decimal`] - The amount of LP tokens to remove.
+        ///
+        /// # Returns
+        ///
+        /// - [`FungibleBucket`] - A bucket containing the removed LP tokens.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if `amount` is not positive or exceeds the
+        /// LP tokens in the vault.
+        pub fn remove_lp_amount(&mut self, amount: Decimal) -> FungibleBucket {
+            assert!(
+                amount > Decimal::ZERO,
+                "{}",
+                VesterError::NonPositiveAmount.message()
+            );
+            assert!(
+                amount <= self.lp_tokens_vault.amount(),
+                "{}",
+                VesterError::AmountExceedsLpVault.message()
+            );
+
+            self.lp_tokens_vault.take(amount)
+        }
+
         /// Deposits LP tokens back into the component's internal vault.
         ///
         /// This method returns LP tokens to the component's vault, making them
@@ -391,12 +2011,47 @@ mod incentives_vester {
             self.lp_tokens_vault.put(tokens)
         }
 
+        /// Takes back the LP tokens nobody claimed before the claim deadline.
+        ///
+        /// Once the grace period after `vest_end` is over, claims are
+        /// rejected, so the LP tokens left in the vault can no longer reach
+        /// users. They are fully vested by then and can be redeemed for their
+        /// share of the pool.
+        ///
+        /// # Returns
+        ///
+        /// - [`FungibleBucket`] - A bucket containing all unclaimed LP tokens.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if:
+        /// - No claim deadline is configured, or setup is not finished
+        /// - The claim deadline has not passed yet
+        pub fn reclaim_unclaimed(&mut self) -> FungibleBucket {
+            let deadline = self
+                .claim_deadline
+                .unwrap_or_else(|| panic!("{}", VesterError::NoClaimDeadline.message()));
+            assert!(
+                Clock::current_time_is_at_or_after(deadline, TimePrecision::Second),
+                "{}",
+                VesterError::ClaimDeadlineNotPassed.message()
+            );
+
+            self.lp_tokens_vault.take_all()
+        }
+
         /// Removes all locked (unvested) tokens from the component.
         ///
         /// This method withdraws all tokens that are still in the locked vault
         /// and have not yet been vested into the pool. This will affect future
         /// vesting as these tokens will no longer be available to vest.
         ///
+        /// `total_tokens_to_vest` and `vested_tokens` shrink by the fraction of
+        /// the locked vault withdrawn, so the schedule shrinks to what is
+        /// actually left instead of later refills trying to release tokens
+        /// that are gone. An `EmergencyWithdrawalEvent` and a
+        /// `LockedVaultChangedEvent` make the withdrawal observable.
+        ///
         /// This is an emergency function that allows the super admin to recover
         /// unvested tokens if needed. Use with extreme caution as it will prevent
         /// users from receiving their full vested amount.
@@ -405,330 +2060,3307 @@ mod incentives_vester {
         ///
         /// - [`FungibleBucket`] - A bucket containing all locked tokens.
         pub fn remove_locked_tokens(&mut self) -> FungibleBucket {
-            self.locked_tokens_vault.take_all()
+            let locked_before = self.locked_tokens_vault.amount();
+            let tokens = self.locked_tokens_vault.take_all();
+            self.record_locked_withdrawal(&tokens, locked_before, "remove_locked_tokens");
+
+            tokens
         }
 
-        /// Deposits locked tokens back into the component's vault.
-        ///
-        /// This method returns locked tokens to the component's vault, making them
-        /// available for vesting according to the vesting schedule.
+        /// Removes the given amount of locked (unvested) tokens from the
+        /// component.
         ///
-        /// This is typically used in conjunction with `remove_locked_tokens` to
-        /// temporarily withdraw and then return locked tokens.
+        /// This works like `remove_locked_tokens`, including shrinking the
+        /// schedule and emitting the same events, but leaves the rest of the
+        /// locked tokens vesting along the original curve. As the vested
+        /// amount shrinks along with the total, refills carry on right after
+        /// the withdrawal, even in the middle of the vest.
         ///
         /// # Arguments
         ///
-        /// - `tokens`: [`FungibleBucket`] - A bucket containing the tokens to
-        ///   deposit into the locked vault.
-        pub fn put_locked_tokens(&mut self, tokens: FungibleBucket) {
-            self.locked_tokens_vault.put(tokens)
-        }
-
-        // endregion:Super Admin Methods
-
-        // region:Admin Methods
-
-        /// Claims LP tokens for a user and deposits them into their account.
-        ///
-        /// This method distributes LP tokens to a user's account during the
-        /// pre-claim period or after vesting has started. The LP tokens are
-        /// deposited using the AccountLocker, which acts as a mailbox for accounts
-        /// that have deposit restrictions. If the account doesn't allow direct
-        /// deposits, the tokens are stored in the locker where the user can claim
-        /// them.
-        ///
-        /// This method is typically called by a backend service that holds the
-        /// admin badge and distributes rewards to users based on their activity
-        /// or participation in an incentives program.
+        /// - `amount`: [`Decimal`] - The amount of locked tokens to remove.
         ///
-        /// # Arguments
+        /// # Returns
         ///
-        /// - `lp_token_amount`: [`Decimal`] - The amount of LP tokens to claim
-        ///   for the user. Must be greater than zero.
-        /// - `account_address`: [`Global<Account>`] - The account address where
-        ///   the LP tokens will be deposited.
+        /// - [`FungibleBucket`] - A bucket containing the removed tokens.
         ///
         /// # Panics
         ///
-        /// This method will panic if:
-        /// - Called before `finish_setup` has been called
-        /// - `lp_token_amount` is zero or negative
-        pub fn claim(&mut self, lp_token_amount: Decimal, account_address: Global<Account>) {
-            assert!(self.vest_start.is_some(), "Vesting not set up yet.");
-
+        /// This method will panic if `amount` is not positive or exceeds the
+        /// tokens in the locked vault.
+        pub fn remove_locked_tokens_amount(&mut self, amount: Decimal) -> FungibleBucket {
             assert!(
-                lp_token_amount > Decimal::ZERO,
-                "LP token amount must be greater than zero"
+                amount > Decimal::ZERO,
+                "{}",
+                VesterError::NonPositiveAmount.message()
+            );
+            assert!(
+                amount <= self.locked_tokens_vault.amount(),
+                "{}",
+                VesterError::AmountExceedsLockedTokens.message()
             );
 
-            let lp_tokens = self.lp_tokens_vault.take(lp_token_amount);
-            self.locker.store(account_address, lp_tokens.into(), true);
+            let locked_before = self.locked_tokens_vault.amount();
+            let tokens = self.locked_tokens_vault.take(amount);
+            self.record_locked_withdrawal(&tokens, locked_before, "remove_locked_tokens_amount");
 
-            // Potentially, we can mint an NFT here to represent the user's performance in Season 1
-            // We would also deposit it with the account_locker
+            tokens
         }
 
-        // endregion:Admin Methods
-
-        // region:Public Methods
-
-        /// Moves vested tokens from the locked vault into the pool.
+        /// Deposits locked tokens back into the component's vault.
         ///
-        /// This method calculates how many tokens should have vested based on
-        /// the current time and the vesting schedule, then moves those tokens
-        /// from the locked vault into the pool, making them available for
-        /// redemption.
+        /// This method returns locked tokens to the component's vault, making them
+        /// available for vesting according to the vesting schedule.
         ///
-        /// The vesting calculation uses a linear schedule with an initial vested
-        /// fraction:
-        /// - At `vest_start` (0% progress): `initial_vested_fraction` is available
-        /// - During vesting: Linear interpolation between initial and 100%
-        /// - At `vest_end` (100% progress): All tokens are available
+        /// This is typically used in conjunction with `remove_locked_tokens` to
+        /// temporarily withdraw and then return locked tokens. It is a pure
+        /// restore: just as removing shrinks `total_tokens_to_vest` and
+        /// `vested_tokens` by the fraction of the locked vault withdrawn,
+        /// putting tokens back grows them by the fraction the locked vault
+        /// grows by. The returned tokens therefore stay locked and vest along
+        /// the rest of the curve, and removing and putting back the same
+        /// tokens leaves the schedule as it was. If the locked vault is empty,
+        /// e.g. after `remove_locked_tokens`, the tokens rejoin the schedule
+        /// as its unvested part at the current vested fraction instead.
         ///
-        /// Formula: `vested_fraction = initial_vested_fraction + (1 - initial_vested_fraction) * progress`
+        /// To top up the vest with new tokens that release the part that
+        /// would already have vested, use `add_vesting_funds`.
         ///
-        /// This method is idempotent - calling it multiple times at the same
-        /// point in time will not move additional tokens. It automatically gets
-        /// called during `redeem`, but can also be called manually to update
-        /// the pool and show accurate LP token values in wallets.
+        /// # Arguments
+        ///
+        /// - `tokens`: [`FungibleBucket`] - A bucket containing the tokens to
+        ///   deposit into the locked vault.
         ///
         /// # Panics
         ///
-        /// This method will panic if:
-        /// - Called before `finish_setup` has been called
-        /// - Called during the pre-claim period (before `vest_start`)
-        pub fn refill(&mut self) {
-            if let Some(vest_start) = self.vest_start {
-                assert!(
-                    Clock::current_time_is_at_or_after(vest_start, TimePrecision::Second),
-                    "Still in pre-claim period. Vesting not started yet."
-                );
+        /// This method will panic if the total to vest would exceed
+        /// `max_total_to_vest`.
+        pub fn put_locked_tokens(&mut self, tokens: FungibleBucket) {
+            let amount = tokens.amount();
+            let locked_before = self.locked_tokens_vault.amount();
+            self.locked_tokens_vault.put(tokens);
+            if locked_before > Decimal::ZERO {
+                self.rescale_schedule(locked_before, -amount);
             } else {
-                panic!("Vesting setup not complete yet.");
+                self.rejoin_schedule(amount);
             }
+            self.assert_within_max_total_to_vest();
+            self.last_refill_at = None;
 
-            let current_time = Clock::current_time_rounded_to_seconds();
-
-            let vest_duration = self.vest_end.unwrap().seconds_since_unix_epoch
-                - self.vest_start.unwrap().seconds_since_unix_epoch;
-
-            let elapsed = current_time.seconds_since_unix_epoch
-                - self.vest_start.unwrap().seconds_since_unix_epoch;
-
-            let raw_progress = Decimal::from(elapsed) / Decimal::from(vest_duration);
-
-            let vest_progress = if raw_progress <= Decimal::ZERO {
-                Decimal::ZERO
-            } else if raw_progress >= Decimal::ONE {
-                Decimal::ONE
-            } else {
-                raw_progress
-            };
-
-            // Apply initial vested fraction + linear vesting of the remainder
-            // At vest_start (progress = 0): initial_vested_fraction is available
-            // At vest_end (progress = 1): 100% is available
-            // Formula: initial + (1 - initial) * progress
-            let vested_fraction = self.initial_vested_fraction
-                + (Decimal::ONE - self.initial_vested_fraction) * vest_progress;
-
-            // Target total vested amount at this point in time
-            let vested_tokens_target = self.total_tokens_to_vest * vested_fraction;
-
-            let tokens_to_vest_now = vested_tokens_target - self.vested_tokens;
+            Runtime::emit_event(LockedVaultChangedEvent {
+                delta: amount,
+                new_balance: self.locked_tokens_vault.amount(),
+                caller_action: "put_locked_tokens".to_string(),
+            });
+        }
 
-            if tokens_to_vest_now <= Decimal::ZERO {
-                return;
-            }
+        /// Sets the name, symbol and icon of the LP token (pool unit).
+        ///
+        /// The pool creates the pool unit resource without any metadata, so
+        /// wallets only show its address. The pool unit's owner role is the
+        /// pool's owner role, which this component is part of through its
+        /// global caller badge. The component can therefore set the metadata
+        /// itself, so only the super admin badge is needed to call this.
+        ///
+        /// # Arguments
+        ///
+        /// - `name`: [`String`] - The name of the LP token.
+        /// - `symbol`: [`String`] - The symbol of the LP token.
+        /// - `icon_url`: [`String`] - The URL of the LP token's icon.
+        pub fn configure_lp_metadata(&mut self, name: String, symbol: String, icon_url: String) {
+            let pool_unit = ResourceManager::from(self.lp_tokens_vault.resource_address());
 
-            let tokens = self.locked_tokens_vault.take(tokens_to_vest_now);
-            self.pool.protected_deposit(tokens);
+            pool_unit.set_metadata("name", name);
+            pool_unit.set_metadata("symbol", symbol);
+            pool_unit.set_metadata("icon_url", UncheckedUrl::of(icon_url));
+        }
 
-            self.vested_tokens = vested_tokens_target;
+        /// Points the component's `dapp_definition` metadata at a new dApp
+        /// definition account, e.g. when a project migrates to a new one.
+        ///
+        /// # Arguments
+        ///
+        /// - `address`: [`ComponentAddress`] - The address of the new dApp
+        ///   definition account.
+        pub fn set_dapp_definition(&mut self, address: ComponentAddress) {
+            Runtime::global_component()
+                .set_metadata("dapp_definition", GlobalAddress::from(address));
         }
 
-        /// Redeems LP tokens for the vested portion of the underlying tokens.
+        /// Withdraws all redemption fees collected in the treasury.
         ///
-        /// This method allows users to exchange their LP tokens for the tokens
-        /// that have vested so far. Users receive a proportional share of the
-        /// currently vested tokens based on their LP token amount, and forfeit
-        /// their claim to any unvested tokens.
+        /// # Returns
         ///
-        /// The redemption value is calculated by the OneResourcePool based on the
-        /// ratio of vested tokens in the pool to the total LP token supply. When
-        /// users redeem early (before 100% vesting), they forfeit their unvested
-        /// portion, which remains in the pool and increases the maturity value for
-        /// remaining LP token holders.
+        /// - [`FungibleBucket`] - A bucket containing the collected fees.
+        pub fn withdraw_treasury(&mut self) -> FungibleBucket {
+            self.treasury_vault.take_all()
+        }
+
+        /// Withdraws all tokens of a stray resource from the component.
         ///
-        /// This method automatically calls `refill` before redemption to ensure
-        /// the pool is up-to-date with the current vesting progress.
+        /// Tokens of resources the component has no use for can only end up
+        /// in it through `deposit_stray`. This hands them to the super admin
+        /// so they can be returned to whoever sent them.
         ///
         /// # Arguments
         ///
-        /// - `lp_token_bucket`: [`FungibleBucket`] - A bucket containing the LP
-        ///   tokens to redeem. Must contain at least some amount.
+        /// - `resource`: [`ResourceAddress`] - The resource to rescue.
         ///
         /// # Returns
         ///
-        /// - [`FungibleBucket`] - A bucket containing the vested tokens received
-        ///   in exchange for the LP tokens.
+        /// - [`Bucket`] - A bucket containing all stray tokens of the resource.
         ///
         /// # Panics
         ///
-        /// This method will panic if the LP token bucket is empty (contains zero
-        /// tokens).
-        pub fn redeem(&mut self, lp_token_bucket: FungibleBucket) -> FungibleBucket {
-            assert!(
-                lp_token_bucket.amount() > Decimal::ZERO,
-                "LP bucket must contain some amount"
-            );
-            self.refill();
-            self.pool.redeem(lp_token_bucket)
+        /// This method will panic if no tokens of `resource` were ever
+        /// deposited with `deposit_stray`.
+        pub fn rescue_tokens(&mut self, resource: ResourceAddress) -> Bucket {
+            self.stray_vaults
+                .get_mut(&resource)
+                .unwrap_or_else(|| panic!("{}", VesterError::NoStrayTokens.message()))
+                .take_all()
         }
 
-        /// Returns the amount of LP tokens in the component's internal vault.
+        /// Halts all redemptions.
         ///
-        /// This method returns the amount of LP tokens that have not yet been
-        /// claimed by users. It does not include LP tokens that have already
-        /// been distributed to user accounts.
+        /// This is an emergency function that allows the super admin or the
+        /// guardian to stop users from redeeming, e.g. when a vulnerability is
+        /// discovered, without draining the locked vault and destroying the
+        /// schedule. Vesting continues while paused, and `refill` and all
+        /// getters keep working so the state remains observable.
+        pub fn pause(&mut self) {
+            self.paused = true;
+        }
+
+        /// Resumes redemptions after a `pause`.
+        pub fn unpause(&mut self) {
+            self.paused = false;
+        }
+
+        /// Restricts claims to the accounts on the allowlist, or lifts the
+        /// restriction.
         ///
-        /// # Returns
+        /// While enabled, every claim path, including delivering reserved and
+        /// pending claims, rejects accounts that are not on the allowlist.
+        /// Accounts can be added to the list before enabling it.
         ///
-        /// - [`Decimal`] - The amount of unclaimed LP tokens in the vault.
-        pub fn get_lp_token_amount(&mut self) -> Decimal {
-            self.lp_tokens_vault.amount()
+        /// # Arguments
+        ///
+        /// - `enabled`: [`bool`] - Whether claims are restricted to allowed
+        ///   accounts.
+        pub fn set_allowlist_enabled(&mut self, enabled: bool) {
+            self.allowlist_enabled = enabled;
         }
 
-        /// Returns the projected value of 1 LP token at full maturity.
+        /// Makes the vault and vesting getters refill before reading, or stops
+        /// them from doing so.
         ///
-        /// This method calculates what 1 LP token will be worth when all tokens
-        /// are fully vested (at `vest_end`). This is useful for showing users
-        /// the potential future value of their LP tokens.
+        /// While enabled, `get_pool_vault_amount`, `get_locked_vault_amount`,
+        /// `get_vested_tokens` and `get_pool_redemption_value` call `refill`
+        /// first once vesting has started, so wallets always show current
+        /// values. This makes these reads cost as much as a refill.
         ///
-        /// The maturity value increases when users redeem early, as they forfeit
-        /// their unvested portion which remains in the pool for other LP token
-        /// holders. This creates an incentive to hold LP tokens until full vesting.
+        /// # Arguments
         ///
-        /// The calculation is:
-        /// `maturity_value = (pool_tokens + locked_tokens) / pool_tokens * current_redemption_value`
+        /// - `enabled`: [`bool`] - Whether the getters refill first.
+        pub fn set_auto_refill_on_read(&mut self, enabled: bool) {
+            self.auto_refill_on_read = enabled;
+        }
+
+        /// Adds an account to the allowlist of claim targets.
         ///
-        /// This method calls `refill` first to ensure the pool is up-to-date.
+        /// # Arguments
         ///
-        /// # Returns
+        /// - `account`: [`Global<Account>`] - The account to allow.
+        pub fn add_allowed_account(&mut self, account: Global<Account>) {
+            self.allowed_accounts.insert(account, ());
+        }
+
+        /// Removes an account from the allowlist of claim targets. LP tokens
+        /// already delivered to it are unaffected.
+        ///
+        /// # Arguments
+        ///
+        /// - `account`: [`Global<Account>`] - The account to remove.
+        pub fn remove_allowed_account(&mut self, account: Global<Account>) {
+            self.allowed_accounts.remove(&account);
+        }
+
+        /// Authorizes LP holders to migrate to a new vester.
+        ///
+        /// This is the old vester's half of the migration handshake. Once it
+        /// is set, anyone can move LP tokens of this component to `new_vester`
+        /// with `migrate`. The new vester must accept this component with
+        /// `accept_migration_from` for migrations to succeed, and must vest
+        /// the same token.
+        ///
+        /// # Arguments
         ///
-        /// - [`Decimal`] - The projected value of 1 LP token at full maturity.
+        /// - `new_vester`: [`Global<IncentivesVester>`] - The vester to
+        ///   migrate to.
         ///
         /// # Panics
         ///
-        /// This method will panic if the current redemption value is 0, which
-        /// should only occur if the pool is empty.
-        pub fn get_maturity_value(&mut self) -> Decimal {
-            self.refill();
+        /// This method will panic if `new_vester` is this component.
+        pub fn authorize_migration(&mut self, new_vester: Global<IncentivesVester>) {
+            assert!(
+                new_vester.address() != Runtime::global_address(),
+                "{}",
+                VesterError::MigrationToSelf.message()
+            );
+
+            self.migration_target = Some(new_vester);
+        }
+
+        /// Accepts LP holders migrating from an old vester.
+        ///
+        /// This is the new vester's half of the migration handshake. It
+        /// allows `old_vester`, and only it, to hand over the tokens of
+        /// migrating LP holders with `accept_migrated_tokens`.
+        ///
+        /// # Arguments
+        ///
+        /// - `old_vester`: [`Global<IncentivesVester>`] - The vester to
+        ///   migrate from.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if `old_vester` is this component.
+        pub fn accept_migration_from(&mut self, old_vester: Global<IncentivesVester>) {
+            assert!(
+                old_vester.address() != Runtime::global_address(),
+                "{}",
+                VesterError::MigrationFromSelf.message()
+            );
+
+            self.migration_source = Some(old_vester);
+        }
 
-            let current_redemption_value = self.pool.get_redemption_value(Decimal::ONE);
+        /// Creates an additional vesting track with its own schedule.
+        ///
+        /// Cohorts with different durations or initial vested fractions can
+        /// vest side by side in one component this way. The component's own
+        /// schedule is track 0; the methods without a `track_id` act on it.
+        /// Every additional track gets its own OneResourcePool and LP token,
+        /// as the value of an LP token follows the schedule of its own pool.
+        /// All tracks start vesting at `vest_start` and follow the component's
+        /// vesting curve and granularity, but each has its own duration,
+        /// initial vested fraction and cliff.
+        ///
+        /// The per-account claim limit, receipts, reservations, the forfeit
+        /// policy, redemption fees and floors, migration and the accounting
+        /// getters only cover track 0. Forfeits on an additional track stay in
+        /// its pool for its remaining LP holders.
+        ///
+        /// # Arguments
+        ///
+        /// - `params`: [`TrackParams`] - The schedule of the new track.
+        ///
+        /// # Returns
+        ///
+        /// - [`u32`] - The id of the new track.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if:
+        /// - Called after `finish_setup` has been called
+        /// - The schedule is invalid, with the same checks as in `instantiate`
+        pub fn create_track(&mut self, params: TrackParams) -> u32 {
+            assert!(
+                self.vest_start.is_none(),
+                "{}",
+                VesterError::SetupAlreadyFinished.message()
+            );
+            assert!(
+                params.vest_duration_days > 0,
+                "{}",
+                VesterError::NonPositiveVestDuration.message()
+            );
+            assert!(
+                params.vest_duration_days <= MAX_VEST_DURATION_DAYS,
+                "{}",
+                VesterError::VestDurationTooLong.message()
+            );
+            assert!(
+                params.initial_vested_fraction >= Decimal::ZERO
+                    && params.initial_vested_fraction <= Decimal::ONE,
+                "{}",
+                VesterError::InvalidInitialVestedFraction.message()
+            );
+            assert!(
+                params.cliff_duration_days >= 0,
+                "{}",
+                VesterError::NegativeCliff.message()
+            );
+            assert!(
+                params.cliff_duration_days <= params.vest_duration_days,
+                "{}",
+                VesterError::CliffLongerThanVest.message()
+            );
 
-            let current_unlocked_amount = self.pool.get_vault_amount();
-            let still_locked_amount = self.locked_tokens_vault.amount();
+            let component_address = Runtime::global_address();
+            let super_admin_access_rule = rule!(
+                require(self.super_admin_badge_address)
+                    || require(global_caller(component_address))
+            );
+            let token_to_vest = self.locked_tokens_vault.resource_address();
+            let pool = Blueprint::<OneResourcePool>::instantiate(
+                OwnerRole::Fixed(super_admin_access_rule.clone()),
+                super_admin_access_rule,
+                token_to_vest,
+                None,
+            );
 
-            let final_token_amount = current_unlocked_amount + still_locked_amount;
+            let pool_unit_global_address: GlobalAddress =
+                pool.get_metadata("pool_unit").unwrap().unwrap();
+            let pool_unit_resource_address =
+                ResourceAddress::try_from(pool_unit_global_address).unwrap();
 
-            let maturity_factor = final_token_amount / current_unlocked_amount;
+            let track_id = self.track_count + 1;
+            self.tracks.insert(
+                track_id,
+                VestingTrack {
+                    params,
+                    pool,
+                    locked_tokens_vault: FungibleVault::new(token_to_vest),
+                    lp_tokens_vault: FungibleVault::new(pool_unit_resource_address),
+                    total_tokens_to_vest: Decimal::ZERO,
+                    vested_tokens: Decimal::ZERO,
+                },
+            );
+            self.track_count = track_id;
 
-            maturity_factor * current_redemption_value
+            track_id
         }
 
-        /// Returns the amount of tokens currently in the pool.
+        /// Deposits tokens to vest into a vesting track and keeps the minted
+        /// LP tokens for claiming, like `create_pool_units` does for track 0.
         ///
-        /// This method returns the amount of vested tokens that are currently
-        /// available for redemption in the pool. This amount increases over time
-        /// as tokens are vested via the `refill` method.
+        /// # Arguments
         ///
-        /// # Returns
+        /// - `track_id`: [`u32`] - The track to deposit into, 0 for the
+        ///   component's own schedule.
+        /// - `tokens_to_vest`: [`FungibleBucket`] - A bucket containing the
+        ///   tokens to vest.
         ///
-        /// - [`Decimal`] - The amount of tokens in the pool vault.
-        pub fn get_pool_vault_amount(&mut self) -> Decimal {
-            self.pool.get_vault_amount()
+        /// # Panics
+        ///
+        /// This method will panic if:
+        /// - Called after `finish_setup` has been called
+        /// - There is no track with the id
+        /// - The total to vest of all tracks would exceed `max_total_to_vest`
+        pub fn create_track_pool_units(&mut self, track_id: u32, tokens_to_vest: FungibleBucket) {
+            if track_id == 0 {
+                self.create_pool_units(tokens_to_vest);
+                return;
+            }
+
+            assert!(
+                self.vest_start.is_none(),
+                "{}",
+                VesterError::SetupAlreadyFinished.message()
+            );
+
+            {
+                let mut track = self
+                    .tracks
+                    .get_mut(&track_id)
+                    .unwrap_or_else(|| panic!("{}", VesterError::UnknownTrack.message()));
+
+                // Track the amount of tokens the pool actually received
+                let pool_amount_before = track.pool.get_vault_amount();
+                let lp_tokens = track.pool.contribute(tokens_to_vest);
+                track.total_tokens_to_vest += track.pool.get_vault_amount() - pool_amount_before;
+                track.lp_tokens_vault.put(lp_tokens);
+            }
+
+            self.assert_within_max_total_to_vest();
         }
 
-        /// Returns the amount of tokens still locked (not yet vested).
+        // endregion:Super Admin Methods
+
+        // region:Admin Methods
+
+        /// Claims LP tokens for a user and deposits them into their account.
         ///
-        /// This method returns the amount of tokens in the locked vault that
-        /// have not yet been vested into the pool. This amount decreases over
-        /// time as tokens are vested via the `refill` method.
+        /// This method distributes LP tokens to a user's account during the
+        /// pre-claim period or after vesting has started. The LP tokens are
+        /// deposited using the AccountLocker, which acts as a mailbox for accounts
+        /// that have deposit restrictions. If the account doesn't allow direct
+        /// deposits, the tokens are stored in the locker where the user can claim
+        /// them.
+        ///
+        /// Alongside the LP tokens, a receipt NFT recording the claimed amount,
+        /// the claim time and the season is minted and delivered the same way.
+        /// If `receipt_data` is given, it is recorded on the new receipt and on
+        /// every earlier receipt of the account. Otherwise the new receipt
+        /// carries over the account's current receipt data, if any.
+        ///
+        /// This method is typically called by a backend service that holds the
+        /// admin badge and distributes rewards to users based on their activity
+        /// or participation in an incentives program.
+        ///
+        /// # Arguments
+        ///
+        /// - `lp_token_amount`: [`Decimal`] - The amount of LP tokens to claim
+        ///   for the user. Must be greater than zero.
+        /// - `account_address`: [`Global<Account>`] - The account address where
+        ///   the LP tokens will be deposited.
+        /// - `receipt_data`: [`Option<ReceiptData>`] - The recipient's unlock
+        ///   schedule to record on their receipts, or `None` to keep it as is.
         ///
         /// # Returns
         ///
-        /// - [`Decimal`] - The amount of locked tokens.
-        pub fn get_locked_vault_amount(&mut self) -> Decimal {
-            self.locked_tokens_vault.amount()
+        /// - [`Decimal`] - The amount of LP tokens stored for the account.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if:
+        /// - Called before `finish_setup` has been called
+        /// - `lp_token_amount` is zero or negative
+        /// - The vault doesn't hold `lp_token_amount` LP tokens
+        /// - The account's cumulative claims would exceed `max_claim_per_account`
+        /// - The claim deadline has passed
+        pub fn claim(
+            &mut self,
+            lp_token_amount: Decimal,
+            account_address: Global<Account>,
+            receipt_data: Option<ReceiptData>,
+        ) -> Decimal {
+            assert!(
+                self.vest_start.is_some(),
+                "{}",
+                VesterError::SetupNotFinished.message()
+            );
+
+            assert!(
+                lp_token_amount > Decimal::ZERO,
+                "{}",
+                VesterError::NonPositiveLpAmount.message()
+            );
+
+            self.deliver_claim(lp_token_amount, account_address, receipt_data);
+
+            lp_token_amount
         }
 
-        /// Returns the resource address of the LP tokens.
+        /// Claims up to the given amount of LP tokens for a user.
         ///
-        /// This method returns the resource address of the LP tokens that are
-        /// minted by the pool and represent claims to vested tokens. Users need
-        /// this address to identify their LP tokens in their wallets.
+        /// This works like `claim`, but if the vault holds fewer LP tokens
+        /// than requested, it delivers whatever is left instead of panicking.
+        /// Distribution scripts can use it to drain a partially depleted vault
+        /// and learn from the return value how much was actually stored. If
+        /// the vault is empty, nothing is delivered and no receipt is minted.
+        ///
+        /// # Arguments
+        ///
+        /// - `lp_token_amount`: [`Decimal`] - The amount of LP tokens to claim
+        ///   at most. Must be greater than zero.
+        /// - `account_address`: [`Global<Account>`] - The account address where
+        ///   the LP tokens will be deposited.
         ///
         /// # Returns
         ///
-        /// - [`ResourceAddress`] - The resource address of the LP tokens.
-        pub fn get_pool_unit_resource_address(&self) -> ResourceAddress {
-            self.lp_tokens_vault.resource_address()
+        /// - [`Decimal`] - The amount of LP tokens stored for the account,
+        ///   `min(lp_token_amount, vault amount)`.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if:
+        /// - Called before `finish_setup` has been called
+        /// - `lp_token_amount` is zero or negative
+        /// - The account's cumulative claims would exceed `max_claim_per_account`
+        pub fn claim_up_to(
+            &mut self,
+            lp_token_amount: Decimal,
+            account_address: Global<Account>,
+        ) -> Decimal {
+            assert!(
+                self.vest_start.is_some(),
+                "{}",
+                VesterError::SetupNotFinished.message()
+            );
+
+            assert!(
+                lp_token_amount > Decimal::ZERO,
+                "{}",
+                VesterError::NonPositiveLpAmount.message()
+            );
+
+            let claimed_amount = lp_token_amount.min(self.lp_tokens_vault.amount());
+            if claimed_amount > Decimal::ZERO {
+                self.deliver_claim(claimed_amount, account_address, None);
+            }
+
+            claimed_amount
         }
 
-        /// Returns the current redemption value for a given amount of LP tokens.
-        ///
-        /// This method calculates how many tokens would be received if the
-        /// specified amount of LP tokens were redeemed at the current time.
-        /// The value depends on how much has vested so far and how many LP
-        /// tokens have already been redeemed.
+        /// Claims an LP bucket for a user and deposits it into their account.
         ///
-        /// Note that this returns the value at the current moment. To get an
-        /// up-to-date value that includes the latest vesting progress, call
-        /// `refill` first or use this after a `redeem` call (which automatically
-        /// calls `refill`).
+        /// This method behaves like `claim`, but delivers LP tokens the caller
+        /// brings along instead of taking them from the component's vault. It
+        /// allows distributors to withdraw LP tokens with `remove_lp`, split
+        /// them off-component and hand specific buckets to accounts, while
+        /// still going through the AccountLocker, the receipt NFT and the
+        /// `max_claim_per_account` check.
         ///
         /// # Arguments
         ///
-        /// - `lp_amount`: [`Decimal`] - The amount of LP tokens to calculate
-        ///   the redemption value for.
+        /// - `lp_bucket`: [`FungibleBucket`] - A bucket containing the LP tokens
+        ///   to claim for the user.
+        /// - `account_address`: [`Global<Account>`] - The account address where
+        ///   the LP tokens will be deposited.
         ///
-        /// # Returns
+        /// # Panics
         ///
-        /// - [`Decimal`] - The amount of tokens that would be received for
-        ///   redeeming the specified amount of LP tokens.
-        pub fn get_pool_redemption_value(&self, lp_amount: Decimal) -> Decimal {
-            self.pool.get_redemption_value(lp_amount)
+        /// This method will panic if:
+        /// - Called before `finish_setup` has been called
+        /// - The bucket doesn't contain this component's LP tokens
+        /// - The bucket is empty
+        /// - The account's cumulative claims would exceed `max_claim_per_account`
+        pub fn claim_bucket(
+            &mut self,
+            lp_bucket: FungibleBucket,
+            account_address: Global<Account>,
+        ) {
+            assert!(
+                self.vest_start.is_some(),
+                "{}",
+                VesterError::SetupNotFinished.message()
+            );
+
+            assert!(
+                self.is_redeemable_resource(lp_bucket.resource_address()),
+                "{}",
+                VesterError::NotLpTokensOfComponent.message()
+            );
+            assert!(
+                lp_bucket.amount() > Decimal::ZERO,
+                "{}",
+                VesterError::NonPositiveLpAmount.message()
+            );
+
+            self.deliver_lp_tokens(lp_bucket, account_address, None);
         }
 
-        /// Returns the total amount of tokens that have been vested so far.
+        /// Claims LP tokens for many users in a single transaction.
         ///
-        /// This method returns the cumulative amount of tokens that have been
-        /// moved from the locked vault into the pool through the `refill` method.
-        /// This value increases over time and approaches `total_tokens_to_vest`
-        /// as vesting progresses.
+        /// This method behaves like calling `claim` once per entry, but saves
+        /// the per-transaction fee when distributing to many accounts. Every
+        /// entry gets its own receipt NFT and `ClaimEvent`.
         ///
-        /// # Returns
+        /// All entries are validated before anything is delivered, so a batch
+        /// either reaches every account or fails as a whole with a clear
+        /// message.
         ///
-        /// - [`Decimal`] - The amount of tokens that have been vested.
-        pub fn get_vested_tokens(&self) -> Decimal {
-            self.vested_tokens
+        /// The practical batch size is limited by the transaction's cost unit
+        /// limit, as every entry stores LP tokens and a receipt in the locker.
+        /// This allows a few dozen entries per transaction; preview large
+        /// batches before submitting them and split them up when needed.
+        ///
+        /// # Arguments
+        ///
+        /// - `claims`: [`Vec<(Decimal, Global<Account>)>`] - The amount of LP
+        ///   tokens to claim and the receiving account, for every user.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if:
+        /// - Called before `finish_setup` has been called
+        /// - Any amount is zero or negative
+        /// - The vault doesn't hold enough LP tokens for all entries combined
+        /// - Any account's cumulative claims would exceed `max_claim_per_account`
+        pub fn claim_batch(&mut self, claims: Vec<(Decimal, Global<Account>)>) {
+            assert!(
+                self.vest_start.is_some(),
+                "{}",
+                VesterError::SetupNotFinished.message()
+            );
+
+            let mut total_lp_amount = Decimal::ZERO;
+            for (lp_token_amount, _) in claims.iter() {
+                assert!(
+                    *lp_token_amount > Decimal::ZERO,
+                    "{}",
+                    VesterError::NonPositiveLpAmount.message()
+                );
+                total_lp_amount += *lp_token_amount;
+            }
+            assert!(
+                total_lp_amount <= self.lp_tokens_vault.amount(),
+                "{}",
+                VesterError::NotEnoughLpTokensForBatch.message()
+            );
+
+            for (lp_token_amount, account_address) in claims {
+                self.deliver_claim(lp_token_amount, account_address, None);
+            }
         }
 
-        /// Returns the total amount of tokens that will be vested over the
-        /// entire vesting period.
+        /// Splits an amount of LP tokens across many users by weight.
         ///
-        /// This method returns the total amount of tokens that were deposited
-        /// via `create_pool_units` during the setup phase. This value is set
-        /// during setup and remains constant throughout the vesting period.
+        /// Every recipient gets `total * weight / sum_of_weights` LP tokens,
+        /// rounded down, and the last recipient also gets the rounding dust,
+        /// so exactly `total` LP tokens are delivered. Otherwise this behaves
+        /// like `claim_batch`, including its limit on the practical batch
+        /// size.
         ///
-        /// # Returns
+        /// # Arguments
         ///
-        /// - [`Decimal`] - The total amount of tokens to vest.
-        pub fn get_total_tokens_to_vest(&self) -> Decimal {
-            self.total_tokens_to_vest
+        /// - `total`: [`Decimal`] - The amount of LP tokens to distribute.
+        /// - `recipients`: [`Vec<(Global<Account>, Decimal)>`] - The receiving
+        ///   account and its weight, for every user. Weights only matter
+        ///   relative to each other.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if:
+        /// - Called before `finish_setup` has been called
+        /// - `total` is zero or negative
+        /// - `recipients` is empty or any weight is zero or negative
+        /// - The vault doesn't hold `total` LP tokens
+        /// - Any account's cumulative claims would exceed `max_claim_per_account`
+        pub fn claim_weighted(
+            &mut self,
+            total: Decimal,
+            recipients: Vec<(Global<Account>, Decimal)>,
+        ) {
+            assert!(
+                self.vest_start.is_some(),
+                "{}",
+                VesterError::SetupNotFinished.message()
+            );
+            assert!(
+                total > Decimal::ZERO,
+                "{}",
+                VesterError::NonPositiveLpAmount.message()
+            );
+            assert!(
+                !recipients.is_empty(),
+                "{}",
+                VesterError::NoRecipients.message()
+            );
+
+            let mut total_weight = Decimal::ZERO;
+            for (_, weight) in recipients.iter() {
+                assert!(
+                    *weight > Decimal::ZERO,
+                    "{}",
+                    VesterError::NonPositiveWeight.message()
+                );
+                total_weight += *weight;
+            }
+            assert!(
+                total <= self.lp_tokens_vault.amount(),
+                "{}",
+                VesterError::NotEnoughLpTokensForBatch.message()
+            );
+
+            let last_index = recipients.len() - 1;
+            let mut delivered = Decimal::ZERO;
+            for (index, (account_address, weight)) in recipients.into_iter().enumerate() {
+                let lp_token_amount = if index == last_index {
+                    total - delivered
+                } else {
+                    total * weight / total_weight
+                };
+                delivered += lp_token_amount;
+                self.deliver_claim(lp_token_amount, account_address, None);
+            }
         }
 
-        // endregion:Public Methods
+        /// Claims LP tokens for a user up to a cumulative total.
+        ///
+        /// This method only delivers the difference between `total_lp_amount`
+        /// and what has already been claimed for the account, and does nothing
+        /// if the account already received at least that much. A backend that
+        /// crashed mid-distribution can therefore simply replay its claims
+        /// without paying anyone twice.
+        ///
+        /// # Arguments
+        ///
+        /// - `total_lp_amount`: [`Decimal`] - The cumulative amount of LP
+        ///   tokens the account should have received in total.
+        /// - `account_address`: [`Global<Account>`] - The account address where
+        ///   the LP tokens will be deposited.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if:
+        /// - Called before `finish_setup` has been called
+        /// - `total_lp_amount` exceeds `max_claim_per_account`
+        pub fn claim_idempotent(
+            &mut self,
+            total_lp_amount: Decimal,
+            account_address: Global<Account>,
+        ) {
+            assert!(
+                self.vest_start.is_some(),
+                "{}",
+                VesterError::SetupNotFinished.message()
+            );
+
+            let already_claimed = self.get_claimed_amount(account_address);
+            if already_claimed >= total_lp_amount {
+                return;
+            }
+
+            self.deliver_claim(total_lp_amount - already_claimed, account_address, None);
+        }
+
+        /// Sets LP tokens aside for an account until the claim is confirmed.
+        ///
+        /// The LP tokens are moved out of the unclaimed LP vault, so other
+        /// claims cannot hand them out, but nothing is delivered yet. Once the
+        /// account has confirmed, `finalize_claim` delivers them like `claim`.
+        /// If that doesn't happen before `expiry`, `release_expired` returns
+        /// them to the vault.
+        ///
+        /// # Arguments
+        ///
+        /// - `lp_token_amount`: [`Decimal`] - The amount of LP tokens to
+        ///   reserve. Must be greater than zero.
+        /// - `account_address`: [`Global<Account>`] - The account the LP tokens
+        ///   are reserved for.
+        /// - `expiry`: [`Instant`] - The instant from which the reservation can
+        ///   no longer be finalized. Must be in the future.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if:
+        /// - Called before `finish_setup` has been called
+        /// - `lp_token_amount` is zero or negative
+        /// - `expiry` is not in the future
+        /// - The account already has an open reservation
+        /// - The allowlist is enabled and the account is not on it
+        /// - The account's claimed LP tokens plus `lp_token_amount` would
+        ///   exceed `max_claim_per_account`
+        /// - The vault doesn't hold `lp_token_amount` LP tokens
+        pub fn reserve_claim(
+            &mut self,
+            lp_token_amount: Decimal,
+            account_address: Global<Account>,
+            expiry: Instant,
+        ) {
+            assert!(
+                self.vest_start.is_some(),
+                "{}",
+                VesterError::SetupNotFinished.message()
+            );
+            assert!(
+                lp_token_amount > Decimal::ZERO,
+                "{}",
+                VesterError::NonPositiveLpAmount.message()
+            );
+            assert!(
+                Clock::current_time_is_strictly_before(expiry, TimePrecision::Second),
+                "{}",
+                VesterError::ReservationExpiryNotInFuture.message()
+            );
+            assert!(
+                self.claims_open(),
+                "{}",
+                VesterError::ClaimDeadlinePassed.message()
+            );
+            assert!(
+                self.reservations.get(&account_address).is_none(),
+                "{}",
+                VesterError::ReservationAlreadyOpen.message()
+            );
+            assert!(
+                self.claim_target_allowed(account_address),
+                "{}",
+                VesterError::AccountNotAllowed.message()
+            );
+            if let Some(max_claim) = self.max_claim_per_account {
+                assert!(
+                    self.get_claimed_amount(account_address) + lp_token_amount <= max_claim,
+                    "{}",
+                    VesterError::ClaimLimitExceeded.message()
+                );
+            }
+
+            let lp_tokens = self.lp_tokens_vault.take(lp_token_amount);
+            self.reserved_lp_vault.put(lp_tokens);
+            self.reservations.insert(
+                account_address,
+                ClaimReservation {
+                    lp_amount: lp_token_amount,
+                    expires_at: expiry,
+                },
+            );
+        }
+
+        /// Delivers the LP tokens reserved for an account.
+        ///
+        /// The reserved LP tokens and a receipt NFT are stored in the locker
+        /// for the account, exactly as `claim` would, and the reservation is
+        /// closed.
+        ///
+        /// # Arguments
+        ///
+        /// - `account_address`: [`Global<Account>`] - The account whose
+        ///   reservation to finalize.
+        ///
+        /// # Returns
+        ///
+        /// - [`Decimal`] - The amount of LP tokens stored for the account.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if:
+        /// - The account has no open reservation
+        /// - The reservation has expired
+        /// - The account's cumulative claims would exceed `max_claim_per_account`
+        pub fn finalize_claim(&mut self, account_address: Global<Account>) -> Decimal {
+            let reservation = self
+                .reservations
+                .remove(&account_address)
+                .unwrap_or_else(|| panic!("{}", VesterError::NoOpenReservation.message()));
+            assert!(
+                Clock::current_time_is_strictly_before(
+                    reservation.expires_at,
+                    TimePrecision::Second
+                ),
+                "{}",
+                VesterError::ReservationExpired.message()
+            );
+
+            let lp_tokens = self.reserved_lp_vault.take(reservation.lp_amount);
+            self.deliver_lp_tokens(lp_tokens, account_address, None);
+
+            reservation.lp_amount
+        }
+
+        /// Claims LP tokens of a vesting track for a user.
+        ///
+        /// This works like `claim` for track 0. For an additional track, the
+        /// LP tokens are delivered through the locker as well, subject to the
+        /// claim deadline and the allowlist, but without a receipt and without
+        /// counting towards `max_claim_per_account`.
+        ///
+        /// # Arguments
+        ///
+        /// - `track_id`: [`u32`] - The track whose LP tokens to claim, 0 for
+        ///   the component's own schedule.
+        /// - `lp_token_amount`: [`Decimal`] - The amount of LP tokens to claim
+        ///   for the user. Must be greater than zero.
+        /// - `account_address`: [`Global<Account>`] - The account address where
+        ///   the LP tokens will be deposited.
+        ///
+        /// # Returns
+        ///
+        /// - [`Decimal`] - The amount of LP tokens stored for the account.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if:
+        /// - Called before `finish_setup` has been called
+        /// - There is no track with the id
+        /// - `lp_token_amount` is zero or negative
+        /// - The track's vault doesn't hold `lp_token_amount` LP tokens
+        /// - The claim deadline has passed
+        /// - The allowlist is enabled and the account is not on it
+        pub fn claim_track(
+            &mut self,
+            track_id: u32,
+            lp_token_amount: Decimal,
+            account_address: Global<Account>,
+        ) -> Decimal {
+            if track_id == 0 {
+                return self.claim(lp_token_amount, account_address, None);
+            }
+
+            assert!(
+                self.vest_start.is_some(),
+                "{}",
+                VesterError::SetupNotFinished.message()
+            );
+            assert!(
+                lp_token_amount > Decimal::ZERO,
+                "{}",
+                VesterError::NonPositiveLpAmount.message()
+            );
+            assert!(
+                self.claims_open(),
+                "{}",
+                VesterError::ClaimDeadlinePassed.message()
+            );
+            assert!(
+                self.claim_target_allowed(account_address),
+                "{}",
+                VesterError::AccountNotAllowed.message()
+            );
+
+            let lp_tokens = self
+                .tracks
+                .get_mut(&track_id)
+                .unwrap_or_else(|| panic!("{}", VesterError::UnknownTrack.message()))
+                .lp_tokens_vault
+                .take(lp_token_amount);
+            self.locker.store(account_address, lp_tokens.into(), true);
+
+            Runtime::emit_event(ClaimEvent {
+                account: account_address,
+                lp_amount: lp_token_amount,
+            });
+
+            lp_token_amount
+        }
+
+        // endregion:Admin Methods
+
+        // region:Recovery Methods
+
+        /// Recovers LP tokens that are stuck in the AccountLocker for an account.
+        ///
+        /// When `claim` cannot deposit LP tokens directly into an account, they
+        /// are stored in the locker until the user claims them. If the account
+        /// becomes permanently inaccessible, those tokens would be stranded.
+        /// This emergency function takes them back out of the locker and
+        /// returns them to the holder of the recovery badge.
+        ///
+        /// Recovery is authorized by its own badge rather than the super admin
+        /// badge, so the badge that controls the vesting pool cannot also take
+        /// tokens out of users' mailboxes.
+        ///
+        /// # Arguments
+        ///
+        /// - `account`: [`Global<Account>`] - The account the LP tokens were
+        ///   stored for.
+        /// - `amount`: [`Decimal`] - The amount of LP tokens to recover.
+        ///
+        /// # Returns
+        ///
+        /// - [`FungibleBucket`] - A bucket containing the recovered LP tokens.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if the locker holds less than `amount` LP
+        /// tokens for the account.
+        pub fn recover_from_locker(
+            &mut self,
+            account: Global<Account>,
+            amount: Decimal,
+        ) -> FungibleBucket {
+            self.locker
+                .recover(account, self.lp_tokens_vault.resource_address(), amount)
+                .as_fungible()
+        }
+
+        // endregion:Recovery Methods
+
+        // region:Public Methods
+
+        /// Returns the LP tokens of an expired reservation to the vault.
+        ///
+        /// Anyone can call this once a reservation has expired without being
+        /// finalized, so that LP tokens of unconfirmed claims don't stay tied
+        /// up. The account can be reserved for again afterwards.
+        ///
+        /// # Arguments
+        ///
+        /// - `account_address`: [`Global<Account>`] - The account whose
+        ///   reservation to release.
+        ///
+        /// # Returns
+        ///
+        /// - [`Decimal`] - The amount of LP tokens returned to the vault.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if:
+        /// - The account has no open reservation
+        /// - The reservation has not expired yet
+        pub fn release_expired(&mut self, account_address: Global<Account>) -> Decimal {
+            let reservation = self
+                .reservations
+                .remove(&account_address)
+                .unwrap_or_else(|| panic!("{}", VesterError::NoOpenReservation.message()));
+            assert!(
+                Clock::current_time_is_at_or_after(reservation.expires_at, TimePrecision::Second),
+                "{}",
+                VesterError::ReservationNotExpired.message()
+            );
+
+            let lp_tokens = self.reserved_lp_vault.take(reservation.lp_amount);
+            self.lp_tokens_vault.put(lp_tokens);
+
+            reservation.lp_amount
+        }
+
+        /// Moves vested tokens from the locked vault into the pool.
+        ///
+        /// This method calculates how many tokens should have vested based on
+        /// the current time and the vesting schedule, then moves those tokens
+        /// from the locked vault into the pool, making them available for
+        /// redemption.
+        ///
+        /// The vesting calculation uses the configured curve with an initial
+        /// vested fraction:
+        /// - Before `cliff_end`: Nothing is available
+        /// - At `vest_start` (0% progress): `initial_vested_fraction` is available
+        /// - During vesting: Interpolation between initial and 100% along the curve
+        /// - At `vest_end` (100% progress): All tokens are available
+        ///
+        /// Formula: `vested_fraction = initial_vested_fraction + (1 - initial_vested_fraction) * curve(progress)`
+        ///
+        /// where `curve(progress)` is `progress` for `Linear`, `progress^2` for
+        /// `Quadratic` and `progress^0.5` for `SquareRoot`.
+        ///
+        /// Progress is always measured from `vest_start`, so when the cliff ends
+        /// everything that accrued during the cliff becomes available at once.
+        ///
+        /// A refill never takes more than what is left in the locked vault, and
+        /// once `vest_end` has passed it moves the locked vault over entirely.
+        ///
+        /// This method is idempotent - calling it multiple times at the same
+        /// point in time will not move additional tokens. It automatically gets
+        /// called during `redeem`, but can also be called manually to update
+        /// the pool and show accurate LP token values in wallets.
+        ///
+        /// As anyone can call it, a refill within the same clock second as the
+        /// last one returns right away without touching the pool or the
+        /// vaults.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if:
+        /// - Called before `finish_setup` has been called
+        /// - Called during the pre-claim period (before `vest_start`)
+        pub fn refill(&mut self) {
+            if let Some(vest_start) = self.vest_start {
+                assert!(
+                    Clock::current_time_is_at_or_after(vest_start, TimePrecision::Second),
+                    "{}",
+                    VesterError::StillInPreClaim.message()
+                );
+            } else {
+                panic!("{}", VesterError::SetupNotFinished.message());
+            }
+
+            let now = Clock::current_time_rounded_to_seconds();
+            if self.last_refill_at == Some(now) {
+                return;
+            }
+
+            self.refill_at(now);
+            self.last_refill_at = Some(now);
+        }
+
+        /// Moves the tokens vested as of a past instant into the pool.
+        ///
+        /// This works like `refill`, but evaluates the vesting schedule at
+        /// `instant` instead of the current time. Automated keepers can use it
+        /// to catch up a schedule that has not been refilled for a while in
+        /// controlled increments, so the pool reflects each point in time.
+        ///
+        /// Refilling to an instant at or before the last refill moves nothing,
+        /// as the vested amount never decreases.
+        ///
+        /// # Arguments
+        ///
+        /// - `instant`: [`Instant`] - The instant to refill to. Must not be
+        ///   in the future.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if:
+        /// - Called before `finish_setup` has been called
+        /// - Called during the pre-claim period (before `vest_start`)
+        /// - `instant` is in the future
+        pub fn refill_to(&mut self, instant: Instant) {
+            if let Some(vest_start) = self.vest_start {
+                assert!(
+                    Clock::current_time_is_at_or_after(vest_start, TimePrecision::Second),
+                    "{}",
+                    VesterError::StillInPreClaim.message()
+                );
+            } else {
+                panic!("{}", VesterError::SetupNotFinished.message());
+            }
+            assert!(
+                Clock::current_time_is_at_or_after(instant, TimePrecision::Second),
+                "{}",
+                VesterError::FutureRefill.message()
+            );
+
+            self.refill_at(instant);
+        }
+
+        /// Redeems LP tokens for the vested portion of the underlying tokens.
+        ///
+        /// This method allows users to exchange their LP tokens for the tokens
+        /// that have vested so far. Users receive a proportional share of the
+        /// currently vested tokens based on their LP token amount, and forfeit
+        /// their claim to any unvested tokens.
+        ///
+        /// The redemption value is calculated by the OneResourcePool based on the
+        /// ratio of vested tokens in the pool to the total LP token supply. When
+        /// users redeem early (before 100% vesting), they forfeit their unvested
+        /// portion, which remains in the pool and increases the maturity value for
+        /// remaining LP token holders.
+        ///
+        /// This method automatically calls `refill` before redemption to ensure
+        /// the pool is up-to-date with the current vesting progress.
+        ///
+        /// If nothing has vested yet (e.g. during the cliff), there is nothing
+        /// to redeem. The redemption then yields zero tokens and forfeits
+        /// nothing, and the LP tokens are handed back untouched instead of
+        /// being burned for zero tokens.
+        ///
+        /// Before `vest_end`, `redemption_fee_fraction` of the redeemed tokens
+        /// is kept in the treasury as a redemption fee. The fee does not count
+        /// as forfeited.
+        ///
+        /// With the `Treasury` forfeit policy, the redeemer's share of the
+        /// locked vault is moved to the treasury. `total_tokens_to_vest` and
+        /// `vested_tokens` shrink by the same fraction as the locked vault, so
+        /// the remaining tokens keep vesting along the original schedule and
+        /// the maturity value of the remaining LP holders stays unchanged.
+        ///
+        /// If `min_redemption_value` is set and the redemption value of 1 LP
+        /// token has fallen below it after the refill, the redemption is
+        /// aborted. As this rolls back the whole transaction, redemptions stay
+        /// halted for as long as the value remains below the floor.
+        ///
+        /// # Arguments
+        ///
+        /// - `lp_token_bucket`: [`FungibleBucket`] - A bucket containing the LP
+        ///   tokens to redeem. Must contain at least some amount.
+        ///
+        /// # Returns
+        ///
+        /// - [`FungibleBucket`] - A bucket containing the vested tokens received
+        ///   in exchange for the LP tokens minus the redemption fee.
+        /// - [`Decimal`] - The amount of tokens forfeited by redeeming now
+        ///   instead of at full maturity: the redeemer's share of all remaining
+        ///   tokens minus the tokens received. Without earlier redemptions this
+        ///   equals `lp_fraction * total_tokens_to_vest - tokens_received`.
+        /// - [`FungibleBucket`] - A bucket containing the LP tokens that were
+        ///   not redeemed, i.e. all of them if nothing has vested yet and none
+        ///   otherwise.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if:
+        /// - Redemptions are paused
+        /// - The bucket doesn't contain this component's LP tokens
+        /// - The LP token bucket is empty (contains zero tokens)
+        /// - The LP token bucket contains less than `min_redeem_lp`
+        /// - The redemption value of 1 LP token is below `min_redemption_value`
+        pub fn redeem(
+            &mut self,
+            lp_token_bucket: FungibleBucket,
+        ) -> (FungibleBucket, Decimal, FungibleBucket) {
+            assert!(!self.paused, "{}", VesterError::RedemptionsPaused.message());
+            assert!(
+                self.is_redeemable_resource(lp_token_bucket.resource_address()),
+                "{}",
+                VesterError::NotLpToken.message()
+            );
+            assert!(
+                lp_token_bucket.amount() > Decimal::ZERO,
+                "{}",
+                VesterError::EmptyBucket.message()
+            );
+            assert!(
+                lp_token_bucket.amount() >= self.min_redeem_lp,
+                "{}",
+                VesterError::BelowMinRedemptionAmount.message()
+            );
+            self.refill();
+
+            // The pool refuses to redeem for zero tokens, which is what any
+            // redemption during the cliff would amount to, so hand the LP
+            // tokens back instead
+            if self.pool.get_vault_amount() == Decimal::ZERO {
+                let no_tokens = FungibleBucket::new(self.locked_tokens_vault.resource_address());
+                return (no_tokens, Decimal::ZERO, lp_token_bucket);
+            }
+
+            if let Some(min_value) = self.min_redemption_value {
+                let redemption_value = self.pool.get_redemption_value(Decimal::ONE);
+                assert!(
+                    redemption_value >= min_value,
+                    "{} Value: {}, floor: {}",
+                    VesterError::RedemptionValueBelowFloor.message(),
+                    redemption_value,
+                    min_value
+                );
+            }
+
+            // The redeemer's share of everything that will eventually vest
+            let lp_burned = lp_token_bucket.amount();
+            let lp_share = lp_burned / self.lp_total_supply();
+            let maturity_claim =
+                lp_share * (self.pool.get_vault_amount() + self.locked_tokens_vault.amount());
+
+            let mut redeemed_tokens = self.pool.redeem(lp_token_bucket);
+
+            let forfeited = maturity_claim - redeemed_tokens.amount();
+            self.forfeited_tokens += forfeited;
+            self.total_redeemed += redeemed_tokens.amount();
+
+            if self.forfeit_policy == ForfeitPolicy::Treasury {
+                self.forfeit_locked_to_treasury(lp_share);
+            }
+
+            let fee = self.redemption_fee(redeemed_tokens.amount());
+            if fee > Decimal::ZERO {
+                self.treasury_vault.put(redeemed_tokens.take(fee));
+            }
+
+            Runtime::emit_event(RedeemEvent {
+                lp_burned,
+                tokens_returned: redeemed_tokens.amount(),
+                fee,
+            });
+
+            let no_lp_tokens = FungibleBucket::new(self.lp_tokens_vault.resource_address());
+            (redeemed_tokens, forfeited, no_lp_tokens)
+        }
+
+        /// Redeems LP tokens and delivers the vested tokens to an account.
+        ///
+        /// This works like `redeem`, but instead of returning the tokens to
+        /// the caller, they are stored for `account_address` via the
+        /// AccountLocker, the same way claims are delivered. Accounts with
+        /// deposit restrictions can then claim them from the locker.
+        ///
+        /// `account_address` can be any account, so custodians can redeem on
+        /// behalf of a beneficiary. The LP tokens in the bucket are all the
+        /// authorization needed.
+        ///
+        /// If nothing has vested yet, nothing is stored and the LP tokens are
+        /// handed back to the caller, as with `redeem`.
+        ///
+        /// # Arguments
+        ///
+        /// - `lp_bucket`: [`FungibleBucket`] - A bucket containing the LP
+        ///   tokens to redeem.
+        /// - `account_address`: [`Global<Account>`] - The account address where
+        ///   the vested tokens will be deposited.
+        ///
+        /// # Returns
+        ///
+        /// - [`FungibleBucket`] - A bucket containing the LP tokens that were
+        ///   not redeemed.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic in any case where `redeem` panics.
+        pub fn redeem_to(
+            &mut self,
+            lp_bucket: FungibleBucket,
+            account_address: Global<Account>,
+        ) -> FungibleBucket {
+            let (redeemed_tokens, _, unredeemed_lp) = self.redeem(lp_bucket);
+            if redeemed_tokens.amount() > Decimal::ZERO {
+                self.locker
+                    .store(account_address, redeemed_tokens.into(), true);
+            } else {
+                redeemed_tokens.drop_empty();
+            }
+
+            unredeemed_lp
+        }
+
+        /// Accepts tokens of an unrelated resource that were meant for the
+        /// component by mistake.
+        ///
+        /// A component only holds resources it has vaults for and cannot be
+        /// sent arbitrary tokens like an account. Tokens of any other resource
+        /// are kept here until the super admin takes them back out with
+        /// `rescue_tokens`.
+        ///
+        /// # Arguments
+        ///
+        /// - `tokens`: [`Bucket`] - A bucket of the stray tokens.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if the tokens are the token to vest or the
+        /// LP tokens, which have their own deposit methods.
+        pub fn deposit_stray(&mut self, tokens: Bucket) {
+            let resource = tokens.resource_address();
+            assert!(
+                resource != self.locked_tokens_vault.resource_address()
+                    && resource != self.lp_tokens_vault.resource_address(),
+                "{}",
+                VesterError::UseDedicatedDeposit.message()
+            );
+
+            if let Some(mut vault) = self.stray_vaults.get_mut(&resource) {
+                vault.put(tokens);
+                return;
+            }
+            self.stray_vaults
+                .insert(resource, Vault::with_bucket(tokens));
+        }
+
+        /// Redeems LP tokens and aborts if fewer tokens than expected come
+        /// out.
+        ///
+        /// This works like `redeem`, but protects the caller against the
+        /// value of the LP tokens changing between quoting the redemption
+        /// (e.g. with `preview_redeem`) and executing it. If the redemption
+        /// yields less than `min_out`, the whole transaction fails instead of
+        /// executing at a worse rate. If nothing has vested yet, the LP tokens
+        /// are handed back as with `redeem`, which only passes for a `min_out`
+        /// of zero.
+        ///
+        /// # Arguments
+        ///
+        /// - `lp_bucket`: [`FungibleBucket`] - A bucket containing the LP
+        ///   tokens to redeem.
+        /// - `min_out`: [`Decimal`] - The minimum amount of tokens the
+        ///   redemption must yield, after the redemption fee.
+        ///
+        /// # Returns
+        ///
+        /// - [`FungibleBucket`] - A bucket containing the vested tokens received.
+        /// - [`FungibleBucket`] - A bucket containing the LP tokens that were
+        ///   not redeemed.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if the redemption yields less than `min_out`,
+        /// or in any case where `redeem` panics.
+        pub fn redeem_with_min_out(
+            &mut self,
+            lp_bucket: FungibleBucket,
+            min_out: Decimal,
+        ) -> (FungibleBucket, FungibleBucket) {
+            let (redeemed_tokens, _, unredeemed_lp) = self.redeem(lp_bucket);
+            assert!(
+                redeemed_tokens.amount() >= min_out,
+                "{}",
+                VesterError::MinAmountOutNotMet.message()
+            );
+
+            (redeemed_tokens, unredeemed_lp)
+        }
+
+        /// Redeems part of a bucket of LP tokens and hands back the rest.
+        ///
+        /// This works like `redeem`, but only `amount` of the LP tokens in the
+        /// bucket are redeemed. It saves users from splitting the bucket in
+        /// their manifest first. If nothing has vested yet, no tokens are
+        /// returned and the LP tokens are all handed back.
+        ///
+        /// # Arguments
+        ///
+        /// - `lp_bucket`: [`FungibleBucket`] - A bucket containing LP tokens.
+        /// - `amount`: [`Decimal`] - The amount of LP tokens to redeem.
+        ///
+        /// # Returns
+        ///
+        /// - [`FungibleBucket`] - A bucket containing the vested tokens received.
+        /// - [`FungibleBucket`] - A bucket containing the LP tokens that were
+        ///   not redeemed.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if `amount` exceeds the LP tokens in the
+        /// bucket, or in any case where `redeem` panics.
+        pub fn redeem_amount(
+            &mut self,
+            mut lp_bucket: FungibleBucket,
+            amount: Decimal,
+        ) -> (FungibleBucket, FungibleBucket) {
+            assert!(
+                amount <= lp_bucket.amount(),
+                "{}",
+                VesterError::AmountExceedsLpBucket.message()
+            );
+
+            let (redeemed_tokens, _, unredeemed_lp) = self.redeem(lp_bucket.take(amount));
+            lp_bucket.put(unredeemed_lp);
+            (redeemed_tokens, lp_bucket)
+        }
+
+        /// Returns the amount of tokens that redeeming the given amount of LP
+        /// tokens would yield right now.
+        ///
+        /// Unlike `get_pool_redemption_value`, this takes into account the
+        /// `refill` that `redeem` triggers, by replaying the vesting math
+        /// against the current time without moving any tokens. The result
+        /// matches what `redeem` would return at the same timestamp, after the
+        /// redemption fee.
+        ///
+        /// Before vesting has started, or during the cliff, nothing can be
+        /// redeemed, so zero is returned.
+        ///
+        /// # Arguments
+        ///
+        /// - `lp_amount`: [`Decimal`] - The amount of LP tokens to preview
+        ///   the redemption for.
+        ///
+        /// # Returns
+        ///
+        /// - [`Decimal`] - The amount of tokens `redeem` would return.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if `lp_amount` is zero or negative.
+        pub fn preview_redeem(&self, lp_amount: Decimal) -> Decimal {
+            assert!(
+                lp_amount > Decimal::ZERO,
+                "{}",
+                VesterError::NonPositiveLpAmount.message()
+            );
+
+            if !self.vesting_started() {
+                return Decimal::ZERO;
+            }
+
+            let current_time = Clock::current_time_rounded_to_seconds();
+            let pending_tokens = self.tokens_to_vest_at(current_time).max(Decimal::ZERO);
+
+            let pool_amount = self.pool.get_vault_amount() + pending_tokens;
+            if pool_amount == Decimal::ZERO {
+                return Decimal::ZERO;
+            }
+            let redeemed_amount = self.pool_redemption_amount(lp_amount, pool_amount);
+
+            redeemed_amount - self.redemption_fee(redeemed_amount)
+        }
+
+        /// Moves LP holders to the vester authorized with
+        /// `authorize_migration`.
+        ///
+        /// The LP tokens are redeemed for their share of both the pool and the
+        /// locked vault, so no unvested tokens are forfeited. Those tokens are
+        /// handed to the new vester's `accept_migrated_tokens`, which
+        /// contributes them and returns the LP tokens it mints for them. The
+        /// tokens left here keep vesting along the original curve.
+        ///
+        /// As migrating takes tokens out just like `redeem`, it is only
+        /// possible once vesting has started and while redemptions are not
+        /// paused.
+        ///
+        /// # Arguments
+        ///
+        /// - `old_lp`: [`FungibleBucket`] - A bucket containing LP tokens of
+        ///   this component.
+        ///
+        /// # Returns
+        ///
+        /// - [`FungibleBucket`] - A bucket containing the LP tokens of the new
+        ///   vester minted for the migrated tokens.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if:
+        /// - No migration has been authorized
+        /// - Redemptions are paused
+        /// - Called before `finish_setup` has been called
+        /// - Called during the pre-claim period (before `vest_start`)
+        /// - The bucket doesn't contain this component's LP tokens
+        /// - The bucket is empty
+        /// - The new vester has not accepted this component as its source
+        pub fn migrate(&mut self, old_lp: FungibleBucket) -> FungibleBucket {
+            let new_vester = self
+                .migration_target
+                .unwrap_or_else(|| panic!("{}", VesterError::NoMigrationAuthorized.message()));
+            assert!(!self.paused, "{}", VesterError::RedemptionsPaused.message());
+            assert!(
+                self.is_redeemable_resource(old_lp.resource_address()),
+                "{}",
+                VesterError::NotLpTokensOfComponent.message()
+            );
+            assert!(
+                old_lp.amount() > Decimal::ZERO,
+                "{}",
+                VesterError::EmptyBucket.message()
+            );
+            self.refill();
+
+            // Redeem against the pool and the locked vault together, so the LP
+            // tokens take their share of both, then lock the rest again
+            let lp_share = old_lp.amount() / self.lp_total_supply();
+            let locked_before = self.locked_tokens_vault.amount();
+            if locked_before > Decimal::ZERO {
+                self.pool
+                    .protected_deposit(self.locked_tokens_vault.take_all());
+            }
+            let tokens = self.pool.redeem(old_lp);
+            if locked_before > Decimal::ZERO {
+                let locked_tokens = self.pool.protected_withdraw(
+                    locked_before - lp_share * locked_before,
+                    WithdrawStrategy::Rounded(RoundingMode::ToZero),
+                );
+                self.locked_tokens_vault.put(locked_tokens);
+            }
+            self.rescale_schedule(locked_before, tokens.amount());
+
+            new_vester.accept_migrated_tokens(tokens)
+        }
+
+        /// Contributes the tokens of LP holders migrating from the vester
+        /// accepted with `accept_migration_from`, for its `migrate`.
+        ///
+        /// During setup, the tokens are contributed like `create_pool_units`.
+        /// Afterwards they are split between the pool and the locked vault in
+        /// the ratio of the two, so the LP tokens minted for them are backed
+        /// like the existing ones and their value is unchanged. The locked
+        /// part vests along this component's curve from where it stands.
+        ///
+        /// This method is public, but only succeeds when called by the
+        /// accepted old vester itself.
+        ///
+        /// # Arguments
+        ///
+        /// - `tokens`: [`FungibleBucket`] - A bucket containing the migrated
+        ///   tokens to vest.
+        ///
+        /// # Returns
+        ///
+        /// - [`FungibleBucket`] - A bucket containing the LP tokens minted for
+        ///   the tokens.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if:
+        /// - No migration source has been accepted
+        /// - The caller is not the accepted old vester
+        /// - The tokens are not of the token to vest
+        /// - The total to vest would exceed `max_total_to_vest`
+        pub fn accept_migrated_tokens(&mut self, tokens: FungibleBucket) -> FungibleBucket {
+            let old_vester = self
+                .migration_source
+                .unwrap_or_else(|| panic!("{}", VesterError::NoMigrationSource.message()));
+            Runtime::assert_access_rule(rule!(require(global_caller(old_vester.address()))));
+            assert!(
+                tokens.resource_address() == self.locked_tokens_vault.resource_address(),
+                "{}",
+                VesterError::MigratedTokensNotTokenToVest.message()
+            );
+
+            if self.vest_start.is_none() {
+                return self.contribute(tokens);
+            }
+            if self.vesting_started() {
+                self.refill();
+            }
+
+            // Contribute against the pool and the locked vault together, so
+            // the minted LP tokens are backed like the existing ones, then
+            // lock the locked vault's share again
+            let amount = tokens.amount();
+            let locked_before = self.locked_tokens_vault.amount();
+            if locked_before > Decimal::ZERO {
+                self.pool
+                    .protected_deposit(self.locked_tokens_vault.take_all());
+            }
+            let backing_before = self.pool.get_vault_amount();
+            let lp_tokens = self.pool.contribute(tokens);
+            if locked_before > Decimal::ZERO {
+                let locked_tokens = self.pool.protected_withdraw(
+                    locked_before + amount * locked_before / backing_before,
+                    WithdrawStrategy::Rounded(RoundingMode::ToZero),
+                );
+                self.locked_tokens_vault.put(locked_tokens);
+            }
+            self.rescale_schedule(locked_before, -amount);
+            self.assert_within_max_total_to_vest();
+
+            lp_tokens
+        }
+
+        /// Returns the vester LP holders can migrate to.
+        ///
+        /// # Returns
+        ///
+        /// - [`Option<Global<IncentivesVester>>`] - The vester authorized with
+        ///   `authorize_migration`, or `None` if no migration is authorized.
+        pub fn get_migration_target(&self) -> Option<Global<IncentivesVester>> {
+            self.migration_target
+        }
+
+        /// Returns the amount of tokens the entire LP supply could redeem
+        /// right now.
+        ///
+        /// This calls `refill` and then returns the redemption value of the
+        /// total LP supply, i.e. of all circulating LP tokens plus the ones
+        /// not claimed yet. Since every LP token has a claim on the pool, this
+        /// is the whole pool, including the vested tokens forfeited by earlier
+        /// redemptions. It is the maximum the component could have to pay out
+        /// immediately, before redemption fees.
+        ///
+        /// Before vesting has started nothing can be redeemed, so zero is
+        /// returned.
+        ///
+        /// # Returns
+        ///
+        /// - [`Decimal`] - The amount of tokens redeemable right now by all
+        ///   LP tokens combined.
+        pub fn get_total_redeemable_now(&mut self) -> Decimal {
+            if !self.vesting_started() {
+                return Decimal::ZERO;
+            }
+
+            self.refill();
+
+            if self.pool.get_vault_amount() == Decimal::ZERO {
+                return Decimal::ZERO;
+            }
+
+            self.pool.get_redemption_value(self.lp_total_supply())
+        }
+
+        /// Returns the amount of LP tokens in the component's internal vault.
+        ///
+        /// This method returns the amount of LP tokens that have not yet been
+        /// claimed by users. It does not include LP tokens that have already
+        /// been distributed to user accounts.
+        ///
+        /// # Returns
+        ///
+        /// - [`Decimal`] - The amount of unclaimed LP tokens in the vault.
+        pub fn get_lp_token_amount(&self) -> Decimal {
+            self.lp_tokens_vault.amount()
+        }
+
+        /// Returns the total supply of LP tokens.
+        ///
+        /// This is the amount of LP tokens that have not been claimed yet (see
+        /// `get_lp_token_amount`) plus the claimed LP tokens that have not been
+        /// redeemed yet. It is the supply the maturity value is spread over,
+        /// which lets integrators verify `get_maturity_value` independently.
+        ///
+        /// # Returns
+        ///
+        /// - [`Decimal`] - The total supply of LP tokens, or zero if none were
+        ///   created yet.
+        pub fn get_lp_total_supply(&self) -> Decimal {
+            self.lp_total_supply()
+        }
+
+        /// Returns the amount of LP tokens held outside the component.
+        ///
+        /// This is the total supply minus the unclaimed LP tokens in the
+        /// vault, i.e. the LP tokens distributed to users (including those
+        /// still waiting in the locker) and not redeemed yet. LP tokens
+        /// reserved by `reserve_claim` or `fund_and_reserve` are held by the
+        /// component and are not counted either.
+        ///
+        /// # Returns
+        ///
+        /// - [`Decimal`] - The amount of distributed, unredeemed LP tokens.
+        pub fn get_distributed_lp(&self) -> Decimal {
+            self.lp_total_supply()
+                - self.lp_tokens_vault.amount()
+                - self.reserved_lp_vault.amount()
+                - self.pending_claims_vault.amount()
+        }
+
+        /// Returns the projected value of 1 LP token at full maturity.
+        ///
+        /// This method calculates what 1 LP token will be worth when all tokens
+        /// are fully vested (at `vest_end`). This is useful for showing users
+        /// the potential future value of their LP tokens.
+        ///
+        /// The maturity value increases when users redeem early, as they forfeit
+        /// their unvested portion which remains in the pool for other LP token
+        /// holders. This creates an incentive to hold LP tokens until full vesting.
+        ///
+        /// The calculation is:
+        /// `maturity_value = (pool_tokens + locked_tokens) / lp_supply`
+        ///
+        /// Every token in the pool or the locked vault ends up in the pool by
+        /// `vest_end` and is shared by the LP tokens still in existence, so
+        /// this is what 1 LP token realizes at full maturity. It is computed
+        /// from the vault amounts directly rather than by scaling the pool's
+        /// redemption value of 1 LP token, which the pool rounds to the
+        /// token's divisibility.
+        ///
+        /// Once vesting has started, this method calls `refill` first to ensure
+        /// the pool is up-to-date.
+        ///
+        /// # Returns
+        ///
+        /// - [`Decimal`] - The projected value of 1 LP token at full maturity,
+        ///   or zero if no LP tokens exist.
+        pub fn get_maturity_value(&mut self) -> Decimal {
+            if self.vesting_started() {
+                self.refill();
+            }
+
+            let lp_supply = self.lp_total_supply();
+            if lp_supply == Decimal::ZERO {
+                return Decimal::ZERO;
+            }
+
+            let final_token_amount =
+                self.pool.get_vault_amount() + self.locked_tokens_vault.amount();
+
+            final_token_amount / lp_supply
+        }
+
+        /// Returns the projected value of 1 LP token at full maturity if a
+        /// fraction of the distributed LP tokens were redeemed right now.
+        ///
+        /// This replays what `redeem` would do at the current vested state,
+        /// including the pending `refill` and the forfeit policy, without
+        /// moving any tokens. The redeemers take their share of the pool, and
+        /// with the `Treasury` forfeit policy also their share of the locked
+        /// vault leaves the vest. The remaining tokens are then shared by the
+        /// LP tokens left, including the unclaimed ones, as in
+        /// `get_maturity_value`.
+        ///
+        /// A fraction of zero yields the current maturity value. Before
+        /// vesting has started `redeem` panics, and during the cliff it hands
+        /// the LP tokens back, so neither pays anything out and the maturity
+        /// value is unaffected by the fraction.
+        ///
+        /// # Arguments
+        ///
+        /// - `fraction_redeemed_now`: [`Decimal`] - The fraction of the
+        ///   distributed LP tokens (see `get_distributed_lp`) that is redeemed.
+        ///
+        /// # Returns
+        ///
+        /// - [`Decimal`] - The projected value of 1 remaining LP token at full
+        ///   maturity, or zero if no LP tokens would be left.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if `fraction_redeemed_now` is not between 0
+        /// and 1.
+        pub fn simulate_maturity_after_redemption(
+            &self,
+            fraction_redeemed_now: Decimal,
+        ) -> Decimal {
+            assert!(
+                fraction_redeemed_now >= Decimal::ZERO && fraction_redeemed_now <= Decimal::ONE,
+                "{}",
+                VesterError::InvalidFractionRedeemedNow.message()
+            );
+
+            let lp_supply = self.lp_total_supply();
+            if lp_supply == Decimal::ZERO {
+                return Decimal::ZERO;
+            }
+
+            let pending_tokens = if self.vesting_started() {
+                self.tokens_to_vest_at(Clock::current_time_rounded_to_seconds())
+                    .max(Decimal::ZERO)
+            } else {
+                Decimal::ZERO
+            };
+            let pool_amount = self.pool.get_vault_amount() + pending_tokens;
+            let locked_amount = self.locked_tokens_vault.amount() - pending_tokens;
+
+            // Nothing can be redeemed yet, see redeem
+            if !self.vesting_started() || pool_amount == Decimal::ZERO {
+                return (pool_amount + locked_amount) / lp_supply;
+            }
+
+            let lp_redeemed = self.get_distributed_lp() * fraction_redeemed_now;
+            let remaining_lp = lp_supply - lp_redeemed;
+            if remaining_lp == Decimal::ZERO {
+                return Decimal::ZERO;
+            }
+
+            // The redemption fee leaves the pool as well, so it doesn't matter
+            // here whether it goes to the redeemers or the treasury
+            let redeemed_amount = self.pool_redemption_amount(lp_redeemed, pool_amount);
+            let forfeited_to_treasury = if self.forfeit_policy == ForfeitPolicy::Treasury {
+                (lp_redeemed / lp_supply * locked_amount)
+                    .checked_round(self.token_divisibility(), RoundingMode::ToZero)
+                    .unwrap()
+            } else {
+                Decimal::ZERO
+            };
+
+            (pool_amount - redeemed_amount + locked_amount - forfeited_to_treasury) / remaining_lp
+        }
+
+        /// Returns the amount of tokens currently in the pool.
+        ///
+        /// This method returns the amount of vested tokens that are currently
+        /// available for redemption in the pool. This amount increases over time
+        /// as tokens are vested via the `refill` method, which this method
+        /// calls first if `auto_refill_on_read` is enabled.
+        ///
+        /// # Returns
+        ///
+        /// - [`Decimal`] - The amount of tokens in the pool vault.
+        pub fn get_pool_vault_amount(&mut self) -> Decimal {
+            self.refill_on_read();
+            self.pool.get_vault_amount()
+        }
+
+        /// Returns the amount of tokens still locked (not yet vested).
+        ///
+        /// This method returns the amount of tokens in the locked vault that
+        /// have not yet been vested into the pool. This amount decreases over
+        /// time as tokens are vested via the `refill` method, which this
+        /// method calls first if `auto_refill_on_read` is enabled.
+        ///
+        /// # Returns
+        ///
+        /// - [`Decimal`] - The amount of locked tokens.
+        pub fn get_locked_vault_amount(&mut self) -> Decimal {
+            self.refill_on_read();
+            self.locked_tokens_vault.amount()
+        }
+
+        /// Returns all vault amounts and vesting totals at once.
+        ///
+        /// Once vesting has started, this method calls `refill` first, so all
+        /// values are consistent with each other at the current instant. This
+        /// saves dashboards from combining several separate calls that could
+        /// otherwise disagree.
+        ///
+        /// # Returns
+        ///
+        /// - [`StateSnapshot`] - The pool, locked and unclaimed LP amounts
+        ///   together with the vested and total tokens.
+        pub fn get_state_snapshot(&mut self) -> StateSnapshot {
+            if self.vesting_started() {
+                self.refill();
+            }
+
+            StateSnapshot {
+                pool_amount: self.pool.get_vault_amount(),
+                locked_amount: self.locked_tokens_vault.amount(),
+                unclaimed_lp: self.lp_tokens_vault.amount(),
+                vested_tokens: self.vested_tokens,
+                total_tokens_to_vest: self.total_tokens_to_vest,
+            }
+        }
+
+        /// Returns a summary of what the given amount of LP tokens is worth.
+        ///
+        /// This packages the redemption value, the maturity value and the
+        /// vested fraction into a single call, e.g. for wallets showing a
+        /// user's position. Like `get_maturity_value`, it calls `refill` first
+        /// once vesting has started, so all values are consistent with each
+        /// other at the current instant.
+        ///
+        /// # Arguments
+        ///
+        /// - `lp_amount`: [`Decimal`] - The amount of LP tokens the user holds.
+        ///
+        /// # Returns
+        ///
+        /// - [`UserPosition`] - The tokens redeemable now (after the redemption
+        ///   fee), the value at maturity, the tokens forfeited by redeeming now
+        ///   and the vested fraction. Nothing is redeemable before vesting has
+        ///   started.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if `lp_amount` is zero or negative.
+        pub fn get_position(&mut self, lp_amount: Decimal) -> UserPosition {
+            assert!(
+                lp_amount > Decimal::ZERO,
+                "{}",
+                VesterError::NonPositiveLpAmount.message()
+            );
+
+            let value_at_maturity = self.get_maturity_value() * lp_amount;
+
+            let redemption_value =
+                if self.vesting_started() && self.pool.get_vault_amount() > Decimal::ZERO {
+                    self.pool.get_redemption_value(lp_amount)
+                } else {
+                    Decimal::ZERO
+                };
+
+            UserPosition {
+                redeemable_now: redemption_value - self.redemption_fee(redemption_value),
+                value_at_maturity,
+                forfeit_if_redeem_now: value_at_maturity - redemption_value,
+                vested_fraction: self.get_vested_fraction(),
+            }
+        }
+
+        /// Returns the resource address of the LP tokens.
+        ///
+        /// This method returns the resource address of the LP tokens that are
+        /// minted by the pool and represent claims to vested tokens. Users need
+        /// this address to identify their LP tokens in their wallets.
+        ///
+        /// # Returns
+        ///
+        /// - [`ResourceAddress`] - The resource address of the LP tokens.
+        pub fn get_pool_unit_resource_address(&self) -> ResourceAddress {
+            self.lp_tokens_vault.resource_address()
+        }
+
+        /// Returns the current redemption value for a given amount of LP tokens.
+        ///
+        /// This method calculates how many tokens would be received if the
+        /// specified amount of LP tokens were redeemed at the current time.
+        /// The value depends on how much has vested so far and how many LP
+        /// tokens have already been redeemed.
+        ///
+        /// Note that this returns the value at the current moment. To get an
+        /// up-to-date value that includes the latest vesting progress, call
+        /// `refill` first or use this after a `redeem` call (which automatically
+        /// calls `refill`). Whether a third party has refilled in the meantime
+        /// changes the result, so quotes shown to users should come from
+        /// `get_redemption_value_current` instead, unless `auto_refill_on_read`
+        /// is enabled, in which case this method refills first as well.
+        ///
+        /// # Arguments
+        ///
+        /// - `lp_amount`: [`Decimal`] - The amount of LP tokens to calculate
+        ///   the redemption value for.
+        ///
+        /// # Returns
+        ///
+        /// - [`Decimal`] - The amount of tokens that would be received for
+        ///   redeeming the specified amount of LP tokens.
+        pub fn get_pool_redemption_value(&mut self, lp_amount: Decimal) -> Decimal {
+            self.refill_on_read();
+            self.pool.get_redemption_value(lp_amount)
+        }
+
+        /// Returns the redemption value of the given amount of LP tokens after
+        /// refilling the pool.
+        ///
+        /// Unlike `get_pool_redemption_value`, this first calls `refill` just
+        /// like `redeem` does, so the result does not depend on whether anyone
+        /// else has refilled since the last redemption. At the same timestamp
+        /// it matches what `redeem` pays out before the redemption fee.
+        ///
+        /// Before vesting has started there is nothing to refill, so the value
+        /// of the current pool is returned.
+        ///
+        /// # Arguments
+        ///
+        /// - `lp_amount`: [`Decimal`] - The amount of LP tokens to calculate
+        ///   the redemption value for.
+        ///
+        /// # Returns
+        ///
+        /// - [`Decimal`] - The amount of tokens that would be received for
+        ///   redeeming the specified amount of LP tokens, before the fee.
+        pub fn get_redemption_value_current(&mut self, lp_amount: Decimal) -> Decimal {
+            if self.vesting_started() {
+                self.refill();
+            }
+
+            self.pool.get_redemption_value(lp_amount)
+        }
+
+        /// Returns the total amount of tokens that have been vested so far.
+        ///
+        /// This method returns the cumulative amount of tokens that have been
+        /// moved from the locked vault into the pool through the `refill` method.
+        /// This value increases over time and approaches `total_tokens_to_vest`
+        /// as vesting progresses. If `auto_refill_on_read` is enabled, this
+        /// method calls `refill` first.
+        ///
+        /// # Returns
+        ///
+        /// - [`Decimal`] - The amount of tokens that have been vested.
+        pub fn get_vested_tokens(&mut self) -> Decimal {
+            self.refill_on_read();
+            self.vested_tokens
+        }
+
+        /// Returns the average amount of tokens vested per second since
+        /// `vest_start`.
+        ///
+        /// This first calls `refill`, then divides the vested tokens by the
+        /// seconds elapsed since `vest_start`, at least one. Unlike the
+        /// configured schedule, it reflects every intervention that changed
+        /// what actually vested, such as tranches, withdrawn locked tokens or
+        /// treasury forfeits. The initial vested fraction counts as vested at
+        /// `vest_start`, so the rate starts high and settles over the vest.
+        ///
+        /// # Returns
+        ///
+        /// - [`Decimal`] - The tokens vested per second so far, or zero before
+        ///   `vest_start`.
+        pub fn get_realized_rate(&mut self) -> Decimal {
+            if !self.vesting_started() {
+                return Decimal::ZERO;
+            }
+            self.refill();
+
+            let elapsed = Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch
+                - self.vest_start.unwrap().seconds_since_unix_epoch;
+
+            self.vested_tokens / Decimal::from(elapsed.max(1))
+        }
+
+        /// Returns the total amount of tokens the schedule vests.
+        ///
+        /// This starts out as the tokens deposited during the setup phase and
+        /// is raised by `add_tranche`, `add_vesting_funds` and tokens accepted
+        /// from a migration. Withdrawing locked tokens, forfeiting to the
+        /// treasury and migrating out shrink it, and putting locked tokens
+        /// back restores it. The refill at any point in time moves this total
+        /// times the vested fraction into the pool, minus what was vested
+        /// already.
+        ///
+        /// # Returns
+        ///
+        /// - [`Decimal`] - The total amount of tokens to vest.
+        pub fn get_total_tokens_to_vest(&self) -> Decimal {
+            self.total_tokens_to_vest
+        }
+
+        /// Returns the fraction of tokens that should be vested right now.
+        ///
+        /// This computes `initial_vested_fraction + (1 - initial_vested_fraction) * curve(progress)`
+        /// against the current time, without relying on `refill` having run.
+        /// It does not mutate state, so it can be called cheaply by frontends.
+        ///
+        /// # Returns
+        ///
+        /// - [`Decimal`] - The vested fraction in `[0, 1]`, or zero before setup,
+        ///   during the pre-claim period and during the cliff.
+        pub fn get_vested_fraction(&self) -> Decimal {
+            if !self.vesting_started() {
+                return Decimal::ZERO;
+            }
+
+            self.vested_fraction_at(Clock::current_time_rounded_to_seconds())
+        }
+
+        /// Returns the amount of tokens released per day by the linear part of
+        /// the vest.
+        ///
+        /// This is `total_tokens_to_vest * (1 - initial_vested_fraction) /
+        /// vest_duration_days`: the initial vested fraction is released at
+        /// `vest_start` at once and is not part of the daily emission. A cliff
+        /// holds back the emission of its days and releases it when it ends,
+        /// but does not change the daily rate afterwards. For the non-linear
+        /// curves this is the average daily emission.
+        ///
+        /// # Returns
+        ///
+        /// - [`Decimal`] - The daily emission, or zero before setup is
+        ///   finished.
+        pub fn get_daily_emission(&self) -> Decimal {
+            if self.vest_start.is_none() {
+                return Decimal::ZERO;
+            }
+
+            self.total_tokens_to_vest * (Decimal::ONE - self.initial_vested_fraction)
+                / Decimal::from(self.vest_duration_days)
+        }
+
+        /// Returns the amount of tokens vested at an arbitrary instant.
+        ///
+        /// This evaluates the same schedule as `refill` at `instant`, which
+        /// may lie in the past or the future, so modeling tools can chart the
+        /// emission curve without waiting for the ledger clock. It does not
+        /// mutate state.
+        ///
+        /// # Arguments
+        ///
+        /// - `instant`: [`Instant`] - The instant to evaluate the schedule at.
+        ///
+        /// # Returns
+        ///
+        /// - [`Decimal`] - The tokens vested at `instant`: zero before setup,
+        ///   before `vest_start` and during the cliff, and `total_tokens_to_vest`
+        ///   from `vest_end` on.
+        pub fn projected_vested_at(&self, instant: Instant) -> Decimal {
+            if self.vest_start.is_none() {
+                return Decimal::ZERO;
+            }
+
+            self.total_tokens_to_vest * self.vested_fraction_at(instant)
+        }
+
+        /// Returns the number of seconds until vesting starts.
+        ///
+        /// This is meant for countdowns during the pre-claim period. Before
+        /// `finish_setup` is called, the pre-claim period has not started yet,
+        /// so its full duration is returned.
+        ///
+        /// # Returns
+        ///
+        /// - [`i64`] - The seconds remaining until `vest_start`, or zero once
+        ///   vesting has begun.
+        pub fn get_pre_claim_seconds_remaining(&self) -> i64 {
+            match self.vest_start {
+                Some(vest_start) => {
+                    let now = Clock::current_time_rounded_to_seconds();
+                    (vest_start.seconds_since_unix_epoch - now.seconds_since_unix_epoch).max(0)
+                }
+                None => self.pre_claim_duration_seconds,
+            }
+        }
+
+        /// Returns the number of seconds until all tokens are vested.
+        ///
+        /// This complements `get_pre_claim_seconds_remaining` for "fully vested
+        /// in N days" countdowns. Before `finish_setup` is called, the full
+        /// pre-claim period and vest duration are still ahead.
+        ///
+        /// # Returns
+        ///
+        /// - [`i64`] - The seconds remaining until `vest_end`, or zero once the
+        ///   vest has ended.
+        pub fn get_vesting_seconds_remaining(&self) -> i64 {
+            match self.vest_end {
+                Some(vest_end) => {
+                    let now = Clock::current_time_rounded_to_seconds();
+                    (vest_end.seconds_since_unix_epoch - now.seconds_since_unix_epoch).max(0)
+                }
+                None => self.pre_claim_duration_seconds + self.vest_duration_days * 86400,
+            }
+        }
+
+        /// Returns the number of seconds of the vest that have elapsed.
+        ///
+        /// This is the clock arithmetic `refill` uses to determine the vesting
+        /// progress, so clients don't need to compute it against their own
+        /// clock from `vest_start`.
+        ///
+        /// # Returns
+        ///
+        /// - [`i64`] - The seconds elapsed since `vest_start`, at most the
+        ///   vest duration, or zero if vesting has not started yet.
+        pub fn get_elapsed_seconds(&self) -> i64 {
+            match self.vest_start {
+                Some(_) => self.elapsed_seconds_at(Clock::current_time_rounded_to_seconds()),
+                None => 0,
+            }
+        }
+
+        /// Returns whether the vest has ended and all tokens are vested.
+        ///
+        /// This only looks at the clock, not at the vault amounts, so it is
+        /// not affected by rounding dust and does not require `refill` to have
+        /// been called.
+        ///
+        /// # Returns
+        ///
+        /// - [`bool`] - `true` if the current time is at or after `vest_end`,
+        ///   `false` before setup and while vesting is still in progress.
+        pub fn is_fully_vested(&self) -> bool {
+            match self.vest_end {
+                Some(_) => self.vest_ended_at(Clock::current_time_rounded_to_seconds()),
+                None => false,
+            }
+        }
+
+        /// Returns the total amount of tokens forfeited by early redemptions.
+        ///
+        /// Every redemption before full vesting forfeits the unvested part of
+        /// the redeemer's share. Where it goes depends on the forfeit policy:
+        /// - `Redistribute`: the tokens stay in the component and keep vesting,
+        ///   raising the maturity value for the remaining LP holders.
+        /// - `Treasury`: the tokens are moved from the locked vault to the
+        ///   treasury, and `total_tokens_to_vest` shrinks accordingly, leaving
+        ///   the maturity value of the remaining LP holders unchanged. See
+        ///   `get_treasury_amount`.
+        ///
+        /// The amount is counted here under either policy.
+        ///
+        /// # Returns
+        ///
+        /// - [`Decimal`] - The cumulative amount of forfeited tokens.
+        pub fn get_forfeited_tokens(&self) -> Decimal {
+            self.forfeited_tokens
+        }
+
+        /// Returns the total amount of vested tokens paid out by redemptions.
+        ///
+        /// # Returns
+        ///
+        /// - [`Decimal`] - The cumulative amount of tokens paid out of the pool
+        ///   by `redeem`, including redemption fees.
+        pub fn get_total_redeemed(&self) -> Decimal {
+            self.total_redeemed
+        }
+
+        /// Returns the amount of redemption fees in the treasury that have not
+        /// been withdrawn yet.
+        ///
+        /// # Returns
+        ///
+        /// - [`Decimal`] - The amount of tokens in the treasury vault.
+        pub fn get_treasury_amount(&self) -> Decimal {
+            self.treasury_vault.amount()
+        }
+
+        /// Checks that every token added to the vest is still accounted for.
+        ///
+        /// The tokens in the pool and the locked vault, plus everything paid
+        /// out by redemptions (fees included) and forfeited to the treasury,
+        /// must add up to `total_tokens_to_vest` plus the amount by which
+        /// treasury forfeits shrank it. Refills only move tokens between the
+        /// locked vault and the pool, so they never change the sum. A
+        /// mismatch beyond `CONSERVATION_TOLERANCE` points to an accounting
+        /// bug.
+        ///
+        /// Once vesting has started, this method calls `refill` first.
+        ///
+        /// # Returns
+        ///
+        /// - [`bool`] - Whether the invariant holds.
+        pub fn verify_conservation(&mut self) -> bool {
+            if self.vesting_started() {
+                self.refill();
+            }
+
+            let accounted_for = self.pool.get_vault_amount()
+                + self.locked_tokens_vault.amount()
+                + self.total_redeemed
+                + self.forfeited_to_treasury;
+            let expected = self.total_tokens_to_vest + self.schedule_reduction;
+
+            (accounted_for - expected).checked_abs().unwrap() <= CONSERVATION_TOLERANCE
+        }
+
+        /// Returns the addresses of the badges that control this component.
+        ///
+        /// # Returns
+        ///
+        /// - [`ResourceAddress`] - The address of the admin badge, which can
+        ///   claim LP tokens for users.
+        /// - [`ResourceAddress`] - The address of the super admin badge, which
+        ///   owns the component.
+        pub fn get_admin_badges(&self) -> (ResourceAddress, ResourceAddress) {
+            (self.admin_badge_address, self.super_admin_badge_address)
+        }
+
+        /// Returns the resource address of the claim receipt NFTs.
+        ///
+        /// Every claim mints one receipt NFT for the receiving account, so
+        /// wallets can display the user's participation in the season.
+        ///
+        /// # Returns
+        ///
+        /// - [`ResourceAddress`] - The resource address of the receipt NFTs.
+        pub fn get_receipt_resource_address(&self) -> ResourceAddress {
+            self.receipt_resource_manager.address()
+        }
+
+        /// Returns the recipient's unlock schedule recorded on a receipt NFT.
+        ///
+        /// # Arguments
+        ///
+        /// - `local_id`: [`NonFungibleLocalId`] - The local id of the receipt.
+        ///
+        /// # Returns
+        ///
+        /// - [`Option<ReceiptData>`] - The data recorded on the receipt, or
+        ///   `None` if no data was given for its account.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if no receipt with `local_id` exists.
+        pub fn get_receipt_data(&self, local_id: NonFungibleLocalId) -> Option<ReceiptData> {
+            self.receipt_resource_manager
+                .get_non_fungible_data::<ClaimReceipt>(&local_id)
+                .receipt_data
+        }
+
+        /// Returns whether the given resource can be redeemed by this
+        /// component, i.e. whether it is the LP token (pool unit).
+        ///
+        /// # Arguments
+        ///
+        /// - `resource`: [`ResourceAddress`] - The resource address to check.
+        ///
+        /// # Returns
+        ///
+        /// - [`bool`] - `true` if `resource` is this component's LP token.
+        pub fn is_redeemable_resource(&self, resource: ResourceAddress) -> bool {
+            resource == self.lp_tokens_vault.resource_address()
+        }
+
+        /// Returns the address of the underlying `OneResourcePool`.
+        ///
+        /// Other components can use it to read the pool state directly, e.g.
+        /// to price LP tokens on a secondary market.
+        ///
+        /// # Returns
+        ///
+        /// - [`Global<OneResourcePool>`] - The pool holding the vested tokens.
+        pub fn get_pool_address(&self) -> Global<OneResourcePool> {
+            self.pool
+        }
+
+        /// Returns the address of the `AccountLocker` that holds claimed LP
+        /// tokens and receipts for accounts that rejected the deposit.
+        ///
+        /// # Returns
+        ///
+        /// - [`Global<AccountLocker>`] - The locker used for claims.
+        pub fn get_locker_address(&self) -> Global<AccountLocker> {
+            self.locker
+        }
+
+        /// Returns the cumulative amount of LP tokens claimed for an account.
+        ///
+        /// # Arguments
+        ///
+        /// - `account`: [`Global<Account>`] - The account to look up.
+        ///
+        /// # Returns
+        ///
+        /// - [`Decimal`] - The LP tokens claimed for the account through
+        ///   `claim`, `claim_batch` and `claim_idempotent`, or zero if it never
+        ///   received any.
+        pub fn get_claimed_amount(&self, account: Global<Account>) -> Decimal {
+            self.claimed_per_account
+                .get(&account)
+                .map(|claimed| *claimed)
+                .unwrap_or(Decimal::ZERO)
+        }
+
+        /// Returns whether an account has been claimed for at least a given
+        /// amount of LP tokens.
+        ///
+        /// A distribution script resuming after an interruption can use this
+        /// to skip accounts that already received their allocation.
+        ///
+        /// # Arguments
+        ///
+        /// - `account`: [`Global<Account>`] - The account to look up.
+        /// - `amount`: [`Decimal`] - The LP token amount to compare against.
+        ///
+        /// # Returns
+        ///
+        /// - [`bool`] - `true` if `get_claimed_amount` for the account is at
+        ///   least `amount`.
+        pub fn has_claimed_at_least(&self, account: Global<Account>, amount: Decimal) -> bool {
+            self.get_claimed_amount(account) >= amount
+        }
+
+        /// Returns the amount of LP tokens waiting in the locker for an
+        /// account.
+        ///
+        /// Claims for accounts that reject deposits are stored in the
+        /// AccountLocker, where the account has to claim them itself. This
+        /// lets frontends show how much is still waiting there.
+        ///
+        /// # Arguments
+        ///
+        /// - `account`: [`Global<Account>`] - The account to look up.
+        ///
+        /// # Returns
+        ///
+        /// - [`Decimal`] - The LP tokens held in the locker for the account,
+        ///   or zero if there are none.
+        pub fn get_locker_balance(&self, account: Global<Account>) -> Decimal {
+            self.locker
+                .get_amount(account, self.lp_tokens_vault.resource_address())
+        }
+
+        /// Returns the claims recorded by `fund_and_reserve` that `finish_setup`
+        /// has not delivered yet.
+        ///
+        /// # Returns
+        ///
+        /// - [`Vec<(Global<Account>, Decimal)>`] - The receiving account and
+        ///   the amount of LP tokens of every pending claim, in order. Empty
+        ///   once setup is finished.
+        pub fn get_pending_claims(&self) -> Vec<(Global<Account>, Decimal)> {
+            self.pending_claims.clone()
+        }
+
+        /// Returns the open claim reservation of an account.
+        ///
+        /// # Arguments
+        ///
+        /// - `account`: [`Global<Account>`] - The account to look up.
+        ///
+        /// # Returns
+        ///
+        /// - [`Option<ClaimReservation>`] - The reserved LP amount and the
+        ///   expiry, or `None` if the account has no open reservation. An
+        ///   expired reservation stays open until it is released.
+        pub fn get_reservation(&self, account: Global<Account>) -> Option<ClaimReservation> {
+            self.reservations
+                .get(&account)
+                .map(|reservation| reservation.clone())
+        }
+
+        /// Returns a page of the accounts that have been claimed for, in order
+        /// of their first claim.
+        ///
+        /// Each account is indexed from 0 in order of its first claim, so all
+        /// of them can be enumerated page by page up to `get_claimant_count`.
+        ///
+        /// # Arguments
+        ///
+        /// - `start`: [`u64`] - The index of the first account to return.
+        /// - `count`: [`u64`] - The maximum number of accounts to return.
+        ///
+        /// # Returns
+        ///
+        /// - [`Vec<Global<Account>>`] - The distinct accounts claimed for with
+        ///   an index from `start` on, at most `count` of them. Empty if `start`
+        ///   is past the last account.
+        pub fn get_claimants(&self, start: u64, count: u64) -> Vec<Global<Account>> {
+            let end = start.saturating_add(count).min(self.claimant_count);
+            (start..end)
+                .map(|index| *self.claimants.get(&index).unwrap())
+                .collect()
+        }
+
+        /// Returns the number of distinct accounts that have been claimed for.
+        ///
+        /// # Returns
+        ///
+        /// - [`u64`] - The number of accounts `get_claimants` can enumerate.
+        pub fn get_claimant_count(&self) -> u64 {
+            self.claimant_count
+        }
+
+        /// Returns whether a `claim` with the given parameters would succeed.
+        ///
+        /// This lets a backend check a claim before building and paying for a
+        /// transaction that would abort.
+        ///
+        /// # Arguments
+        ///
+        /// - `lp_amount`: [`Decimal`] - The amount of LP tokens to claim.
+        /// - `account`: [`Global<Account>`] - The account to claim for.
+        ///
+        /// # Returns
+        ///
+        /// - [`bool`] - `false` if setup is not finished, the claim deadline
+        ///   has passed, the account is not on the enabled allowlist, the
+        ///   amount is not positive, the vault holds fewer LP tokens or the
+        ///   claim would push the account above `max_claim_per_account`;
+        ///   `true` otherwise.
+        pub fn can_claim(&self, lp_amount: Decimal, account: Global<Account>) -> bool {
+            if self.vest_start.is_none()
+                || !self.claims_open()
+                || !self.claim_target_allowed(account)
+                || lp_amount <= Decimal::ZERO
+                || lp_amount > self.lp_tokens_vault.amount()
+            {
+                return false;
+            }
+
+            match self.max_claim_per_account {
+                Some(max_claim) => self.get_claimed_amount(account) + lp_amount <= max_claim,
+                None => true,
+            }
+        }
+
+        /// Returns whether claims are restricted to the allowlist.
+        ///
+        /// # Returns
+        ///
+        /// - [`bool`] - `true` if the super admin has enabled the allowlist.
+        pub fn is_allowlist_enabled(&self) -> bool {
+            self.allowlist_enabled
+        }
+
+        /// Returns whether an account is on the allowlist of claim targets.
+        /// This does not depend on whether the allowlist is enabled.
+        ///
+        /// # Arguments
+        ///
+        /// - `account`: [`Global<Account>`] - The account to look up.
+        ///
+        /// # Returns
+        ///
+        /// - [`bool`] - `true` if the account is on the allowlist.
+        pub fn is_allowed_account(&self, account: Global<Account>) -> bool {
+            self.allowed_accounts.get(&account).is_some()
+        }
+
+        /// Returns whether the vault and vesting getters refill before
+        /// reading.
+        ///
+        /// # Returns
+        ///
+        /// - [`bool`] - `true` if the super admin has enabled refilling on
+        ///   read.
+        pub fn is_auto_refill_on_read(&self) -> bool {
+            self.auto_refill_on_read
+        }
+
+        /// Returns whether redemptions are currently paused.
+        ///
+        /// # Returns
+        ///
+        /// - [`bool`] - `true` if the super admin has paused redemptions.
+        pub fn is_paused(&self) -> bool {
+            self.paused
+        }
+
+        /// Returns the instant when the cliff ends.
+        ///
+        /// Until this instant nothing is vested, not even the initial vested
+        /// fraction. When the component has no cliff, this equals `vest_start`.
+        ///
+        /// # Returns
+        ///
+        /// - [`Option<Instant>`] - The end of the cliff, or `None` if setup
+        ///   has not been finished yet.
+        pub fn get_cliff_end(&self) -> Option<Instant> {
+            self.cliff_end
+        }
+
+        /// Returns the boundary instants of the pre-claim and vesting periods.
+        ///
+        /// `setup_finished_at` is recorded by `finish_setup`, so it stays
+        /// exact even after `accelerate_vest_start` has shortened the
+        /// pre-claim period. Until then it is `vest_start` minus
+        /// `pre_claim_duration_seconds`.
+        ///
+        /// # Returns
+        ///
+        /// - [`Timeline`] - The timeline of this component, with all instants
+        ///   `None` if setup has not been finished yet.
+        pub fn get_timeline(&self) -> Timeline {
+            Timeline {
+                setup_finished_at: self.setup_finished_at,
+                vest_start: self.vest_start,
+                vest_end: self.vest_end,
+            }
+        }
+
+        /// Returns the shape of the vesting schedule in a single call.
+        ///
+        /// Together with `vest_start`, this is everything needed to render
+        /// the emission curve: the vested fraction at progress `p` is
+        /// `initial_fraction + (1 - initial_fraction) * curve(p)` once the
+        /// cliff has passed, and zero before. It does not call `refill` and
+        /// only changes if `set_vest_duration_days` is called during setup.
+        ///
+        /// # Returns
+        ///
+        /// - [`ScheduleDescriptor`] - The shape of the vesting schedule.
+        pub fn get_schedule_descriptor(&self) -> ScheduleDescriptor {
+            ScheduleDescriptor {
+                initial_fraction: self.initial_vested_fraction,
+                curve: self.vesting_curve,
+                duration_days: self.vest_duration_days,
+                cliff_days: self.cliff_duration_days,
+                granularity: self.vest_granularity,
+            }
+        }
+
+        /// Returns the full vesting configuration in a single call.
+        ///
+        /// This method bundles the vesting schedule and the relevant resource
+        /// addresses, so integrators don't need to call every getter
+        /// separately. It does not call `refill`. During the setup phase the
+        /// instants are `None`, as the schedule only starts at `finish_setup`.
+        ///
+        /// # Returns
+        ///
+        /// - [`VesterConfig`] - The vesting configuration of this component.
+        pub fn get_config(&self) -> VesterConfig {
+            VesterConfig {
+                vest_start: self.vest_start,
+                vest_end: self.vest_end,
+                cliff_end: self.cliff_end,
+                claim_deadline: self.claim_deadline,
+                vest_duration_days: self.vest_duration_days,
+                pre_claim_duration_seconds: self.pre_claim_duration_seconds,
+                cliff_duration_days: self.cliff_duration_days,
+                initial_vested_fraction: self.initial_vested_fraction,
+                vesting_curve: self.vesting_curve,
+                rounding_mode: self.rounding_mode,
+                max_claim_per_account: self.max_claim_per_account,
+                redemption_fee_fraction: self.redemption_fee_fraction,
+                min_redeem_lp: self.min_redeem_lp,
+                min_redemption_value: self.min_redemption_value,
+                forfeit_policy: self.forfeit_policy,
+                claim_grace_days: self.claim_grace_days,
+                vest_granularity: self.vest_granularity,
+                max_total_to_vest: self.max_total_to_vest,
+                token_to_vest: self.locked_tokens_vault.resource_address(),
+                pool_unit: self.lp_tokens_vault.resource_address(),
+            }
+        }
+
+        /// Moves the tokens of a vesting track vested so far into its pool.
+        ///
+        /// This works like `refill` for track 0, along the track's own
+        /// schedule: nothing before the track's cliff ends, then its initial
+        /// vested fraction, and all of its tokens once its vest ends.
+        ///
+        /// # Arguments
+        ///
+        /// - `track_id`: [`u32`] - The track to refill, 0 for the component's
+        ///   own schedule.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if:
+        /// - Called before `finish_setup` has been called
+        /// - Called during the pre-claim period (before `vest_start`)
+        /// - There is no track with the id
+        pub fn refill_track(&mut self, track_id: u32) {
+            if track_id == 0 {
+                self.refill();
+                return;
+            }
+
+            if let Some(vest_start) = self.vest_start {
+                assert!(
+                    Clock::current_time_is_at_or_after(vest_start, TimePrecision::Second),
+                    "{}",
+                    VesterError::StillInPreClaim.message()
+                );
+            } else {
+                panic!("{}", VesterError::SetupNotFinished.message());
+            }
+
+            let now = Clock::current_time_rounded_to_seconds();
+            let params = self
+                .tracks
+                .get(&track_id)
+                .unwrap_or_else(|| panic!("{}", VesterError::UnknownTrack.message()))
+                .params
+                .clone();
+            let vest_ended = now.compare(self.track_vest_end(&params), TimeComparisonOperator::Gte);
+            let vested_fraction = self.track_vested_fraction_at(&params, now);
+            let divisibility = self.token_divisibility();
+            let rounding_mode = self.rounding_mode;
+
+            let mut track = self.tracks.get_mut(&track_id).unwrap();
+            let locked_amount = track.locked_tokens_vault.amount();
+
+            // Once fully vested, sweep the locked vault so no rounding dust
+            // is left behind
+            let tokens = if vest_ended {
+                track.locked_tokens_vault.take_all()
+            } else {
+                let tokens_to_vest_now = (track.total_tokens_to_vest * vested_fraction
+                    - track.vested_tokens)
+                    .checked_round(divisibility, rounding_mode)
+                    .unwrap()
+                    .min(locked_amount);
+                if tokens_to_vest_now <= Decimal::ZERO {
+                    return;
+                }
+                track
+                    .locked_tokens_vault
+                    .take_advanced(tokens_to_vest_now, WithdrawStrategy::Rounded(rounding_mode))
+            };
+
+            track.vested_tokens += tokens.amount();
+            track.pool.protected_deposit(tokens);
+        }
+
+        /// Redeems LP tokens of a vesting track for the vested tokens.
+        ///
+        /// This works like `redeem` for track 0. For an additional track, the
+        /// track is refilled first and the LP tokens are redeemed against its
+        /// pool. The unvested part of the redeemer's share is forfeited and
+        /// stays in the track for its remaining LP holders. If nothing of the
+        /// track has vested yet, the LP tokens are handed back untouched.
+        ///
+        /// # Arguments
+        ///
+        /// - `track_id`: [`u32`] - The track the LP tokens belong to, 0 for
+        ///   the component's own schedule.
+        /// - `lp_token_bucket`: [`FungibleBucket`] - A bucket containing the LP
+        ///   tokens of the track to redeem.
+        ///
+        /// # Returns
+        ///
+        /// - [`FungibleBucket`] - A bucket containing the vested tokens received.
+        /// - [`Decimal`] - The amount of tokens forfeited by redeeming now
+        ///   instead of at full maturity.
+        /// - [`FungibleBucket`] - A bucket containing the LP tokens that were
+        ///   not redeemed.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if:
+        /// - Redemptions are paused
+        /// - Called before vesting has started
+        /// - There is no track with the id
+        /// - The bucket doesn't contain the track's LP tokens
+        /// - The LP token bucket is empty (contains zero tokens)
+        pub fn redeem_track(
+            &mut self,
+            track_id: u32,
+            lp_token_bucket: FungibleBucket,
+        ) -> (FungibleBucket, Decimal, FungibleBucket) {
+            if track_id == 0 {
+                return self.redeem(lp_token_bucket);
+            }
+
+            assert!(!self.paused, "{}", VesterError::RedemptionsPaused.message());
+            self.refill_track(track_id);
+
+            let token_to_vest = self.locked_tokens_vault.resource_address();
+            let mut track = self.tracks.get_mut(&track_id).unwrap();
+            let lp_resource = track.lp_tokens_vault.resource_address();
+            assert!(
+                lp_token_bucket.resource_address() == lp_resource,
+                "{}",
+                VesterError::NotLpToken.message()
+            );
+            assert!(
+                lp_token_bucket.amount() > Decimal::ZERO,
+                "{}",
+                VesterError::EmptyBucket.message()
+            );
+
+            // The pool refuses to redeem for zero tokens, so hand the LP
+            // tokens back during the track's cliff
+            if track.pool.get_vault_amount() == Decimal::ZERO {
+                return (
+                    FungibleBucket::new(token_to_vest),
+                    Decimal::ZERO,
+                    lp_token_bucket,
+                );
+            }
+
+            // The redeemer's share of everything the track will eventually vest
+            let lp_share = lp_token_bucket.amount()
+                / ResourceManager::from(lp_resource)
+                    .total_supply()
+                    .unwrap_or(Decimal::ZERO);
+            let maturity_claim =
+                lp_share * (track.pool.get_vault_amount() + track.locked_tokens_vault.amount());
+
+            let redeemed_tokens = track.pool.redeem(lp_token_bucket);
+            let forfeited = maturity_claim - redeemed_tokens.amount();
+
+            (redeemed_tokens, forfeited, FungibleBucket::new(lp_resource))
+        }
+
+        /// Returns the number of additional vesting tracks.
+        ///
+        /// # Returns
+        ///
+        /// - [`u32`] - The number of tracks created with `create_track`, which
+        ///   is also the id of the last one.
+        pub fn get_track_count(&self) -> u32 {
+            self.track_count
+        }
+
+        /// Returns the state of an additional vesting track.
+        ///
+        /// # Arguments
+        ///
+        /// - `track_id`: [`u32`] - The id returned by `create_track`.
+        ///
+        /// # Returns
+        ///
+        /// - [`TrackInfo`] - The schedule, LP token, vault amounts and vesting
+        ///   progress of the track.
+        ///
+        /// # Panics
+        ///
+        /// This method will panic if there is no additional track with the id.
+        /// Use the getters without a `track_id` for track 0.
+        pub fn get_track(&self, track_id: u32) -> TrackInfo {
+            let track = self
+                .tracks
+                .get(&track_id)
+                .unwrap_or_else(|| panic!("{}", VesterError::UnknownTrack.message()));
+
+            TrackInfo {
+                params: track.params.clone(),
+                pool_unit_resource_address: track.lp_tokens_vault.resource_address(),
+                pool_vault_amount: track.pool.get_vault_amount(),
+                locked_vault_amount: track.locked_tokens_vault.amount(),
+                total_tokens_to_vest: track.total_tokens_to_vest,
+                vested_tokens: track.vested_tokens,
+            }
+        }
+
+        // endregion:Public Methods
+
+        // region:Helper Methods
+
+        /// Contributes tokens to the pool during the setup phase and returns
+        /// the LP tokens minted for them. `total_tokens_to_vest` grows by what
+        /// the pool actually received rather than by the bucket amount, so it
+        /// never overstates the pool.
+        fn contribute(&mut self, tokens_to_vest: FungibleBucket) -> FungibleBucket {
+            assert!(
+                self.vest_start.is_none(),
+                "{}",
+                VesterError::SetupAlreadyFinished.message()
+            );
+
+            let pool_amount_before = self.pool.get_vault_amount();
+
+            let lp_tokens = self.pool.contribute(tokens_to_vest);
+
+            // Track the amount of tokens the pool actually received
+            self.total_tokens_to_vest += self.pool.get_vault_amount() - pool_amount_before;
+            self.assert_within_max_total_to_vest();
+
+            lp_tokens
+        }
+
+        /// Shrinks the schedule for tokens withdrawn from the locked vault,
+        /// which held `locked_before` tokens, and emits the withdrawal events.
+        fn record_locked_withdrawal(
+            &mut self,
+            tokens: &FungibleBucket,
+            locked_before: Decimal,
+            caller_action: &str,
+        ) {
+            self.rescale_schedule(locked_before, tokens.amount());
+
+            Runtime::emit_event(EmergencyWithdrawalEvent {
+                resource: tokens.resource_address(),
+                amount: tokens.amount(),
+                remaining_locked: self.locked_tokens_vault.amount(),
+            });
+            Runtime::emit_event(LockedVaultChangedEvent {
+                delta: -tokens.amount(),
+                new_balance: self.locked_tokens_vault.amount(),
+                caller_action: caller_action.to_string(),
+            });
+        }
+
+        /// Deposits new tokens into the locked vault and adds them to
+        /// `total_tokens_to_vest`, for `add_tranche` and `add_vesting_funds`.
+        fn add_to_vest(&mut self, tokens: FungibleBucket, caller_action: &str) {
+            assert!(
+                self.vest_start.is_some(),
+                "{}",
+                VesterError::SetupNotFinished.message()
+            );
+            assert!(
+                tokens.amount() > Decimal::ZERO,
+                "{}",
+                VesterError::EmptyBucket.message()
+            );
+
+            let amount = tokens.amount();
+            self.total_tokens_to_vest += amount;
+            self.assert_within_max_total_to_vest();
+            self.locked_tokens_vault.put(tokens);
+            self.last_refill_at = None;
+
+            Runtime::emit_event(LockedVaultChangedEvent {
+                delta: amount,
+                new_balance: self.locked_tokens_vault.amount(),
+                caller_action: caller_action.to_string(),
+            });
+        }
+
+        /// Panics if `total_tokens_to_vest`, together with that of the
+        /// additional vesting tracks, exceeds `max_total_to_vest`, if set. As
+        /// this aborts the transaction, the excess contribution is never made.
+        fn assert_within_max_total_to_vest(&self) {
+            if let Some(max_total) = self.max_total_to_vest {
+                let tracks_total = (1..=self.track_count)
+                    .map(|track_id| self.tracks.get(&track_id).unwrap().total_tokens_to_vest)
+                    .fold(Decimal::ZERO, |total, amount| total + amount);
+                assert!(
+                    self.total_tokens_to_vest + tracks_total <= max_total,
+                    "{}",
+                    VesterError::MaxTotalToVestExceeded.message()
+                );
+            }
+        }
+
+        /// Takes LP tokens from the vault and delivers them to the account.
+        fn deliver_claim(
+            &mut self,
+            lp_token_amount: Decimal,
+            account_address: Global<Account>,
+            receipt_data: Option<ReceiptData>,
+        ) {
+            let lp_tokens = self.lp_tokens_vault.take(lp_token_amount);
+            self.deliver_lp_tokens(lp_tokens, account_address, receipt_data);
+        }
+
+        /// Stores LP tokens and a receipt NFT for the account in the locker.
+        ///
+        /// Given `receipt_data` is also written to the account's earlier
+        /// receipts; without it the new receipt carries over their data.
+        fn deliver_lp_tokens(
+            &mut self,
+            lp_tokens: FungibleBucket,
+            account_address: Global<Account>,
+            receipt_data: Option<ReceiptData>,
+        ) {
+            assert!(
+                self.claims_open(),
+                "{}",
+                VesterError::ClaimDeadlinePassed.message()
+            );
+            assert!(
+                self.claim_target_allowed(account_address),
+                "{}",
+                VesterError::AccountNotAllowed.message()
+            );
+
+            let lp_token_amount = lp_tokens.amount();
+            let claimed = self.get_claimed_amount(account_address) + lp_token_amount;
+            if let Some(max_claim) = self.max_claim_per_account {
+                assert!(
+                    claimed <= max_claim,
+                    "{}",
+                    VesterError::ClaimLimitExceeded.message()
+                );
+            }
+            if self.claimed_per_account.get(&account_address).is_none() {
+                self.claimants.insert(self.claimant_count, account_address);
+                self.claimant_count += 1;
+            }
+            self.claimed_per_account.insert(account_address, claimed);
+
+            self.locker.store(account_address, lp_tokens.into(), true);
+
+            let mut receipt_ids = self
+                .receipts_per_account
+                .get(&account_address)
+                .map(|receipt_ids| receipt_ids.clone())
+                .unwrap_or_default();
+            let receipt_data = match receipt_data {
+                Some(receipt_data) => {
+                    for receipt_id in receipt_ids.iter() {
+                        self.receipt_resource_manager.update_non_fungible_data(
+                            receipt_id,
+                            "receipt_data",
+                            Some(receipt_data.clone()),
+                        );
+                    }
+                    Some(receipt_data)
+                }
+                None => receipt_ids
+                    .last()
+                    .and_then(|receipt_id| self.get_receipt_data(receipt_id.clone())),
+            };
+
+            let receipt = self
+                .receipt_resource_manager
+                .mint_ruid_non_fungible(ClaimReceipt {
+                    lp_amount: lp_token_amount,
+                    claimed_at: Clock::current_time_rounded_to_seconds(),
+                    season: self.season,
+                    receipt_data,
+                });
+            receipt_ids.push(receipt.non_fungible_local_id());
+            self.receipts_per_account
+                .insert(account_address, receipt_ids);
+            self.locker.store(account_address, receipt.into(), true);
+
+            Runtime::emit_event(ClaimEvent {
+                account: account_address,
+                lp_amount: lp_token_amount,
+            });
+        }
+
+        /// Moves the given share of the locked vault into the treasury and
+        /// shrinks the schedule by the same fraction, so that the remaining
+        /// locked tokens keep vesting along the original curve.
+        fn forfeit_locked_to_treasury(&mut self, lp_share: Decimal) {
+            let locked_before = self.locked_tokens_vault.amount();
+            if locked_before == Decimal::ZERO {
+                return;
+            }
+
+            let forfeited_tokens = self.locked_tokens_vault.take_advanced(
+                lp_share * locked_before,
+                WithdrawStrategy::Rounded(RoundingMode::ToZero),
+            );
+            // The forfeited tokens stay accounted for in the treasury
+            self.rescale_schedule(locked_before, Decimal::ZERO);
+
+            self.forfeited_to_treasury += forfeited_tokens.amount();
+            self.treasury_vault.put(forfeited_tokens);
+        }
+
+        /// Scales `total_tokens_to_vest` and `vested_tokens` by the factor
+        /// the locked vault changed by since it held `locked_before`, so that
+        /// the locked tokens keep vesting along the original curve. Adjusting
+        /// only the total would move the vesting target away from what was
+        /// vested so far, making refills stall or jump ahead.
+        ///
+        /// `tokens_removed` are the tokens that left the vest along with the
+        /// change (negative if tokens came in). `schedule_reduction` records
+        /// how much more the total shrank, for `verify_conservation`. If the
+        /// locked vault was empty there is no curve left to keep, and only
+        /// `schedule_reduction` is updated.
+        fn rescale_schedule(&mut self, locked_before: Decimal, tokens_removed: Decimal) {
+            let total_before = self.total_tokens_to_vest;
+            if locked_before > Decimal::ZERO {
+                let factor = self.locked_tokens_vault.amount() / locked_before;
+                self.total_tokens_to_vest *= factor;
+                self.vested_tokens *= factor;
+            }
+
+            self.schedule_reduction += total_before - self.total_tokens_to_vest - tokens_removed;
+        }
+
+        /// Adds `amount` tokens put into the empty locked vault back to the
+        /// schedule as its unvested part. At a vested fraction `f`, the total
+        /// grows by `amount / (1 - f)` and `vested_tokens` by the vested part
+        /// of that, so nothing is released right away and the tokens vest
+        /// along the rest of the curve, as `rescale_schedule` keeps them
+        /// otherwise. Before vesting has started or once it has ended, the
+        /// total simply grows by `amount`.
+        fn rejoin_schedule(&mut self, amount: Decimal) {
+            let vested_fraction = if self.vesting_started() {
+                self.vested_fraction_at(Clock::current_time_rounded_to_seconds())
+            } else {
+                Decimal::ZERO
+            };
+            let added_to_total = if vested_fraction < Decimal::ONE {
+                amount / (Decimal::ONE - vested_fraction)
+            } else {
+                amount
+            };
+
+            self.total_tokens_to_vest += added_to_total;
+            self.vested_tokens += added_to_total - amount;
+            self.schedule_reduction += amount - added_to_total;
+        }
+
+        /// Returns the amount of tokens the pool pays out for `lp_amount` LP
+        /// tokens if it holds `pool_amount` tokens, with the same calculation
+        /// and rounding as the pool's own redemption.
+        fn pool_redemption_amount(&self, lp_amount: Decimal, pool_amount: Decimal) -> Decimal {
+            let amount_owed = PreciseDecimal::from(lp_amount)
+                / PreciseDecimal::from(self.lp_total_supply())
+                * PreciseDecimal::from(pool_amount);
+
+            Decimal::try_from(amount_owed)
+                .unwrap()
+                .checked_round(self.token_divisibility(), RoundingMode::ToNegativeInfinity)
+                .unwrap()
+        }
+
+        /// Returns the redemption fee on `redeemed_amount` tokens, which is
+        /// zero once the vest has ended.
+        fn redemption_fee(&self, redeemed_amount: Decimal) -> Decimal {
+            if self.is_fully_vested() {
+                return Decimal::ZERO;
+            }
+
+            (redeemed_amount * self.redemption_fee_fraction)
+                .checked_round(self.token_divisibility(), RoundingMode::ToZero)
+                .unwrap()
+        }
+
+        /// Returns the fraction of `total_tokens_to_vest` that is vested at
+        /// the given instant, with the elapsed time rounded down to a whole
+        /// number of `vest_granularity` steps. Must only be called once setup
+        /// is finished.
+        fn vested_fraction_at(&self, time: Instant) -> Decimal {
+            self.schedule_fraction_at(
+                time,
+                self.vest_end.unwrap(),
+                self.cliff_end.unwrap(),
+                self.initial_vested_fraction,
+            )
+        }
+
+        /// Returns the fraction of a vesting track's tokens that is vested at
+        /// the given instant, along the track's own schedule from
+        /// `vest_start`. Must only be called once setup is finished.
+        fn track_vested_fraction_at(&self, params: &TrackParams, time: Instant) -> Decimal {
+            let cliff_end = self
+                .vest_start
+                .unwrap()
+                .add_days(params.cliff_duration_days)
+                .unwrap();
+
+            self.schedule_fraction_at(
+                time,
+                self.track_vest_end(params),
+                cliff_end,
+                params.initial_vested_fraction,
+            )
+        }
+
+        /// Returns the end of a vesting track's vest. Must only be called once
+        /// setup is finished.
+        fn track_vest_end(&self, params: &TrackParams) -> Instant {
+            self.vest_start
+                .unwrap()
+                .add_days(params.vest_duration_days)
+                .unwrap()
+        }
+
+        /// Returns the vested fraction at the given instant of a schedule
+        /// starting at `vest_start` with the given end, cliff end and initial
+        /// vested fraction, along the component's curve and granularity. Must
+        /// only be called once setup is finished.
+        fn schedule_fraction_at(
+            &self,
+            time: Instant,
+            vest_end: Instant,
+            cliff_end: Instant,
+            initial_vested_fraction: Decimal,
+        ) -> Decimal {
+            let vest_start = self.vest_start.unwrap().seconds_since_unix_epoch;
+            let vest_duration = vest_end.seconds_since_unix_epoch - vest_start;
+
+            let elapsed = (time.seconds_since_unix_epoch - vest_start).clamp(0, vest_duration);
+            let step = self.vest_granularity.seconds();
+            let elapsed = elapsed - elapsed.rem_euclid(step);
+
+            let raw_progress = Decimal::from(elapsed) / Decimal::from(vest_duration);
+
+            let vest_progress = if raw_progress <= Decimal::ZERO {
+                Decimal::ZERO
+            } else if raw_progress >= Decimal::ONE {
+                Decimal::ONE
+            } else {
+                raw_progress
+            };
+
+            let curve_progress = match self.vesting_curve {
+                VestingCurve::Linear => vest_progress,
+                VestingCurve::Quadratic => vest_progress * vest_progress,
+                VestingCurve::SquareRoot => vest_progress.checked_sqrt().unwrap(),
+            };
+
+            // Apply initial vested fraction + curved vesting of the remainder
+            // During the cliff: nothing is available
+            // At vest_start (progress = 0): initial_vested_fraction is available
+            // At vest_end (progress = 1): 100% is available
+            // Formula: initial + (1 - initial) * curve(progress)
+            if time.compare(cliff_end, TimeComparisonOperator::Lt) {
+                Decimal::ZERO
+            } else {
+                initial_vested_fraction + (Decimal::ONE - initial_vested_fraction) * curve_progress
+            }
+        }
+
+        /// Calls `refill` if `auto_refill_on_read` is enabled and vesting has
+        /// started.
+        fn refill_on_read(&mut self) {
+            if self.auto_refill_on_read && self.vesting_started() {
+                self.refill();
+            }
+        }
+
+        /// Returns the seconds elapsed between `vest_start` and the given
+        /// instant, clamped to between zero and the vest duration. Must only
+        /// be called once setup is finished.
+        fn elapsed_seconds_at(&self, time: Instant) -> i64 {
+            let vest_start = self.vest_start.unwrap().seconds_since_unix_epoch;
+            let vest_duration = self.vest_end.unwrap().seconds_since_unix_epoch - vest_start;
+
+            (time.seconds_since_unix_epoch - vest_start).clamp(0, vest_duration)
+        }
+
+        /// Moves the tokens vested as of the given instant from the locked
+        /// vault into the pool. Must only be called once setup is finished.
+        ///
+        /// This cannot be re-entered while `redeem` or another refill is in
+        /// progress: the token to vest is a native fungible resource without
+        /// transfer hooks, the pool and vaults are native objects, and the
+        /// engine rejects any call back into this component while one of its
+        /// methods still holds its state. So no reentrancy guard is needed to
+        /// keep the `vested_tokens` update atomic.
+        fn refill_at(&mut self, time: Instant) {
+            let tokens_to_vest_now = self.tokens_to_vest_at(time);
+
+            if tokens_to_vest_now <= Decimal::ZERO {
+                return;
+            }
+
+            // Once fully vested, sweep the locked vault so no rounding dust
+            // is left behind
+            let tokens = if self.vest_ended_at(time) {
+                self.locked_tokens_vault.take_all()
+            } else {
+                self.locked_tokens_vault.take_advanced(
+                    tokens_to_vest_now,
+                    WithdrawStrategy::Rounded(self.rounding_mode),
+                )
+            };
+            let newly_vested = tokens.amount();
+            self.pool.protected_deposit(tokens);
+
+            self.vested_tokens += newly_vested;
+
+            Runtime::emit_event(RefillEvent {
+                newly_vested,
+                cumulative_vested: self.vested_tokens,
+            });
+        }
+
+        /// Returns the amount of tokens a `refill` at the given instant moves
+        /// from the locked vault into the pool. This is the shortfall against
+        /// the vesting target, rounded to the token's divisibility with
+        /// `rounding_mode` and clamped to what is left in the locked vault, so
+        /// accumulated rounding can never make `refill` take more than exists.
+        /// At or after `vest_end` it is everything that is still locked. Must
+        /// only be called once setup is finished.
+        fn tokens_to_vest_at(&self, time: Instant) -> Decimal {
+            let locked_amount = self.locked_tokens_vault.amount();
+
+            if self.vest_ended_at(time) {
+                return locked_amount;
+            }
+
+            // Target total vested amount at this point in time
+            let vested_tokens_target = self.total_tokens_to_vest * self.vested_fraction_at(time);
+
+            (vested_tokens_target - self.vested_tokens)
+                .checked_round(self.token_divisibility(), self.rounding_mode)
+                .unwrap()
+                .min(locked_amount)
+        }
+
+        /// Returns the divisibility of the vested token.
+        fn token_divisibility(&self) -> u8 {
+            ResourceManager::from(self.locked_tokens_vault.resource_address())
+                .resource_type()
+                .divisibility()
+                .unwrap()
+        }
+
+        /// Returns whether the given instant is at or after `vest_end`. Must
+        /// only be called once setup is finished.
+        fn vest_ended_at(&self, time: Instant) -> bool {
+            time.compare(self.vest_end.unwrap(), TimeComparisonOperator::Gte)
+        }
+
+        /// Returns the claim deadline for the given `vest_end`, or `None` if
+        /// claims never close.
+        fn claim_deadline_after(&self, vest_end: Instant) -> Option<Instant> {
+            self.claim_grace_days
+                .map(|grace_days| vest_end.add_days(grace_days).unwrap())
+        }
+
+        /// Returns whether claims may be delivered to the account, i.e. the
+        /// allowlist is disabled or the account is on it.
+        fn claim_target_allowed(&self, account: Global<Account>) -> bool {
+            !self.allowlist_enabled || self.is_allowed_account(account)
+        }
+
+        /// Returns whether claims are still accepted, i.e. there is no claim
+        /// deadline or it has not been reached yet.
+        fn claims_open(&self) -> bool {
+            match self.claim_deadline {
+                Some(deadline) => {
+                    Clock::current_time_is_strictly_before(deadline, TimePrecision::Second)
+                }
+                None => true,
+            }
+        }
+
+        /// Returns whether setup has finished and the pre-claim period is over.
+        fn vesting_started(&self) -> bool {
+            match self.vest_start {
+                Some(vest_start) => {
+                    Clock::current_time_is_at_or_after(vest_start, TimePrecision::Second)
+                }
+                None => false,
+            }
+        }
+
+        /// Returns the total supply of LP tokens, claimed or not.
+        fn lp_total_supply(&self) -> Decimal {
+            ResourceManager::from(self.lp_tokens_vault.resource_address())
+                .total_supply()
+                .unwrap_or(Decimal::ZERO)
+        }
+
+        // endregion:Helper Methods
     }
 }
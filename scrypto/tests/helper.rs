@@ -1,12 +1,22 @@
 #![allow(dead_code)]
 
-use incentives_vester::incentives_vester_test::*;
+use dummy_account::dummy_unwrapper_test::*;
 use dummy_account::incentives_vester_test::*;
+use incentives_vester::incentives_vester_test::*;
+use incentives_vester::{
+    ClaimReservation, ForfeitPolicy, ReceiptData, ScheduleDescriptor, StateSnapshot, Timeline,
+    TrackInfo, TrackParams, UserPosition, VestGranularity, VesterConfig, VesterError, VesterLimits,
+    VestingCurve,
+};
 use scrypto_test::prelude::*;
 
 /// Standard tolerance for approximate decimal comparisons in tests
 pub const TOLERANCE: Decimal = dec!("0.000000000000001");
 
+/// Standard relative tolerance for `assert_approx_eq_rel`, as a fraction of
+/// the expected value
+pub const REL_TOLERANCE: Decimal = dec!("0.000000000001");
+
 pub struct Helper {
     pub env: TestEnvironment<InMemorySubstateDatabase>,
     pub package_address: PackageAddress,
@@ -15,27 +25,111 @@ pub struct Helper {
     pub token_to_vest: Bucket,
     pub admin_badge: Bucket,
     pub super_admin_badge: Bucket,
+    pub recovery_badge: Bucket,
+    pub guardian_badge: Bucket,
     pub token_address: ResourceAddress,
     pub admin_badge_address: ResourceAddress,
     pub super_admin_badge_address: ResourceAddress,
+    pub recovery_badge_address: ResourceAddress,
+    pub guardian_badge_address: ResourceAddress,
     pub lp_resource_address: ResourceAddress,
 }
 
+/// The instantiation parameters `Helper::new_with` sets the vester up with.
+/// Tests override the fields they care about and take the rest from
+/// `Default`, e.g. `HelperOptions { forfeit_policy, ..Default::default() }`.
+pub struct HelperOptions {
+    pub vest_duration_days: i64,
+    pub initial_vested_fraction: Decimal,
+    pub pre_claim_duration_seconds: i64,
+    pub cliff_duration_days: i64,
+    pub vesting_curve: VestingCurve,
+    pub token_divisibility: u8,
+    pub rounding_mode: RoundingMode,
+    pub redemption_fee_fraction: Decimal,
+    pub forfeit_policy: ForfeitPolicy,
+    pub claim_grace_days: Option<i64>,
+    pub vest_granularity: VestGranularity,
+    pub limits: VesterLimits,
+}
+
+impl Default for HelperOptions {
+    fn default() -> Self {
+        Self {
+            vest_duration_days: 365,
+            initial_vested_fraction: dec!("0.1"),
+            pre_claim_duration_seconds: 604800,
+            cliff_duration_days: 0,
+            vesting_curve: VestingCurve::Linear,
+            token_divisibility: 18,
+            rounding_mode: RoundingMode::ToZero,
+            redemption_fee_fraction: Decimal::ZERO,
+            forfeit_policy: ForfeitPolicy::Redistribute,
+            claim_grace_days: None,
+            vest_granularity: VestGranularity::Second,
+            limits: VesterLimits::default(),
+        }
+    }
+}
+
 impl Helper {
     pub fn new() -> Result<Self, RuntimeError> {
-        Self::new_with_config(365, dec!("0.1"), 604800)
+        Self::new_with(HelperOptions::default())
     }
 
     pub fn new_with_config(
         vest_duration_days: i64,
         initial_vested_fraction: Decimal,
         pre_claim_duration_seconds: i64,
+        cliff_duration_days: i64,
+    ) -> Result<Self, RuntimeError> {
+        Self::new_with_config_and_curve(
+            vest_duration_days,
+            initial_vested_fraction,
+            pre_claim_duration_seconds,
+            cliff_duration_days,
+            VestingCurve::Linear,
+        )
+    }
+
+    pub fn new_with_config_and_curve(
+        vest_duration_days: i64,
+        initial_vested_fraction: Decimal,
+        pre_claim_duration_seconds: i64,
+        cliff_duration_days: i64,
+        vesting_curve: VestingCurve,
     ) -> Result<Self, RuntimeError> {
+        Self::new_with(HelperOptions {
+            vest_duration_days,
+            initial_vested_fraction,
+            pre_claim_duration_seconds,
+            cliff_duration_days,
+            vesting_curve,
+            ..Default::default()
+        })
+    }
+
+    pub fn new_with(options: HelperOptions) -> Result<Self, RuntimeError> {
+        let HelperOptions {
+            vest_duration_days,
+            initial_vested_fraction,
+            pre_claim_duration_seconds,
+            cliff_duration_days,
+            vesting_curve,
+            token_divisibility,
+            rounding_mode,
+            redemption_fee_fraction,
+            forfeit_policy,
+            claim_grace_days,
+            vest_granularity,
+            limits,
+        } = options;
+
         let mut env = TestEnvironmentBuilder::new().build();
 
         // Create test tokens
         let token_to_vest = ResourceBuilder::new_fungible(OwnerRole::None)
-            .divisibility(18)
+            .divisibility(token_divisibility)
             .mint_initial_supply(1_000_000, &mut env)?;
 
         let admin_badge = ResourceBuilder::new_fungible(OwnerRole::None)
@@ -46,10 +140,20 @@ impl Helper {
             .divisibility(0)
             .mint_initial_supply(1, &mut env)?;
 
+        let recovery_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+            .divisibility(0)
+            .mint_initial_supply(1, &mut env)?;
+
+        let guardian_badge = ResourceBuilder::new_fungible(OwnerRole::None)
+            .divisibility(0)
+            .mint_initial_supply(1, &mut env)?;
+
         // Get resource addresses
         let token_address = token_to_vest.resource_address(&mut env)?;
         let admin_badge_address = admin_badge.resource_address(&mut env)?;
         let super_admin_badge_address = super_admin_badge.resource_address(&mut env)?;
+        let recovery_badge_address = recovery_badge.resource_address(&mut env)?;
+        let guardian_badge_address = guardian_badge.resource_address(&mut env)?;
 
         // Compile and publish packages
         let package_address = PackageFactory::compile_and_publish(
@@ -73,17 +177,28 @@ impl Helper {
                 &AccountCreateInput {},
             )?
             .0;
-        let dapp_def_address = ComponentAddress::try_from(dapp_def_account.0.clone()).unwrap();
+        let dapp_def_address = dapp_def_account.0;
 
         // Instantiate the IncentivesVester component using the test stub
         let vester = IncentivesVester::instantiate(
             admin_badge_address,
             super_admin_badge_address,
+            recovery_badge_address,
+            guardian_badge_address,
             vest_duration_days,
             initial_vested_fraction,
             pre_claim_duration_seconds,
+            cliff_duration_days,
+            vesting_curve,
+            rounding_mode,
             token_address,
             dapp_def_address,
+            1,
+            redemption_fee_fraction,
+            forfeit_policy,
+            claim_grace_days,
+            vest_granularity,
+            limits,
             package_address,
             &mut env,
         )?;
@@ -99,13 +214,73 @@ impl Helper {
             token_to_vest: token_to_vest.into(),
             admin_badge: admin_badge.into(),
             super_admin_badge: super_admin_badge.into(),
+            recovery_badge: recovery_badge.into(),
+            guardian_badge: guardian_badge.into(),
             token_address,
             admin_badge_address,
             super_admin_badge_address,
+            recovery_badge_address,
+            guardian_badge_address,
             lp_resource_address,
         })
     }
 
+    pub fn instantiate_with_funds(&mut self, amount: Decimal) -> Result<(), RuntimeError> {
+        self.vester = self.instantiate_funded_vester(amount)?;
+        self.lp_resource_address = self.vester.get_pool_unit_resource_address(&mut self.env)?;
+
+        Ok(())
+    }
+
+    pub fn instantiate_funded_vester(
+        &mut self,
+        amount: Decimal,
+    ) -> Result<IncentivesVester, RuntimeError> {
+        let tokens = self.token_to_vest.take(amount, &mut self.env)?;
+        self.instantiate_vester_with_funds(tokens)
+    }
+
+    pub fn instantiate_vester_with_funds(
+        &mut self,
+        tokens: Bucket,
+    ) -> Result<IncentivesVester, RuntimeError> {
+        let dapp_def_account = self
+            .env
+            .call_function_typed::<_, AccountCreateOutput>(
+                ACCOUNT_PACKAGE,
+                ACCOUNT_BLUEPRINT,
+                ACCOUNT_CREATE_IDENT,
+                &AccountCreateInput {},
+            )?
+            .0;
+
+        let vester = IncentivesVester::instantiate_with_funds(
+            self.admin_badge_address,
+            self.super_admin_badge_address,
+            self.recovery_badge_address,
+            self.guardian_badge_address,
+            365,
+            dec!("0.1"),
+            604800,
+            0,
+            VestingCurve::Linear,
+            RoundingMode::ToZero,
+            self.token_address,
+            dapp_def_account.0,
+            1,
+            Decimal::ZERO,
+            ForfeitPolicy::Redistribute,
+            None,
+            VestGranularity::Second,
+            VesterLimits::default(),
+            FungibleBucket(tokens),
+            self.package_address,
+            &mut self.env,
+        )?;
+
+        Ok(vester)
+    }
+
     pub fn create_pool_units(&mut self, amount: Decimal) -> Result<(), RuntimeError> {
         let tokens = self.token_to_vest.take(amount, &mut self.env)?;
         let fungible_tokens = FungibleBucket(tokens);
@@ -117,6 +292,139 @@ impl Helper {
         Ok(())
     }
 
+    pub fn create_track(&mut self, params: TrackParams) -> Result<u32, RuntimeError> {
+        self.env.disable_auth_module();
+        let track_id = self.vester.create_track(params, &mut self.env)?;
+        self.env.enable_auth_module();
+
+        Ok(track_id)
+    }
+
+    pub fn create_track_pool_units(
+        &mut self,
+        track_id: u32,
+        amount: Decimal,
+    ) -> Result<(), RuntimeError> {
+        let tokens = self.token_to_vest.take(amount, &mut self.env)?;
+
+        self.env.disable_auth_module();
+        self.vester
+            .create_track_pool_units(track_id, FungibleBucket(tokens), &mut self.env)?;
+        self.env.enable_auth_module();
+
+        Ok(())
+    }
+
+    pub fn claim_track(
+        &mut self,
+        track_id: u32,
+        lp_token_amount: Decimal,
+        account: Reference,
+    ) -> Result<Decimal, RuntimeError> {
+        self.env.disable_auth_module();
+        let claimed_amount =
+            self.vester
+                .claim_track(track_id, lp_token_amount, account, &mut self.env)?;
+        self.env.enable_auth_module();
+        Ok(claimed_amount)
+    }
+
+    pub fn refill_track(&mut self, track_id: u32) -> Result<(), RuntimeError> {
+        self.vester.refill_track(track_id, &mut self.env)?;
+        Ok(())
+    }
+
+    pub fn redeem_track(
+        &mut self,
+        track_id: u32,
+        lp_tokens: Bucket,
+    ) -> Result<(Bucket, Decimal, Bucket), RuntimeError> {
+        let (redeemed_tokens, forfeited, unredeemed_lp) =
+            self.vester
+                .redeem_track(track_id, FungibleBucket(lp_tokens), &mut self.env)?;
+        Ok((redeemed_tokens.into(), forfeited, unredeemed_lp.into()))
+    }
+
+    pub fn get_track(&mut self, track_id: u32) -> Result<TrackInfo, RuntimeError> {
+        let track = self.vester.get_track(track_id, &mut self.env)?;
+        Ok(track)
+    }
+
+    pub fn get_track_count(&mut self) -> Result<u32, RuntimeError> {
+        let count = self.vester.get_track_count(&mut self.env)?;
+        Ok(count)
+    }
+
+    pub fn fund_and_reserve(
+        &mut self,
+        amount: Decimal,
+        account: Reference,
+    ) -> Result<(), RuntimeError> {
+        let tokens = self.token_to_vest.take(amount, &mut self.env)?;
+        let fungible_tokens = FungibleBucket(tokens);
+
+        self.env.disable_auth_module();
+        self.vester
+            .fund_and_reserve(fungible_tokens, account, &mut self.env)?;
+        self.env.enable_auth_module();
+
+        Ok(())
+    }
+
+    pub fn get_pending_claims(&mut self) -> Result<Vec<(Reference, Decimal)>, RuntimeError> {
+        let pending_claims = self.vester.get_pending_claims(&mut self.env)?;
+        Ok(pending_claims)
+    }
+
+    pub fn create_pool_units_wrapped(
+        &mut self,
+        backing_tokens: Bucket,
+    ) -> Result<(), RuntimeError> {
+        let (unwrapper, wrapped) = DummyUnwrapper::instantiate_unwrapper(
+            FungibleBucket(backing_tokens),
+            self.dummy_account_package,
+            &mut self.env,
+        )?;
+
+        self.env.disable_auth_module();
+        self.vester
+            .create_pool_units_wrapped(wrapped, Reference(unwrapper.0), &mut self.env)?;
+        self.env.enable_auth_module();
+
+        Ok(())
+    }
+
+    pub fn accelerate_vest_start(&mut self, new_start: Instant) -> Result<(), RuntimeError> {
+        self.env.disable_auth_module();
+        self.vester
+            .accelerate_vest_start(new_start, &mut self.env)?;
+        self.env.enable_auth_module();
+
+        Ok(())
+    }
+
+    pub fn add_tranche(&mut self, amount: Decimal) -> Result<(), RuntimeError> {
+        let tokens = self.token_to_vest.take(amount, &mut self.env)?;
+
+        self.env.disable_auth_module();
+        self.vester
+            .add_tranche(FungibleBucket(tokens), &mut self.env)?;
+        self.env.enable_auth_module();
+
+        Ok(())
+    }
+
+    pub fn add_vesting_funds(&mut self, amount: Decimal) -> Result<(), RuntimeError> {
+        let tokens = self.token_to_vest.take(amount, &mut self.env)?;
+
+        self.env.disable_auth_module();
+        self.vester
+            .add_vesting_funds(FungibleBucket(tokens), &mut self.env)?;
+        self.env.enable_auth_module();
+
+        Ok(())
+    }
+
     pub fn finish_setup(&mut self) -> Result<(), RuntimeError> {
         self.env.disable_auth_module();
         self.vester.finish_setup(&mut self.env)?;
@@ -125,44 +433,666 @@ impl Helper {
         Ok(())
     }
 
+    pub fn set_initial_vested_fraction(&mut self, fraction: Decimal) -> Result<(), RuntimeError> {
+        self.env.disable_auth_module();
+        self.vester
+            .set_initial_vested_fraction(fraction, &mut self.env)?;
+        self.env.enable_auth_module();
+
+        Ok(())
+    }
+
+    pub fn remove_locked_tokens(&mut self) -> Result<Bucket, RuntimeError> {
+        self.env.disable_auth_module();
+        let tokens = self.vester.remove_locked_tokens(&mut self.env)?;
+        self.env.enable_auth_module();
+
+        Ok(tokens.into())
+    }
+
+    pub fn remove_locked_tokens_amount(&mut self, amount: Decimal) -> Result<Bucket, RuntimeError> {
+        self.env.disable_auth_module();
+        let tokens = self
+            .vester
+            .remove_locked_tokens_amount(amount, &mut self.env)?;
+        self.env.enable_auth_module();
+
+        Ok(tokens.into())
+    }
+
+    pub fn put_locked_tokens(&mut self, tokens: Bucket) -> Result<(), RuntimeError> {
+        self.env.disable_auth_module();
+        self.vester
+            .put_locked_tokens(FungibleBucket(tokens), &mut self.env)?;
+        self.env.enable_auth_module();
+
+        Ok(())
+    }
+
+    pub fn set_vest_duration_days(&mut self, days: i64) -> Result<(), RuntimeError> {
+        self.env.disable_auth_module();
+        self.vester.set_vest_duration_days(days, &mut self.env)?;
+        self.env.enable_auth_module();
+
+        Ok(())
+    }
+
+    pub fn withdraw_treasury(&mut self) -> Result<Bucket, RuntimeError> {
+        self.env.disable_auth_module();
+        let fees = self.vester.withdraw_treasury(&mut self.env)?;
+        self.env.enable_auth_module();
+
+        Ok(fees.into())
+    }
+
+    pub fn deposit_stray(&mut self, tokens: Bucket) -> Result<(), RuntimeError> {
+        self.vester.deposit_stray(tokens, &mut self.env)?;
+        Ok(())
+    }
+
+    pub fn rescue_tokens(&mut self, resource: ResourceAddress) -> Result<Bucket, RuntimeError> {
+        self.env.disable_auth_module();
+        let tokens = self.vester.rescue_tokens(resource, &mut self.env)?;
+        self.env.enable_auth_module();
+
+        Ok(tokens)
+    }
+
+    pub fn get_treasury_amount(&mut self) -> Result<Decimal, RuntimeError> {
+        let amount = self.vester.get_treasury_amount(&mut self.env)?;
+        Ok(amount)
+    }
+
+    pub fn verify_conservation(&mut self) -> Result<bool, RuntimeError> {
+        let holds = self.vester.verify_conservation(&mut self.env)?;
+        Ok(holds)
+    }
+
+    pub fn pause(&mut self) -> Result<(), RuntimeError> {
+        self.env.disable_auth_module();
+        self.vester.pause(&mut self.env)?;
+        self.env.enable_auth_module();
+
+        Ok(())
+    }
+
+    pub fn unpause(&mut self) -> Result<(), RuntimeError> {
+        self.env.disable_auth_module();
+        self.vester.unpause(&mut self.env)?;
+        self.env.enable_auth_module();
+
+        Ok(())
+    }
+
+    pub fn is_paused(&mut self) -> Result<bool, RuntimeError> {
+        let paused = self.vester.is_paused(&mut self.env)?;
+        Ok(paused)
+    }
+
+    pub fn set_auto_refill_on_read(&mut self, enabled: bool) -> Result<(), RuntimeError> {
+        self.env.disable_auth_module();
+        self.vester
+            .set_auto_refill_on_read(enabled, &mut self.env)?;
+        self.env.enable_auth_module();
+
+        Ok(())
+    }
+
+    pub fn is_auto_refill_on_read(&mut self) -> Result<bool, RuntimeError> {
+        let enabled = self.vester.is_auto_refill_on_read(&mut self.env)?;
+        Ok(enabled)
+    }
+
+    pub fn set_allowlist_enabled(&mut self, enabled: bool) -> Result<(), RuntimeError> {
+        self.env.disable_auth_module();
+        self.vester.set_allowlist_enabled(enabled, &mut self.env)?;
+        self.env.enable_auth_module();
+
+        Ok(())
+    }
+
+    pub fn add_allowed_account(&mut self, account: Reference) -> Result<(), RuntimeError> {
+        self.env.disable_auth_module();
+        self.vester.add_allowed_account(account, &mut self.env)?;
+        self.env.enable_auth_module();
+
+        Ok(())
+    }
+
+    pub fn remove_allowed_account(&mut self, account: Reference) -> Result<(), RuntimeError> {
+        self.env.disable_auth_module();
+        self.vester.remove_allowed_account(account, &mut self.env)?;
+        self.env.enable_auth_module();
+
+        Ok(())
+    }
+
+    pub fn is_allowed_account(&mut self, account: Reference) -> Result<bool, RuntimeError> {
+        let allowed = self.vester.is_allowed_account(account, &mut self.env)?;
+        Ok(allowed)
+    }
+
+    pub fn set_dapp_definition(&mut self, address: ComponentAddress) -> Result<(), RuntimeError> {
+        self.env.disable_auth_module();
+        self.vester.set_dapp_definition(address, &mut self.env)?;
+        self.env.enable_auth_module();
+
+        Ok(())
+    }
+
+    pub fn get_dapp_definition(&mut self) -> Result<Option<GlobalAddress>, RuntimeError> {
+        let value = self.get_metadata(self.vester.0, "dapp_definition")?;
+
+        Ok(match value {
+            Some(MetadataValue::GlobalAddress(address)) => Some(address),
+            _ => None,
+        })
+    }
+
+    pub fn configure_lp_metadata(
+        &mut self,
+        name: &str,
+        symbol: &str,
+        icon_url: &str,
+    ) -> Result<(), RuntimeError> {
+        self.env.disable_auth_module();
+        self.vester.configure_lp_metadata(
+            name.to_string(),
+            symbol.to_string(),
+            icon_url.to_string(),
+            &mut self.env,
+        )?;
+        self.env.enable_auth_module();
+
+        Ok(())
+    }
+
+    pub fn get_metadata<N: Into<NodeId>>(
+        &mut self,
+        node_id: N,
+        key: &str,
+    ) -> Result<Option<MetadataValue>, RuntimeError> {
+        self.env
+            .call_module_method_typed::<_, _, MetadataGetOutput>(
+                node_id,
+                AttachedModuleId::Metadata,
+                METADATA_GET_IDENT,
+                &MetadataGetInput {
+                    key: key.to_string(),
+                },
+            )
+    }
+
+    pub fn create_account(&mut self) -> Result<ComponentAddress, RuntimeError> {
+        let account = self
+            .env
+            .call_function_typed::<_, AccountCreateOutput>(
+                ACCOUNT_PACKAGE,
+                ACCOUNT_BLUEPRINT,
+                ACCOUNT_CREATE_IDENT,
+                &AccountCreateInput {},
+            )?
+            .0;
+
+        Ok(account.0)
+    }
+
     pub fn refill(&mut self) -> Result<(), RuntimeError> {
         self.vester.refill(&mut self.env)?;
         Ok(())
     }
 
+    pub fn refill_to(&mut self, instant: Instant) -> Result<(), RuntimeError> {
+        self.vester.refill_to(instant, &mut self.env)?;
+        Ok(())
+    }
+
     pub fn get_vested_tokens(&mut self) -> Result<Decimal, RuntimeError> {
         let value = self.vester.get_vested_tokens(&mut self.env)?;
         Ok(value)
     }
 
+    pub fn get_realized_rate(&mut self) -> Result<Decimal, RuntimeError> {
+        let rate = self.vester.get_realized_rate(&mut self.env)?;
+        Ok(rate)
+    }
+
     pub fn get_total_tokens_to_vest(&mut self) -> Result<Decimal, RuntimeError> {
         let value = self.vester.get_total_tokens_to_vest(&mut self.env)?;
         Ok(value)
     }
 
+    pub fn get_vested_fraction(&mut self) -> Result<Decimal, RuntimeError> {
+        let value = self.vester.get_vested_fraction(&mut self.env)?;
+        Ok(value)
+    }
+
+    pub fn projected_vested_at(&mut self, instant: Instant) -> Result<Decimal, RuntimeError> {
+        let value = self.vester.projected_vested_at(instant, &mut self.env)?;
+        Ok(value)
+    }
+
+    pub fn get_vesting_seconds_remaining(&mut self) -> Result<i64, RuntimeError> {
+        let value = self.vester.get_vesting_seconds_remaining(&mut self.env)?;
+        Ok(value)
+    }
+
+    pub fn get_elapsed_seconds(&mut self) -> Result<i64, RuntimeError> {
+        let seconds = self.vester.get_elapsed_seconds(&mut self.env)?;
+        Ok(seconds)
+    }
+
+    pub fn get_daily_emission(&mut self) -> Result<Decimal, RuntimeError> {
+        let emission = self.vester.get_daily_emission(&mut self.env)?;
+        Ok(emission)
+    }
+
+    pub fn get_pre_claim_seconds_remaining(&mut self) -> Result<i64, RuntimeError> {
+        let value = self.vester.get_pre_claim_seconds_remaining(&mut self.env)?;
+        Ok(value)
+    }
+
+    pub fn is_fully_vested(&mut self) -> Result<bool, RuntimeError> {
+        let fully_vested = self.vester.is_fully_vested(&mut self.env)?;
+        Ok(fully_vested)
+    }
+
+    pub fn get_forfeited_tokens(&mut self) -> Result<Decimal, RuntimeError> {
+        let value = self.vester.get_forfeited_tokens(&mut self.env)?;
+        Ok(value)
+    }
+
+    pub fn get_total_redeemed(&mut self) -> Result<Decimal, RuntimeError> {
+        let value = self.vester.get_total_redeemed(&mut self.env)?;
+        Ok(value)
+    }
+
+    pub fn get_position(&mut self, lp_amount: Decimal) -> Result<UserPosition, RuntimeError> {
+        let position = self.vester.get_position(lp_amount, &mut self.env)?;
+        Ok(position)
+    }
+
+    pub fn get_receipt_data(
+        &mut self,
+        local_id: NonFungibleLocalId,
+    ) -> Result<Option<ReceiptData>, RuntimeError> {
+        let receipt_data = self.vester.get_receipt_data(local_id, &mut self.env)?;
+        Ok(receipt_data)
+    }
+
+    pub fn get_admin_badges(&mut self) -> Result<(ResourceAddress, ResourceAddress), RuntimeError> {
+        let badges = self.vester.get_admin_badges(&mut self.env)?;
+        Ok(badges)
+    }
+
+    pub fn get_receipt_resource_address(&mut self) -> Result<ResourceAddress, RuntimeError> {
+        let address = self.vester.get_receipt_resource_address(&mut self.env)?;
+        Ok(address)
+    }
+
+    pub fn is_redeemable_resource(
+        &mut self,
+        resource: ResourceAddress,
+    ) -> Result<bool, RuntimeError> {
+        let redeemable = self
+            .vester
+            .is_redeemable_resource(resource, &mut self.env)?;
+        Ok(redeemable)
+    }
+
+    pub fn get_pool_address(&mut self) -> Result<Reference, RuntimeError> {
+        let address = self.vester.get_pool_address(&mut self.env)?;
+        Ok(address)
+    }
+
+    pub fn get_locker_address(&mut self) -> Result<Reference, RuntimeError> {
+        let address = self.vester.get_locker_address(&mut self.env)?;
+        Ok(address)
+    }
+
+    pub fn get_cliff_end(&mut self) -> Result<Option<Instant>, RuntimeError> {
+        let value = self.vester.get_cliff_end(&mut self.env)?;
+        Ok(value)
+    }
+
+    pub fn get_config(&mut self) -> Result<VesterConfig, RuntimeError> {
+        let config = self.vester.get_config(&mut self.env)?;
+        Ok(config)
+    }
+
+    pub fn get_timeline(&mut self) -> Result<Timeline, RuntimeError> {
+        let timeline = self.vester.get_timeline(&mut self.env)?;
+        Ok(timeline)
+    }
+
+    pub fn get_schedule_descriptor(&mut self) -> Result<ScheduleDescriptor, RuntimeError> {
+        let descriptor = self.vester.get_schedule_descriptor(&mut self.env)?;
+        Ok(descriptor)
+    }
+
     pub fn get_lp_token_amount(&mut self) -> Result<Decimal, RuntimeError> {
         let amount = self.vester.get_lp_token_amount(&mut self.env)?;
         Ok(amount)
     }
 
+    pub fn setup_migration_to(
+        &mut self,
+        new_vester: &mut IncentivesVester,
+    ) -> Result<(), RuntimeError> {
+        self.env.disable_auth_module();
+        new_vester.finish_setup(&mut self.env)?;
+        new_vester.accept_migration_from(Reference(self.vester.0), &mut self.env)?;
+        self.vester
+            .authorize_migration(Reference(new_vester.0), &mut self.env)?;
+        self.env.enable_auth_module();
+
+        Ok(())
+    }
+
+    pub fn authorize_migration(&mut self, new_vester: Reference) -> Result<(), RuntimeError> {
+        self.env.disable_auth_module();
+        self.vester.authorize_migration(new_vester, &mut self.env)?;
+        self.env.enable_auth_module();
+
+        Ok(())
+    }
+
+    pub fn accept_migration_from(&mut self, old_vester: Reference) -> Result<(), RuntimeError> {
+        self.env.disable_auth_module();
+        self.vester
+            .accept_migration_from(old_vester, &mut self.env)?;
+        self.env.enable_auth_module();
+
+        Ok(())
+    }
+
+    pub fn accept_migrated_tokens(&mut self, tokens: Bucket) -> Result<Bucket, RuntimeError> {
+        self.env.disable_auth_module();
+        let lp_tokens = self
+            .vester
+            .accept_migrated_tokens(FungibleBucket(tokens), &mut self.env)?;
+        self.env.enable_auth_module();
+
+        Ok(lp_tokens.into())
+    }
+
+    pub fn migrate_lp_from_account(
+        &mut self,
+        dummy_account: &mut DummyAccount,
+        amount: Decimal,
+    ) -> Result<Bucket, RuntimeError> {
+        let lp_tokens = dummy_account.withdraw(self.lp_resource_address, amount, &mut self.env)?;
+        let new_lp_tokens = self
+            .vester
+            .migrate(FungibleBucket(lp_tokens), &mut self.env)?;
+        Ok(new_lp_tokens.into())
+    }
+
+    pub fn get_lp_total_supply(&mut self) -> Result<Decimal, RuntimeError> {
+        let supply = self.vester.get_lp_total_supply(&mut self.env)?;
+        Ok(supply)
+    }
+
+    pub fn get_distributed_lp(&mut self) -> Result<Decimal, RuntimeError> {
+        let amount = self.vester.get_distributed_lp(&mut self.env)?;
+        Ok(amount)
+    }
+
     pub fn get_maturity_value(&mut self) -> Result<Decimal, RuntimeError> {
         let value = self.vester.get_maturity_value(&mut self.env)?;
         Ok(value)
     }
 
-    pub fn claim(&mut self, lp_token_amount: Decimal, account: Reference) -> Result<(), RuntimeError> {
+    pub fn simulate_maturity_after_redemption(
+        &mut self,
+        fraction_redeemed_now: Decimal,
+    ) -> Result<Decimal, RuntimeError> {
+        let value = self
+            .vester
+            .simulate_maturity_after_redemption(fraction_redeemed_now, &mut self.env)?;
+        Ok(value)
+    }
+
+    pub fn claim(
+        &mut self,
+        lp_token_amount: Decimal,
+        account: Reference,
+    ) -> Result<Decimal, RuntimeError> {
+        self.env.disable_auth_module();
+        let claimed_amount = self
+            .vester
+            .claim(lp_token_amount, account, None, &mut self.env)?;
+        self.env.enable_auth_module();
+        Ok(claimed_amount)
+    }
+
+    pub fn claim_with_receipt_data(
+        &mut self,
+        lp_token_amount: Decimal,
+        account: Reference,
+        receipt_data: ReceiptData,
+    ) -> Result<Decimal, RuntimeError> {
+        self.env.disable_auth_module();
+        let claimed_amount =
+            self.vester
+                .claim(lp_token_amount, account, Some(receipt_data), &mut self.env)?;
+        self.env.enable_auth_module();
+        Ok(claimed_amount)
+    }
+
+    pub fn claim_up_to(
+        &mut self,
+        lp_token_amount: Decimal,
+        account: Reference,
+    ) -> Result<Decimal, RuntimeError> {
+        self.env.disable_auth_module();
+        let claimed_amount = self
+            .vester
+            .claim_up_to(lp_token_amount, account, &mut self.env)?;
+        self.env.enable_auth_module();
+        Ok(claimed_amount)
+    }
+
+    pub fn claim_bucket(
+        &mut self,
+        lp_bucket: Bucket,
+        account: Reference,
+    ) -> Result<(), RuntimeError> {
+        self.env.disable_auth_module();
+        self.vester
+            .claim_bucket(FungibleBucket(lp_bucket), account, &mut self.env)?;
+        self.env.enable_auth_module();
+        Ok(())
+    }
+
+    pub fn remove_lp(&mut self) -> Result<Bucket, RuntimeError> {
+        self.env.disable_auth_module();
+        let lp_tokens = self.vester.remove_lp(&mut self.env)?;
+        self.env.enable_auth_module();
+        Ok(lp_tokens.0)
+    }
+
+    pub fn remove_lp_amount(&mut self, amount: Decimal) -> Result<Bucket, RuntimeError> {
+        self.env.disable_auth_module();
+        let lp_tokens = self.vester.remove_lp_amount(amount, &mut self.env)?;
+        self.env.enable_auth_module();
+        Ok(lp_tokens.0)
+    }
+
+    pub fn reclaim_unclaimed(&mut self) -> Result<Bucket, RuntimeError> {
+        self.env.disable_auth_module();
+        let lp_tokens = self.vester.reclaim_unclaimed(&mut self.env)?;
+        self.env.enable_auth_module();
+        Ok(lp_tokens.0)
+    }
+
+    pub fn claim_batch(&mut self, claims: Vec<(Decimal, Reference)>) -> Result<(), RuntimeError> {
         self.env.disable_auth_module();
-        self.vester.claim(lp_token_amount, account, &mut self.env)?;
+        self.vester.claim_batch(claims, &mut self.env)?;
         self.env.enable_auth_module();
         Ok(())
     }
 
-    pub fn redeem(&mut self, lp_tokens: Bucket) -> Result<Bucket, RuntimeError> {
+    pub fn claim_weighted(
+        &mut self,
+        total: Decimal,
+        recipients: Vec<(Reference, Decimal)>,
+    ) -> Result<(), RuntimeError> {
+        self.env.disable_auth_module();
+        self.vester
+            .claim_weighted(total, recipients, &mut self.env)?;
+        self.env.enable_auth_module();
+        Ok(())
+    }
+
+    pub fn claim_idempotent(
+        &mut self,
+        total_lp_amount: Decimal,
+        account: Reference,
+    ) -> Result<(), RuntimeError> {
+        self.env.disable_auth_module();
+        self.vester
+            .claim_idempotent(total_lp_amount, account, &mut self.env)?;
+        self.env.enable_auth_module();
+        Ok(())
+    }
+
+    pub fn reserve_claim(
+        &mut self,
+        lp_amount: Decimal,
+        account: Reference,
+        expiry: Instant,
+    ) -> Result<(), RuntimeError> {
+        self.env.disable_auth_module();
+        self.vester
+            .reserve_claim(lp_amount, account, expiry, &mut self.env)?;
+        self.env.enable_auth_module();
+        Ok(())
+    }
+
+    pub fn finalize_claim(&mut self, account: Reference) -> Result<Decimal, RuntimeError> {
+        self.env.disable_auth_module();
+        let amount = self.vester.finalize_claim(account, &mut self.env)?;
+        self.env.enable_auth_module();
+        Ok(amount)
+    }
+
+    pub fn release_expired(&mut self, account: Reference) -> Result<Decimal, RuntimeError> {
+        let amount = self.vester.release_expired(account, &mut self.env)?;
+        Ok(amount)
+    }
+
+    pub fn get_reservation(
+        &mut self,
+        account: Reference,
+    ) -> Result<Option<ClaimReservation>, RuntimeError> {
+        let reservation = self.vester.get_reservation(account, &mut self.env)?;
+        Ok(reservation)
+    }
+
+    pub fn get_claimants(
+        &mut self,
+        start: u64,
+        count: u64,
+    ) -> Result<Vec<Reference>, RuntimeError> {
+        let claimants = self.vester.get_claimants(start, count, &mut self.env)?;
+        Ok(claimants)
+    }
+
+    pub fn get_claimant_count(&mut self) -> Result<u64, RuntimeError> {
+        let count = self.vester.get_claimant_count(&mut self.env)?;
+        Ok(count)
+    }
+
+    pub fn get_claimed_amount(&mut self, account: Reference) -> Result<Decimal, RuntimeError> {
+        let amount = self.vester.get_claimed_amount(account, &mut self.env)?;
+        Ok(amount)
+    }
+
+    pub fn has_claimed_at_least(
+        &mut self,
+        account: Reference,
+        amount: Decimal,
+    ) -> Result<bool, RuntimeError> {
+        let claimed = self
+            .vester
+            .has_claimed_at_least(account, amount, &mut self.env)?;
+        Ok(claimed)
+    }
+
+    pub fn get_locker_balance(&mut self, account: Reference) -> Result<Decimal, RuntimeError> {
+        let amount = self.vester.get_locker_balance(account, &mut self.env)?;
+        Ok(amount)
+    }
+
+    pub fn can_claim(
+        &mut self,
+        lp_amount: Decimal,
+        account: Reference,
+    ) -> Result<bool, RuntimeError> {
+        let can_claim = self.vester.can_claim(lp_amount, account, &mut self.env)?;
+        Ok(can_claim)
+    }
+
+    pub fn redeem(&mut self, lp_tokens: Bucket) -> Result<(Bucket, Decimal), RuntimeError> {
+        let (redeemed_tokens, forfeited, _) = self.redeem_with_unredeemed_lp(lp_tokens)?;
+        Ok((redeemed_tokens, forfeited))
+    }
+
+    pub fn redeem_with_unredeemed_lp(
+        &mut self,
+        lp_tokens: Bucket,
+    ) -> Result<(Bucket, Decimal, Bucket), RuntimeError> {
         let fungible_lp_tokens = FungibleBucket(lp_tokens);
-        let redeemed_tokens = self.vester.redeem(fungible_lp_tokens, &mut self.env)?;
+        let (redeemed_tokens, forfeited, unredeemed_lp) =
+            self.vester.redeem(fungible_lp_tokens, &mut self.env)?;
+        Ok((redeemed_tokens.into(), forfeited, unredeemed_lp.into()))
+    }
+
+    pub fn redeem_to(
+        &mut self,
+        lp_tokens: Bucket,
+        account: Reference,
+    ) -> Result<Bucket, RuntimeError> {
+        let unredeemed_lp =
+            self.vester
+                .redeem_to(FungibleBucket(lp_tokens), account, &mut self.env)?;
+        Ok(unredeemed_lp.into())
+    }
+
+    pub fn redeem_with_min_out(
+        &mut self,
+        lp_tokens: Bucket,
+        min_out: Decimal,
+    ) -> Result<Bucket, RuntimeError> {
+        let (redeemed_tokens, _) =
+            self.vester
+                .redeem_with_min_out(FungibleBucket(lp_tokens), min_out, &mut self.env)?;
         Ok(redeemed_tokens.into())
     }
 
+    pub fn redeem_amount(
+        &mut self,
+        lp_tokens: Bucket,
+        amount: Decimal,
+    ) -> Result<(Bucket, Bucket), RuntimeError> {
+        let fungible_lp_tokens = FungibleBucket(lp_tokens);
+        let (redeemed_tokens, leftover_lp) =
+            self.vester
+                .redeem_amount(fungible_lp_tokens, amount, &mut self.env)?;
+        Ok((redeemed_tokens.into(), leftover_lp.into()))
+    }
+
+    pub fn get_total_redeemable_now(&mut self) -> Result<Decimal, RuntimeError> {
+        let value = self.vester.get_total_redeemable_now(&mut self.env)?;
+        Ok(value)
+    }
+
+    pub fn preview_redeem(&mut self, lp_amount: Decimal) -> Result<Decimal, RuntimeError> {
+        let value = self.vester.preview_redeem(lp_amount, &mut self.env)?;
+        Ok(value)
+    }
+
     pub fn get_pool_vault_amount(&mut self) -> Result<Decimal, RuntimeError> {
         let amount = self.vester.get_pool_vault_amount(&mut self.env)?;
         Ok(amount)
@@ -173,6 +1103,11 @@ impl Helper {
         Ok(amount)
     }
 
+    pub fn get_state_snapshot(&mut self) -> Result<StateSnapshot, RuntimeError> {
+        let snapshot = self.vester.get_state_snapshot(&mut self.env)?;
+        Ok(snapshot)
+    }
+
     pub fn get_lp_resource_address(&self) -> ResourceAddress {
         self.lp_resource_address
     }
@@ -183,7 +1118,7 @@ impl Helper {
             &mut self.env,
         )?;
 
-        Ok((dummy_account, account.into()))
+        Ok((dummy_account, account))
     }
 
     pub fn get_account_balance(&mut self, dummy_account: &DummyAccount, resource_address: ResourceAddress) -> Result<Decimal, RuntimeError> {
@@ -196,32 +1131,225 @@ impl Helper {
         Ok(bucket)
     }
 
+    pub fn get_receipt_ids(
+        &mut self,
+        dummy_account: &mut DummyAccount,
+    ) -> Result<Vec<NonFungibleLocalId>, RuntimeError> {
+        let receipt_resource = self.get_receipt_resource_address()?;
+        let amount = self.get_account_balance(dummy_account, receipt_resource)?;
+        let receipts = self.withdraw_from_account(dummy_account, receipt_resource, amount)?;
+        let receipt_ids = receipts.non_fungible_local_ids(&mut self.env)?;
+        Ok(receipt_ids.into_iter().collect())
+    }
+
+    pub fn reject_deposits(
+        &mut self,
+        dummy_account: &mut DummyAccount,
+    ) -> Result<(), RuntimeError> {
+        dummy_account.reject_deposits(&mut self.env)?;
+        Ok(())
+    }
+
+    pub fn recover_from_locker(
+        &mut self,
+        account: Reference,
+        amount: Decimal,
+    ) -> Result<Bucket, RuntimeError> {
+        self.env.disable_auth_module();
+        let recovered = self
+            .vester
+            .recover_from_locker(account, amount, &mut self.env)?;
+        self.env.enable_auth_module();
+        Ok(recovered.into())
+    }
+
+    /// Recovers from the locker with auth enabled, presenting only a proof of
+    /// the given badge.
+    pub fn recover_from_locker_with_badge(
+        &mut self,
+        account: Reference,
+        amount: Decimal,
+        badge_address: ResourceAddress,
+    ) -> Result<Bucket, RuntimeError> {
+        self.push_badge_proof(badge_address)?;
+
+        let recovered = self
+            .vester
+            .recover_from_locker(account, amount, &mut self.env);
+        LocalAuthZone::drop_proofs(&mut self.env)?;
+
+        Ok(recovered?.into())
+    }
+
+    pub fn pause_with_badge(&mut self, badge_address: ResourceAddress) -> Result<(), RuntimeError> {
+        self.push_badge_proof(badge_address)?;
+
+        let result = self.vester.pause(&mut self.env);
+        LocalAuthZone::drop_proofs(&mut self.env)?;
+
+        result
+    }
+
+    pub fn unpause_with_badge(
+        &mut self,
+        badge_address: ResourceAddress,
+    ) -> Result<(), RuntimeError> {
+        self.push_badge_proof(badge_address)?;
+
+        let result = self.vester.unpause(&mut self.env);
+        LocalAuthZone::drop_proofs(&mut self.env)?;
+
+        result
+    }
+
+    pub fn claim_with_badge(
+        &mut self,
+        lp_token_amount: Decimal,
+        account: Reference,
+        badge_address: ResourceAddress,
+    ) -> Result<Decimal, RuntimeError> {
+        self.push_badge_proof(badge_address)?;
+
+        let result = self
+            .vester
+            .claim(lp_token_amount, account, None, &mut self.env);
+        LocalAuthZone::drop_proofs(&mut self.env)?;
+
+        result
+    }
+
+    pub fn remove_lp_with_badge(
+        &mut self,
+        badge_address: ResourceAddress,
+    ) -> Result<Bucket, RuntimeError> {
+        self.push_badge_proof(badge_address)?;
+
+        let lp_tokens = self.vester.remove_lp(&mut self.env);
+        LocalAuthZone::drop_proofs(&mut self.env)?;
+
+        Ok(lp_tokens?.into())
+    }
+
+    /// Pushes a proof of the badge with the given address onto the auth zone.
+    fn push_badge_proof(&mut self, badge_address: ResourceAddress) -> Result<(), RuntimeError> {
+        let badge = if badge_address == self.recovery_badge_address {
+            &self.recovery_badge
+        } else if badge_address == self.guardian_badge_address {
+            &self.guardian_badge
+        } else if badge_address == self.admin_badge_address {
+            &self.admin_badge
+        } else {
+            &self.super_admin_badge
+        };
+        let proof = badge.create_proof_of_all(&mut self.env)?;
+        LocalAuthZone::push(proof, &mut self.env)?;
+
+        Ok(())
+    }
+
     pub fn redeem_lp_from_account(&mut self, dummy_account: &mut DummyAccount, lp_resource_address: ResourceAddress, amount: Decimal) -> Result<Bucket, RuntimeError> {
         // Withdraw LP tokens from the dummy account
         let lp_tokens = dummy_account.withdraw(lp_resource_address, amount, &mut self.env)?;
 
         // Redeem them through the vester
-        let redeemed_tokens = self.redeem(lp_tokens)?;
+        let (redeemed_tokens, _) = self.redeem(lp_tokens)?;
 
         Ok(redeemed_tokens)
     }
 
+    pub fn redeem_lp_from_account_with_forfeited(
+        &mut self,
+        dummy_account: &mut DummyAccount,
+        lp_resource_address: ResourceAddress,
+        amount: Decimal,
+    ) -> Result<(Bucket, Decimal), RuntimeError> {
+        let lp_tokens = dummy_account.withdraw(lp_resource_address, amount, &mut self.env)?;
+        self.redeem(lp_tokens)
+    }
+
+    /// Moves the clock forward. Vesting assumes time only moves forward, so
+    /// a non-positive number of days is a bug in the test.
     pub fn advance_time_days(&mut self, days: i64) {
+        assert!(
+            days > 0,
+            "advance_time_days must move the clock forward, got {} days",
+            days
+        );
         let current_time = self.env.get_current_time();
         let new_time = current_time.add_days(days).unwrap();
-        self.env.set_current_time(new_time);
+        self.set_time_forward(current_time, new_time);
     }
 
+    /// Moves the clock forward. Vesting assumes time only moves forward, so
+    /// a non-positive number of seconds is a bug in the test.
     pub fn advance_time_seconds(&mut self, seconds: i64) {
+        assert!(
+            seconds > 0,
+            "advance_time_seconds must move the clock forward, got {} seconds",
+            seconds
+        );
         let current_time = self.env.get_current_time();
         let new_time = current_time.add_seconds(seconds).unwrap();
+        self.set_time_forward(current_time, new_time);
+    }
+
+    fn set_time_forward(&mut self, current_time: Instant, new_time: Instant) {
+        assert!(
+            new_time > current_time,
+            "Time must move forward: {:?} is not after {:?}",
+            new_time,
+            current_time
+        );
         self.env.set_current_time(new_time);
     }
 
+    pub fn advance_to_vest_start(&mut self) -> Result<(), RuntimeError> {
+        let vest_start = self
+            .get_config()?
+            .vest_start
+            .expect("Setup not finished yet");
+        self.env.set_current_time(vest_start);
+        Ok(())
+    }
+
+    /// Sets the clock to the given fraction of the way from `vest_start` to
+    /// `vest_end`, rounded down to the second.
+    pub fn advance_to_progress(&mut self, progress: Decimal) -> Result<(), RuntimeError> {
+        let config = self.get_config()?;
+        let vest_start = config.vest_start.expect("Setup not finished yet");
+        let vest_seconds =
+            config.vest_end.unwrap().seconds_since_unix_epoch - vest_start.seconds_since_unix_epoch;
+
+        let elapsed = (Decimal::from(vest_seconds) * progress)
+            .checked_floor()
+            .unwrap();
+        let elapsed_seconds: i64 = elapsed.to_string().parse().unwrap();
+
+        self.env
+            .set_current_time(vest_start.add_seconds(elapsed_seconds).unwrap());
+        Ok(())
+    }
+
+    pub fn advance_to_fully_vested(&mut self) -> Result<(), RuntimeError> {
+        let vest_end = self.get_config()?.vest_end.expect("Setup not finished yet");
+        self.env.set_current_time(vest_end);
+        Ok(())
+    }
+
     pub fn get_pool_redemption_value(&mut self, lp_amount: Decimal) -> Result<Decimal, RuntimeError> {
         let value = self.vester.get_pool_redemption_value(lp_amount, &mut self.env)?;
         Ok(value)
     }
+
+    pub fn get_redemption_value_current(
+        &mut self,
+        lp_amount: Decimal,
+    ) -> Result<Decimal, RuntimeError> {
+        let value = self
+            .vester
+            .get_redemption_value_current(lp_amount, &mut self.env)?;
+        Ok(value)
+    }
 }
 
 /// Assert that a value is within a tolerance of an expected value
@@ -242,3 +1370,39 @@ pub fn assert_approx_eq(actual: Decimal, expected: Decimal, tolerance: Decimal,
         tolerance
     );
 }
+
+/// Assert that a value is within a tolerance relative to the size of the
+/// expected value, i.e. `|expected| * rel_tolerance`. The tolerance never
+/// drops below the absolute `TOLERANCE`, so an expected value of zero still
+/// allows for the last decimal place.
+pub fn assert_approx_eq_rel(
+    actual: Decimal,
+    expected: Decimal,
+    rel_tolerance: Decimal,
+    message: &str,
+) {
+    let magnitude = if expected < Decimal::ZERO {
+        -expected
+    } else {
+        expected
+    };
+    let tolerance = (magnitude * rel_tolerance).max(TOLERANCE);
+
+    assert_approx_eq(actual, expected, tolerance, message);
+}
+
+/// Asserts that `result` failed with the panic message of `error`
+pub fn assert_vester_error<T: std::fmt::Debug>(
+    result: Result<T, RuntimeError>,
+    error: VesterError,
+) {
+    let err = result.expect_err("expected the call to fail");
+    let err = format!("{:?}", err);
+    assert!(
+        err.contains(error.message()),
+        "expected {:?} (\"{}\"), got {}",
+        error,
+        error.message(),
+        err
+    );
+}
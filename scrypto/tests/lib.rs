@@ -1,5 +1,9 @@
 mod helper;
-use helper::Helper;
+use helper::{Helper, HelperOptions};
+use incentives_vester::{
+    ClaimReservation, ForfeitPolicy, ReceiptData, TrackParams, VestGranularity, VesterError,
+    VesterLimits, VestingCurve, MAX_VEST_DURATION_DAYS, MIN_PRE_CLAIM_DURATION_SECONDS,
+};
 use scrypto_test::prelude::*;
 
 // ==================== Basic Tests ====================
@@ -11,6 +15,17 @@ fn test_instantiate() -> Result<(), RuntimeError> {
     Ok(())
 }
 
+#[test]
+fn test_get_admin_badges() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    let (admin_badge, super_admin_badge) = helper.get_admin_badges()?;
+    assert_eq!(admin_badge, helper.admin_badge_address);
+    assert_eq!(super_admin_badge, helper.super_admin_badge_address);
+
+    Ok(())
+}
+
 #[test]
 fn test_create_pool_units_once() -> Result<(), RuntimeError> {
     let mut helper = Helper::new()?;
@@ -36,6 +51,90 @@ fn test_create_pool_units_multiple_times() -> Result<(), RuntimeError> {
     Ok(())
 }
 
+#[test]
+fn test_total_tokens_to_vest_matches_pool_received_amount() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("5000"))?;
+    helper.create_pool_units(dec!("3000"))?;
+
+    // Recorded from what the pool received, not from the deposited buckets
+    assert_eq!(
+        helper.get_total_tokens_to_vest()?,
+        helper.get_pool_vault_amount()?
+    );
+    assert_eq!(helper.get_total_tokens_to_vest()?, dec!("8000"));
+
+    Ok(())
+}
+
+#[test]
+fn test_create_pool_units_wrapped() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    let backing_tokens = helper.token_to_vest.take(dec!("10000"), &mut helper.env)?;
+    helper.create_pool_units_wrapped(backing_tokens)?;
+
+    assert_eq!(helper.get_lp_token_amount()?, dec!("10000"));
+    assert_eq!(helper.get_total_tokens_to_vest()?, dec!("10000"));
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "Unwrapped tokens are not the token to vest")]
+fn test_create_pool_units_wrapped_other_token_fails() {
+    let mut helper = Helper::new().unwrap();
+
+    let other_tokens = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(10000, &mut helper.env)
+        .unwrap();
+
+    // This should panic
+    helper
+        .create_pool_units_wrapped(other_tokens.into())
+        .unwrap();
+}
+
+#[test]
+fn test_instantiate_with_funds() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.instantiate_with_funds(dec!("10000"))?;
+
+    assert_eq!(helper.get_total_tokens_to_vest()?, dec!("10000"));
+    assert_eq!(helper.get_pool_vault_amount()?, dec!("10000"));
+    assert_eq!(helper.get_lp_token_amount()?, dec!("10000"));
+
+    // Setup can be finished right away, no create_pool_units needed
+    helper.finish_setup()?;
+    assert_eq!(helper.get_locked_vault_amount()?, dec!("10000"));
+
+    Ok(())
+}
+
+#[test]
+fn test_instantiate_with_funds_with_auth_enabled() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    // Funding must not depend on any proof being present
+    helper.env.enable_auth_module();
+    helper.instantiate_with_funds(dec!("10000"))?;
+
+    assert_eq!(helper.get_total_tokens_to_vest()?, dec!("10000"));
+    assert_eq!(helper.get_lp_token_amount()?, dec!("10000"));
+
+    // Later deposits still need the super admin badge
+    let tokens = helper.token_to_vest.take(dec!("1000"), &mut helper.env)?;
+    let result = helper
+        .vester
+        .create_pool_units(FungibleBucket(tokens), &mut helper.env);
+    assert!(result.is_err());
+    assert_eq!(helper.get_total_tokens_to_vest()?, dec!("10000"));
+
+    Ok(())
+}
+
 #[test]
 fn test_finish_setup() -> Result<(), RuntimeError> {
     let mut helper = Helper::new()?;
@@ -81,7 +180,26 @@ fn test_finish_setup_twice_fails() {
 }
 
 #[test]
-#[should_panic(expected = "Vesting setup not complete yet")]
+#[should_panic(expected = "Nothing to vest. Deposit tokens with create_pool_units first.")]
+fn test_finish_setup_without_deposits_fails() {
+    let mut helper = Helper::new().unwrap();
+    helper.finish_setup().unwrap();
+}
+
+#[test]
+#[should_panic(expected = "No LP tokens created; cannot finish setup.")]
+fn test_finish_setup_after_removing_all_lp_fails() {
+    let mut helper = Helper::new().unwrap();
+
+    helper.create_pool_units(dec!("10000")).unwrap();
+    let _ = helper.remove_lp().unwrap();
+
+    // This should panic
+    helper.finish_setup().unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Vesting not set up yet.")]
 fn test_refill_before_setup_fails() {
     let mut helper = Helper::new().unwrap();
 
@@ -133,6 +251,34 @@ fn test_refill_idempotent() -> Result<(), RuntimeError> {
     Ok(())
 }
 
+#[test]
+fn test_refill_at_midpoint_of_century_long_vest() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new_with_config(MAX_VEST_DURATION_DAYS, dec!("0.1"), 604800, 0)?;
+
+    helper.create_pool_units(dec!("1000000"))?;
+    helper.finish_setup()?;
+
+    // Advance to exactly 50% linear progress (50 years from vest_start)
+    helper.advance_time_seconds(604800);
+    helper.advance_time_days(MAX_VEST_DURATION_DAYS / 2);
+    helper.refill()?;
+
+    // vested_fraction = 0.1 + (1 - 0.1) * 0.5 = 0.55
+    // Expected pool: 1000000 * 0.55 = 550000
+    helper::assert_approx_eq(
+        helper.get_pool_vault_amount()?,
+        dec!("550000"),
+        helper::TOLERANCE,
+        "pool at midpoint",
+    );
+    assert_eq!(
+        helper.get_pool_vault_amount()? + helper.get_locked_vault_amount()?,
+        dec!("1000000")
+    );
+
+    Ok(())
+}
+
 #[test]
 fn test_refill_vault_contents_at_checkpoints() -> Result<(), RuntimeError> {
     let mut helper = Helper::new()?;
@@ -255,328 +401,5017 @@ fn test_refill_long_after_vesting_complete() -> Result<(), RuntimeError> {
     Ok(())
 }
 
-// ==================== Maturity Value Tests ====================
-
 #[test]
-fn test_maturity_value_with_no_redemptions() -> Result<(), RuntimeError> {
+fn test_refill_twice_in_same_second_is_noop() -> Result<(), RuntimeError> {
     let mut helper = Helper::new()?;
 
     helper.create_pool_units(dec!("10000"))?;
     helper.finish_setup()?;
 
-    // Advance to vest_start
-    helper.advance_time_seconds(604800);
+    helper.advance_to_progress(dec!("0.5"))?;
     helper.refill()?;
+    let pool_after_first = helper.get_pool_vault_amount()?;
+    let locked_after_first = helper.get_locked_vault_amount()?;
+    let vested_after_first = helper.get_vested_tokens()?;
 
-    // With no redemptions, maturity value should be exactly 1
-    // Formula: (total_tokens / pool_unlocked) * (pool_unlocked / lp_supply) = total_tokens / lp_supply = 10000 / 10000 = 1
-    let maturity_value = helper.get_maturity_value()?;
-
-    helper::assert_approx_eq(
-        maturity_value,
-        dec!("1"),
-        helper::TOLERANCE,
-        "initial maturity value",
-    );
-
-    // Test at 50% vesting - should still be 1
-    helper.advance_time_days(182);
-    helper.advance_time_seconds(43200); // 0.5 days = 43200 seconds
+    // Same clock second: returns right away
     helper.refill()?;
+    assert_eq!(helper.get_pool_vault_amount()?, pool_after_first);
+    assert_eq!(helper.get_locked_vault_amount()?, locked_after_first);
+    assert_eq!(helper.get_vested_tokens()?, vested_after_first);
 
-    let maturity_50 = helper.get_maturity_value()?;
-    helper::assert_approx_eq(
-        maturity_50,
-        dec!("1"),
-        helper::TOLERANCE,
-        "maturity at 50% vesting",
-    );
-
-    // Test at 100% vesting - should still be 1
-    helper.advance_time_days(182);
-    helper.advance_time_seconds(43200); // 0.5 days = 43200 seconds
+    // The next second refills as usual
+    helper.advance_time_seconds(1);
     helper.refill()?;
-
-    let maturity_100 = helper.get_maturity_value()?;
-    helper::assert_approx_eq(
-        maturity_100,
-        dec!("1"),
-        helper::TOLERANCE,
-        "maturity at 100% vesting",
-    );
+    assert!(helper.get_pool_vault_amount()? > pool_after_first);
 
     Ok(())
 }
 
 #[test]
-fn test_redeem_half_doubles_maturity() -> Result<(), RuntimeError> {
+fn test_add_tranche_after_vesting_started() -> Result<(), RuntimeError> {
     let mut helper = Helper::new()?;
 
     helper.create_pool_units(dec!("10000"))?;
     helper.finish_setup()?;
 
-    // Advance to vest_start (0% linear progress, 10% initial vest)
-    helper.advance_time_seconds(604800);
+    // Advance to 50% linear progress (55% total vesting)
+    helper.advance_to_progress(dec!("0.5"))?;
     helper.refill()?;
 
-    let pool_initial = helper.get_pool_vault_amount()?;
-    let locked_initial = helper.get_locked_vault_amount()?;
-    assert_eq!(pool_initial + locked_initial, dec!("10000"));
-
-    let lp_resource = helper.get_lp_resource_address();
-
-    // Initial maturity = 1
-    let maturity_before = helper.get_maturity_value()?;
+    helper.add_tranche(dec!("10000"))?;
+    assert_eq!(helper.get_total_tokens_to_vest()?, dec!("20000"));
     helper::assert_approx_eq(
-        maturity_before,
-        dec!("1"),
+        helper.get_locked_vault_amount()?,
+        dec!("14500"),
         helper::TOLERANCE,
-        "maturity before redemption",
+        "locked after adding the tranche",
     );
 
-    // Claim and redeem 50% of LP tokens
-    let (mut dummy_account, account) = helper.create_dummy_account()?;
-    helper.claim(dec!("5000"), account)?;
-
-    let redeemed_tokens =
-        helper.redeem_lp_from_account(&mut dummy_account, lp_resource, dec!("5000"))?;
-
-    // Verify redeemed amount is exactly 50% of pool
-    let redeemed_amount = redeemed_tokens.amount(&mut helper.env)?;
-    let expected_redeemed = pool_initial / dec!("2");
+    // The next refill catches the tranche up with the schedule: 55% of 20000
+    helper.refill()?;
     helper::assert_approx_eq(
-        redeemed_amount,
-        expected_redeemed,
+        helper.get_pool_vault_amount()?,
+        dec!("11000"),
         helper::TOLERANCE,
-        "redeemed amount",
+        "pool after refill",
     );
-
-    // After redemption:
-    // - Pool had ~1000 tokens (10% initial vest)
-    // - Redeemer got ~500 tokens (50% of pool)
-    // - Pool now has ~500 tokens
-    // - Locked still has ~9000 tokens
-    // - LP tokens remaining: 5000
-    // - Total tokens: 500 + 9000 = 9500
-    // - Maturity = 9500 / 5000 = 1.9
-    let maturity_after = helper.get_maturity_value()?;
-    let pool_after = helper.get_pool_vault_amount()?;
-    let locked_after = helper.get_locked_vault_amount()?;
-
-    let expected_maturity = dec!("1.9");
-    let expected_maturity_2 = (pool_after + locked_after) / dec!("5000");
-
     helper::assert_approx_eq(
-        maturity_after,
-        expected_maturity,
+        helper.get_vested_tokens()?,
+        dec!("11000"),
         helper::TOLERANCE,
-        "maturity after 50% redemption",
+        "vested tokens after refill",
     );
 
+    // The same 10000 LP tokens now mature to 20000 tokens
     helper::assert_approx_eq(
-        expected_maturity,
-        expected_maturity_2,
+        helper.get_maturity_value()?,
+        dec!("2"),
         helper::TOLERANCE,
-        "maturity after 50% redemption",
+        "maturity value",
     );
 
+    // Everything vests by the original vest_end
+    helper.advance_to_fully_vested()?;
+    helper.refill()?;
+    assert_eq!(helper.get_pool_vault_amount()?, dec!("20000"));
+    assert_eq!(helper.get_locked_vault_amount()?, dec!("0"));
+
     Ok(())
 }
 
 #[test]
-fn test_redemption_amounts_at_vesting_stages() -> Result<(), RuntimeError> {
+#[should_panic(expected = "Vesting not set up yet.")]
+fn test_add_tranche_before_setup_fails() {
+    let mut helper = Helper::new().unwrap();
+
+    helper.create_pool_units(dec!("10000")).unwrap();
+    helper.add_tranche(dec!("10000")).unwrap();
+}
+
+#[test]
+fn test_refill_to_catches_up_in_increments() -> Result<(), RuntimeError> {
     let mut helper = Helper::new()?;
 
     helper.create_pool_units(dec!("10000"))?;
     helper.finish_setup()?;
 
-    let lp_resource = helper.get_lp_resource_address();
+    let vest_start = helper.get_config()?.vest_start.unwrap();
 
-    // Stage 1: Redeem at vest_start (0% linear progress, 10% initial vest)
+    // Nobody refilled for the first 75% of the vest
     helper.advance_time_seconds(604800);
-    helper.refill()?;
+    helper.advance_time_days(273);
+    helper.advance_time_seconds(64800);
 
-    let pool_at_0 = helper.get_pool_vault_amount()?;
-    // Pool should have exactly 1000 tokens (10% initial vest)
+    // Catch up to 50%: 0.1 + 0.9 * 0.5
+    helper.refill_to(
+        vest_start
+            .add_days(182)
+            .unwrap()
+            .add_seconds(43200)
+            .unwrap(),
+    )?;
     helper::assert_approx_eq(
-        pool_at_0,
-        dec!("1000"),
+        helper.get_pool_vault_amount()?,
+        dec!("5500"),
         helper::TOLERANCE,
-        "pool at 0% progress",
+        "pool after refill_to 50%",
     );
 
-    let (mut account1, addr1) = helper.create_dummy_account()?;
-    helper.claim(dec!("2000"), addr1)?;
-
-    let redeemed_at_0 = helper.redeem_lp_from_account(&mut account1, lp_resource, dec!("2000"))?;
-    let redeemed_amount_0 = redeemed_at_0.amount(&mut helper.env)?;
-
-    // Should get 20% of pool: 1000 * 0.2 = 200 tokens
-    let expected_redeemed_0 = pool_at_0 * dec!("0.2");
+    // Going back in time moves nothing
+    helper.refill_to(vest_start)?;
     helper::assert_approx_eq(
-        redeemed_amount_0,
-        expected_redeemed_0,
+        helper.get_pool_vault_amount()?,
+        dec!("5500"),
         helper::TOLERANCE,
-        "redeemed at 0% progress",
+        "pool after refill_to vest_start",
     );
 
-    // Stage 2: Redeem at 50% linear progress (55% total vesting)
-    helper.advance_time_days(182);
-    helper.advance_time_seconds(43200); // 0.5 days = 43200 seconds
+    // A regular refill catches up to now: 0.1 + 0.9 * 0.75
     helper.refill()?;
-
-    let pool_at_50 = helper.get_pool_vault_amount()?;
-    // Pool should have: original pool - redeemed + new vesting
-    // Started with 1000 (10% initial vest)
-    // Redeemed 200 (20% of pool), leaving 800
-    // New vesting from 10% to 55%: 45% of 10000 = 4500
-    // Pool = 800 + 4500 = 5300
     helper::assert_approx_eq(
-        pool_at_50,
-        dec!("5300"),
+        helper.get_pool_vault_amount()?,
+        dec!("7750"),
         helper::TOLERANCE,
-        "pool at 50% progress",
+        "pool after refill at 75%",
     );
 
-    let (mut account2, addr2) = helper.create_dummy_account()?;
-    // 8000 LP remaining, claim 2000 (25% of remaining)
-    helper.claim(dec!("2000"), addr2)?;
+    Ok(())
+}
 
-    let redeemed_at_50 = helper.redeem_lp_from_account(&mut account2, lp_resource, dec!("2000"))?;
-    let redeemed_amount_50 = redeemed_at_50.amount(&mut helper.env)?;
+#[test]
+#[should_panic(expected = "Cannot refill to a future instant.")]
+fn test_refill_to_future_instant_fails() {
+    let mut helper = Helper::new().unwrap();
 
-    // Should get 25% of pool: ~5300 * 0.25 ≈ 1325 (with pool rounding)
-    let expected_redeemed_50 = pool_at_50 * dec!("0.25");
-    helper::assert_approx_eq(
-        redeemed_amount_50,
-        expected_redeemed_50,
-        helper::TOLERANCE,
-        "redeemed at 50% progress",
-    );
+    helper.create_pool_units(dec!("10000")).unwrap();
+    helper.finish_setup().unwrap();
+    helper.advance_time_seconds(604800);
 
-    // Stage 3: Redeem at 100% linear progress (100% total vesting)
-    helper.advance_time_days(182);
-    helper.advance_time_seconds(43200); // 0.5 days = 43200 seconds
-    helper.refill()?;
+    let future = helper.env.get_current_time().add_seconds(1).unwrap();
+    helper.refill_to(future).unwrap();
+}
 
-    let pool_at_100 = helper.get_pool_vault_amount()?;
-    let locked_at_100 = helper.get_locked_vault_amount()?;
+#[test]
+fn test_refill_at_exactly_full_vesting_empties_locked_vault() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
 
-    // Pool should have: previous pool - redeemed + remaining vesting
-    // Due to pool rounding, exact amounts may vary slightly
-    // Approximately: ~3975 (after 2nd redeem) + ~4500 (remaining vest) ≈ 8475
-    helper::assert_approx_eq(
-        pool_at_100,
-        dec!("8475"),
-        helper::TOLERANCE,
-        "pool at 100% progress",
+    // An amount that does not divide evenly, so the targets accumulate rounding
+    helper.create_pool_units(dec!("3333.333333333333333333"))?;
+    helper.finish_setup()?;
+
+    helper.advance_time_seconds(604800);
+    for _ in 0..6 {
+        helper.advance_time_days(59);
+        helper.advance_time_seconds(12345);
+        helper.refill()?;
+    }
+
+    // Advance to exactly vest_end
+    let vest_end = helper.get_config()?.vest_end.unwrap();
+    helper.env.set_current_time(vest_end);
+    helper.refill()?;
+
+    assert_eq!(helper.get_locked_vault_amount()?, dec!("0"));
+    assert_eq!(
+        helper.get_pool_vault_amount()?,
+        dec!("3333.333333333333333333")
     );
 
-    // Nearly all tokens should be vested
-    // Due to OneResourcePool rounding throughout the process, a small amount may remain locked
+    Ok(())
+}
+
+#[test]
+fn test_low_divisibility_token_leaves_no_dust() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new_with(HelperOptions {
+        token_divisibility: 2,
+        rounding_mode: RoundingMode::ToZero,
+        ..Default::default()
+    })?;
+
+    helper.create_pool_units(dec!("100.03"))?;
+    helper.finish_setup()?;
+    assert_eq!(helper.get_locked_vault_amount()?, dec!("100.03"));
+
+    // Refills at odd points only ever move whole cents
+    helper.advance_time_seconds(604800);
+    for _ in 0..5 {
+        helper.advance_time_days(71);
+        helper.advance_time_seconds(4321);
+        helper.refill()?;
+
+        let pool_amount = helper.get_pool_vault_amount()?;
+        assert_eq!(
+            pool_amount,
+            pool_amount.checked_round(2, RoundingMode::ToZero).unwrap()
+        );
+    }
+
+    // Nothing is left stranded at full vesting
+    helper.advance_time_days(30);
+    helper.refill()?;
+    assert_eq!(helper.get_locked_vault_amount()?, dec!("0"));
+    assert_eq!(helper.get_pool_vault_amount()?, dec!("100.03"));
+    assert_eq!(helper.get_vested_tokens()?, dec!("100.03"));
+
+    Ok(())
+}
+
+#[test]
+fn test_indivisible_token_vests_in_whole_units() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new_with(HelperOptions {
+        token_divisibility: 0,
+        rounding_mode: RoundingMode::ToZero,
+        ..Default::default()
+    })?;
+
+    helper.create_pool_units(dec!("10"))?;
+    helper.finish_setup()?;
+
+    // 10% of 10 tokens is exactly one whole token
+    helper.advance_time_seconds(604800);
+    helper.refill()?;
+    assert_eq!(helper.get_pool_vault_amount()?, dec!("1"));
+
+    // 1 + 9 * 30 / 365 = 1.74 rounds down, the fraction is not lost
+    helper.advance_time_days(30);
+    helper.refill()?;
+    assert_eq!(helper.get_pool_vault_amount()?, dec!("1"));
+
+    // 1 + 9 * 61 / 365 = 2.5 catches up to a whole second token
+    helper.advance_time_days(31);
+    helper.refill()?;
+    assert_eq!(helper.get_pool_vault_amount()?, dec!("2"));
+
+    // Everything vests by the end, in whole tokens throughout
+    helper.advance_time_days(304);
+    helper.refill()?;
+    assert_eq!(helper.get_pool_vault_amount()?, dec!("10"));
+    assert_eq!(helper.get_locked_vault_amount()?, dec!("0"));
+
+    Ok(())
+}
+
+#[test]
+fn test_remove_locked_tokens_shrinks_schedule() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    // Drain half of the locked tokens
+    let locked_tokens = helper.remove_locked_tokens()?;
+    let rest = locked_tokens.take(dec!("5000"), &mut helper.env)?;
+    helper.put_locked_tokens(locked_tokens)?;
+    assert_eq!(helper.get_total_tokens_to_vest()?, dec!("5000"));
+
+    // Refill keeps working along the shrunk schedule: 55% of 5000
+    helper.advance_time_seconds(604800);
+    helper.advance_time_days(182);
+    helper.advance_time_seconds(43200);
+    helper.refill()?;
     helper::assert_approx_eq(
-        locked_at_100,
-        dec!("0"),
+        helper.get_pool_vault_amount()?,
+        dec!("2750"),
         helper::TOLERANCE,
-        "locked at 100% progress",
+        "pool at 50% of the shrunk schedule",
     );
 
-    let (mut account3, addr3) = helper.create_dummy_account()?;
-    // 6000 LP remaining, claim 2000 (33.33% of remaining)
-    helper.claim(dec!("2000"), addr3)?;
-
-    let redeemed_at_100 =
-        helper.redeem_lp_from_account(&mut account3, lp_resource, dec!("2000"))?;
-    let redeemed_amount_100 = redeemed_at_100.amount(&mut helper.env)?;
+    // Putting the rest back keeps it locked, nothing is released right away
+    helper.put_locked_tokens(rest)?;
+    assert_eq!(helper.get_locked_vault_amount()?, dec!("7250"));
+    helper.refill()?;
+    helper::assert_approx_eq(
+        helper.get_pool_vault_amount()?,
+        dec!("2750"),
+        helper::TOLERANCE,
+        "pool after putting the locked tokens back",
+    );
 
-    // Should get 33.33% of pool
-    let expected_redeemed_100 = pool_at_100 * dec!("2000") / dec!("6000");
+    // The tokens put back vest along the rest of the curve
+    helper.advance_to_progress(dec!("1"))?;
+    helper.refill()?;
     helper::assert_approx_eq(
-        redeemed_amount_100,
-        expected_redeemed_100,
+        helper.get_pool_vault_amount()?,
+        dec!("10000"),
         helper::TOLERANCE,
-        "redeemed at 100% progress",
+        "pool at the end of the vest",
     );
+    assert!(helper.verify_conservation()?);
 
     Ok(())
 }
 
 #[test]
-fn test_vesting_math_after_redemption() -> Result<(), RuntimeError> {
+fn test_remove_locked_tokens_mid_vest_keeps_refilling() -> Result<(), RuntimeError> {
     let mut helper = Helper::new()?;
 
     helper.create_pool_units(dec!("10000"))?;
     helper.finish_setup()?;
 
-    // Advance to vest_start and refill
-    helper.advance_time_seconds(604800);
+    // 55% vested, 4500 still locked
+    helper.advance_to_progress(dec!("0.5"))?;
     helper.refill()?;
+    assert_eq!(helper.get_pool_vault_amount()?, dec!("5500"));
 
-    let pool_before = helper.get_pool_vault_amount()?;
-    let vested_before = helper.get_locked_vault_amount()?;
+    // Drain the locked vault and put half of it back
+    let locked_tokens = helper.remove_locked_tokens()?;
+    let _ = locked_tokens.take(dec!("2250"), &mut helper.env)?;
+    helper.put_locked_tokens(locked_tokens)?;
 
-    // Should be exactly 1000 in pool, 9000 locked
-    assert_eq!(pool_before, dec!("1000"));
-    assert_eq!(vested_before, dec!("9000"));
+    // The tokens put back rejoin the schedule as its unvested part, so
+    // refills keep releasing them instead of stalling or jumping ahead
+    helper.advance_time_seconds(3600);
+    helper.refill()?;
+    assert!(helper.get_pool_vault_amount()? > dec!("5500"));
 
-    // Advance to 50%
-    helper.advance_time_days(182);
-    helper.advance_time_seconds(43200); // 0.5 days = 43200 seconds
+    // (77.5% - 55%) / 45% of the 2250 put back
+    helper.advance_to_progress(dec!("0.75"))?;
     helper.refill()?;
+    helper::assert_approx_eq(
+        helper.get_pool_vault_amount()?,
+        dec!("5500") + dec!("1125"),
+        helper::TOLERANCE,
+        "pool at 75% after draining mid-vest",
+    );
+    assert!(helper.verify_conservation()?);
 
-    let pool_at_50_no_redeem = helper.get_pool_vault_amount()?;
-    let locked_at_50_no_redeem = helper.get_locked_vault_amount()?;
+    Ok(())
+}
 
-    // Should be 5500 in pool, 4500 locked
+#[test]
+fn test_remove_then_put_locked_tokens_restores_schedule() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    // 55% vested, 4500 still locked
+    helper.advance_to_progress(dec!("0.5"))?;
+    helper.refill()?;
+
+    // Putting back what was removed undoes the removal
+    let tokens = helper.remove_locked_tokens_amount(dec!("1500"))?;
+    helper.put_locked_tokens(tokens)?;
     helper::assert_approx_eq(
-        pool_at_50_no_redeem,
+        helper.get_total_tokens_to_vest()?,
+        dec!("10000"),
+        helper::TOLERANCE,
+        "total after putting part of the locked tokens back",
+    );
+    helper::assert_approx_eq(
+        helper.get_vested_tokens()?,
         dec!("5500"),
         helper::TOLERANCE,
-        "pool at 50% without redemption",
+        "vested tokens after putting part of the locked tokens back",
     );
-    assert_eq!(pool_at_50_no_redeem + locked_at_50_no_redeem, dec!("10000"));
+
+    // The same holds when the locked vault was drained
+    let tokens = helper.remove_locked_tokens()?;
+    helper.put_locked_tokens(tokens)?;
+    helper::assert_approx_eq(
+        helper.get_total_tokens_to_vest()?,
+        dec!("10000"),
+        helper::TOLERANCE,
+        "total after putting all locked tokens back",
+    );
+    helper::assert_approx_eq(
+        helper.get_vested_tokens()?,
+        dec!("5500"),
+        helper::TOLERANCE,
+        "vested tokens after putting all locked tokens back",
+    );
+
+    // Nothing is released by the round trips
+    helper.refill()?;
+    helper::assert_approx_eq(
+        helper.get_pool_vault_amount()?,
+        dec!("5500"),
+        helper::TOLERANCE,
+        "pool after putting the locked tokens back",
+    );
+    assert!(helper.verify_conservation()?);
 
     Ok(())
 }
 
 #[test]
-fn test_redeem_75_percent_quadruples_maturity() -> Result<(), RuntimeError> {
+fn test_add_vesting_funds_after_vesting_started() -> Result<(), RuntimeError> {
     let mut helper = Helper::new()?;
 
     helper.create_pool_units(dec!("10000"))?;
     helper.finish_setup()?;
 
-    // Advance to vest_start
-    helper.advance_time_seconds(604800);
+    // 55% vested
+    helper.advance_to_progress(dec!("0.5"))?;
     helper.refill()?;
 
-    let lp_resource = helper.get_lp_resource_address();
+    // New funds are added to the total, unlike locked tokens put back
+    helper.add_vesting_funds(dec!("10000"))?;
+    assert_eq!(helper.get_total_tokens_to_vest()?, dec!("20000"));
 
-    // Claim and redeem 75% of LP tokens (7500)
-    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    // The next refill catches the new funds up with the schedule: 55% of 20000
+    helper.refill()?;
+    helper::assert_approx_eq(
+        helper.get_pool_vault_amount()?,
+        dec!("11000"),
+        helper::TOLERANCE,
+        "pool after refill",
+    );
+    helper::assert_approx_eq(
+        helper.get_maturity_value()?,
+        dec!("2"),
+        helper::TOLERANCE,
+        "maturity value after adding vesting funds",
+    );
+    assert!(helper.verify_conservation()?);
+
+    Ok(())
+}
+
+#[test]
+fn test_add_vesting_funds_before_finish_setup_fails() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+
+    let result = helper.add_vesting_funds(dec!("1000"));
+    helper::assert_vester_error(result, VesterError::SetupNotFinished);
+
+    Ok(())
+}
+
+#[test]
+fn test_locked_vault_changes_deposit_and_withdrawal() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    // Each change emits a LockedVaultChangedEvent with the delta and new balance
+    // checked here
+    let locked_tokens = helper.remove_locked_tokens()?;
+    assert_eq!(locked_tokens.amount(&mut helper.env)?, dec!("10000"));
+    assert_eq!(helper.get_locked_vault_amount()?, dec!("0"));
+
+    helper.put_locked_tokens(locked_tokens)?;
+    assert_eq!(helper.get_locked_vault_amount()?, dec!("10000"));
+
+    helper.add_tranche(dec!("2500"))?;
+    assert_eq!(helper.get_locked_vault_amount()?, dec!("12500"));
+    assert_eq!(helper.get_total_tokens_to_vest()?, dec!("12500"));
+
+    Ok(())
+}
+
+#[test]
+fn test_remove_locked_tokens_amount() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let tokens = helper.remove_locked_tokens_amount(dec!("3000"))?;
+    assert_eq!(tokens.amount(&mut helper.env)?, dec!("3000"));
+    assert_eq!(helper.get_locked_vault_amount()?, dec!("7000"));
+    assert_eq!(helper.get_total_tokens_to_vest()?, dec!("7000"));
+
+    // The rest keeps vesting along the shrunk schedule: 55% of 7000
+    helper.advance_to_progress(dec!("0.5"))?;
+    helper.refill()?;
+    helper::assert_approx_eq(
+        helper.get_pool_vault_amount()?,
+        dec!("3850"),
+        helper::TOLERANCE,
+        "pool at 50% of the shrunk schedule",
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_remove_locked_tokens_amount_mid_vest() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    // 55% vested, 4500 still locked
+    helper.advance_to_progress(dec!("0.5"))?;
+    helper.refill()?;
+    assert_eq!(helper.get_pool_vault_amount()?, dec!("5500"));
+
+    // Removing half of the locked tokens halves the schedule
+    let tokens = helper.remove_locked_tokens_amount(dec!("2250"))?;
+    assert_eq!(tokens.amount(&mut helper.env)?, dec!("2250"));
+    assert_eq!(helper.get_total_tokens_to_vest()?, dec!("5000"));
+    assert_eq!(helper.get_vested_tokens()?, dec!("2750"));
+
+    // The refill right after still releases tokens
+    helper.advance_time_seconds(3600);
+    helper.refill()?;
+    assert!(helper.get_pool_vault_amount()? > dec!("5500"));
+
+    // The remaining 2250 keep vesting along the original curve
+    helper.advance_to_progress(dec!("0.75"))?;
+    helper.refill()?;
+    helper::assert_approx_eq(
+        helper.get_pool_vault_amount()?,
+        dec!("6625"),
+        helper::TOLERANCE,
+        "pool at 75% after a partial removal",
+    );
+    assert!(helper.verify_conservation()?);
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "Amount exceeds the locked tokens.")]
+fn test_remove_locked_tokens_amount_exceeding_vault_fails() {
+    let mut helper = Helper::new().unwrap();
+
+    helper.create_pool_units(dec!("10000")).unwrap();
+    helper.finish_setup().unwrap();
+
+    // This should panic
+    let _ = helper.remove_locked_tokens_amount(dec!("10001")).unwrap();
+}
+
+#[test]
+fn test_remove_lp_amount() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let lp_tokens = helper.remove_lp_amount(dec!("2500"))?;
+    assert_eq!(
+        lp_tokens.resource_address(&mut helper.env)?,
+        helper.get_lp_resource_address()
+    );
+    assert_eq!(lp_tokens.amount(&mut helper.env)?, dec!("2500"));
+    assert_eq!(helper.get_lp_token_amount()?, dec!("7500"));
+
+    // The rest can still be claimed
+    let (_, account) = helper.create_dummy_account()?;
     helper.claim(dec!("7500"), account)?;
 
-    let _ = helper.redeem_lp_from_account(&mut dummy_account, lp_resource, dec!("7500"))?;
+    Ok(())
+}
 
-    // After redemption:
-    // - Pool had ~1000 tokens (10% initial vest)
-    // - Redeemer got ~750 tokens (75% of pool)
-    // - Pool now has ~250 tokens
-    // - Locked still has ~9000 tokens
-    // - LP tokens remaining: 2500
-    // - Total tokens: 250 + 9000 = 9250
-    // - Maturity = 9250 / 2500 = 3.7
-    let maturity_after = helper.get_maturity_value()?;
-    let pool_after = helper.get_pool_vault_amount()?;
-    let locked_after = helper.get_locked_vault_amount()?;
+#[test]
+#[should_panic(expected = "Amount exceeds the LP tokens in the vault.")]
+fn test_remove_lp_amount_exceeding_vault_fails() {
+    let mut helper = Helper::new().unwrap();
 
-    let expected_maturity = (pool_after + locked_after) / dec!("2500");
+    helper.create_pool_units(dec!("10000")).unwrap();
+
+    // This should panic
+    let _ = helper.remove_lp_amount(dec!("10001")).unwrap();
+}
+
+#[test]
+fn test_refill_after_removing_locked_tokens_mid_vest() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    helper.advance_time_seconds(604800);
+    helper.advance_time_days(182);
+    helper.advance_time_seconds(43200);
+    helper.refill()?;
 
+    let removed = helper.remove_locked_tokens()?;
     helper::assert_approx_eq(
-        maturity_after,
-        expected_maturity,
+        removed.amount(&mut helper.env)?,
+        dec!("4500"),
         helper::TOLERANCE,
-        "maturity after 75% redemption",
+        "removed locked tokens",
+    );
+    // Nothing is left to vest, so the schedule shrinks to nothing
+    assert_eq!(helper.get_total_tokens_to_vest()?, dec!("0"));
+    assert_eq!(helper.get_vested_tokens()?, dec!("0"));
+
+    // Refills move nothing and the pool keeps what had vested
+    helper.advance_time_days(100);
+    helper.refill()?;
+    helper.advance_time_days(100);
+    helper.refill()?;
+    helper::assert_approx_eq(
+        helper.get_pool_vault_amount()?,
+        dec!("5500"),
+        helper::TOLERANCE,
+        "pool after removing the locked tokens",
+    );
+    assert_eq!(helper.get_locked_vault_amount()?, dec!("0"));
+    assert!(helper.verify_conservation()?);
+
+    Ok(())
+}
+
+#[test]
+fn test_get_vested_fraction() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    // Before setup nothing is vested
+    assert_eq!(helper.get_vested_fraction()?, dec!("0"));
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    // Still zero during the pre-claim period
+    assert_eq!(helper.get_vested_fraction()?, dec!("0"));
+
+    // At vest_start only the initial fraction is vested
+    helper.advance_time_seconds(604800);
+    assert_eq!(helper.get_vested_fraction()?, dec!("0.1"));
+
+    // Halfway through: 0.1 + 0.9 * 0.5, without any refill having run
+    helper.advance_time_days(182);
+    helper.advance_time_seconds(43200);
+    helper::assert_approx_eq(
+        helper.get_vested_fraction()?,
+        dec!("0.55"),
+        helper::TOLERANCE,
+        "vested fraction at 50% progress",
+    );
+    assert_eq!(helper.get_vested_tokens()?, dec!("0"));
+
+    // Clamped to one after the vest ends
+    helper.advance_time_days(400);
+    assert_eq!(helper.get_vested_fraction()?, dec!("1"));
+
+    Ok(())
+}
+
+#[test]
+fn test_projected_vested_at() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    let now = helper.env.get_current_time();
+    assert_eq!(helper.projected_vested_at(now)?, dec!("0"));
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let config = helper.get_config()?;
+    let vest_start = config.vest_start.unwrap();
+    let vest_end = config.vest_end.unwrap();
+
+    // Evaluated without advancing the clock or refilling
+    assert_eq!(helper.projected_vested_at(now)?, dec!("0"));
+    assert_eq!(helper.projected_vested_at(vest_start)?, dec!("1000"));
+    helper::assert_approx_eq(
+        helper.projected_vested_at(
+            vest_start
+                .add_days(182)
+                .unwrap()
+                .add_seconds(43200)
+                .unwrap(),
+        )?,
+        dec!("5500"),
+        helper::TOLERANCE,
+        "projected at 50% progress",
+    );
+    assert_eq!(helper.projected_vested_at(vest_end)?, dec!("10000"));
+    assert_eq!(
+        helper.projected_vested_at(vest_end.add_days(100).unwrap())?,
+        dec!("10000")
+    );
+    assert_eq!(helper.get_pool_vault_amount()?, dec!("0"));
+
+    Ok(())
+}
+
+#[test]
+fn test_get_elapsed_seconds() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    assert_eq!(helper.get_elapsed_seconds()?, 0);
+
+    // Zero during the pre-claim period
+    helper.finish_setup()?;
+    helper.advance_time_days(3);
+    assert_eq!(helper.get_elapsed_seconds()?, 0);
+
+    let vest_start = helper.get_config()?.vest_start.unwrap();
+
+    helper.advance_time_days(10);
+    let now = helper.env.get_current_time();
+    assert_eq!(
+        helper.get_elapsed_seconds()?,
+        now.seconds_since_unix_epoch - vest_start.seconds_since_unix_epoch
+    );
+
+    helper.advance_time_seconds(12345);
+    let now = helper.env.get_current_time();
+    assert_eq!(
+        helper.get_elapsed_seconds()?,
+        now.seconds_since_unix_epoch - vest_start.seconds_since_unix_epoch
+    );
+
+    // Clamped to the vest duration once the vest has ended
+    helper.advance_time_days(400);
+    assert_eq!(helper.get_elapsed_seconds()?, 365 * 86400);
+
+    Ok(())
+}
+
+#[test]
+fn test_get_vesting_seconds_remaining() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    // Before setup the full pre-claim period and vest are still ahead
+    assert_eq!(
+        helper.get_vesting_seconds_remaining()?,
+        604800 + 365 * 86400
+    );
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+    assert_eq!(
+        helper.get_vesting_seconds_remaining()?,
+        604800 + 365 * 86400
+    );
+
+    // Halfway through the vest
+    helper.advance_time_seconds(604800);
+    helper.advance_time_days(182);
+    helper.advance_time_seconds(43200);
+    assert_eq!(helper.get_vesting_seconds_remaining()?, 182 * 86400 + 43200);
+
+    // Zero once the vest has ended
+    helper.advance_time_days(183);
+    assert_eq!(helper.get_vesting_seconds_remaining()?, 0);
+
+    helper.advance_time_days(10);
+    assert_eq!(helper.get_vesting_seconds_remaining()?, 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_auto_refill_on_read() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+    assert!(!helper.is_auto_refill_on_read()?);
+
+    // Getters read the stored values by default
+    helper.advance_to_progress(dec!("0.5"))?;
+    assert_eq!(helper.get_pool_vault_amount()?, dec!("0"));
+
+    // Once enabled, they refill first
+    helper.set_auto_refill_on_read(true)?;
+    assert!(helper.is_auto_refill_on_read()?);
+    assert_eq!(helper.get_pool_vault_amount()?, dec!("5500"));
+    assert_eq!(helper.get_locked_vault_amount()?, dec!("4500"));
+    assert_eq!(helper.get_vested_tokens()?, dec!("5500"));
+
+    // Newly vested tokens show up without an explicit refill
+    helper.advance_to_progress(dec!("0.6"))?;
+    assert_eq!(helper.get_pool_vault_amount()?, dec!("6400"));
+
+    Ok(())
+}
+
+#[test]
+fn test_auto_refill_on_read_before_vesting_starts() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.set_auto_refill_on_read(true)?;
+
+    // Nothing to refill yet, so the getters must not fail
+    assert_eq!(helper.get_pool_vault_amount()?, dec!("10000"));
+    helper.finish_setup()?;
+    assert_eq!(helper.get_pool_vault_amount()?, dec!("0"));
+
+    Ok(())
+}
+
+#[test]
+fn test_get_realized_rate() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    assert_eq!(helper.get_realized_rate()?, dec!("0"));
+
+    // Zero during the pre-claim period
+    helper.finish_setup()?;
+    assert_eq!(helper.get_realized_rate()?, dec!("0"));
+
+    // Halfway through: 1000 initial + 4500 linear over 182.5 days
+    helper.advance_time_seconds(604800);
+    helper.advance_time_days(182);
+    helper.advance_time_seconds(43200);
+    assert_eq!(helper.get_realized_rate()?, dec!("5500") / dec!("15768000"));
+
+    // The getter refilled the pool
+    assert_eq!(helper.get_vested_tokens()?, dec!("5500"));
+
+    Ok(())
+}
+
+#[test]
+fn test_get_daily_emission() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    assert_eq!(helper.get_daily_emission()?, dec!("0"));
+
+    helper.finish_setup()?;
+
+    // 10000 * (1 - 0.1) / 365
+    assert_eq!(helper.get_daily_emission()?, dec!("9000") / dec!("365"));
+
+    // The daily emission matches the linear release between two days, up to
+    // the rounding of the vested fraction scaled by the total
+    let vest_start = helper.get_config()?.vest_start.unwrap();
+    let day_10 = helper.projected_vested_at(vest_start.add_days(10).unwrap())?;
+    let day_11 = helper.projected_vested_at(vest_start.add_days(11).unwrap())?;
+    helper::assert_approx_eq(
+        day_11 - day_10,
+        helper.get_daily_emission()?,
+        dec!("0.00000000001"),
+        "release between two days",
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_get_daily_emission_with_other_config() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new_with_config(100, dec!("0.25"), 3600, 10)?;
+
+    helper.create_pool_units(dec!("8000"))?;
+    helper.finish_setup()?;
+
+    // 8000 * (1 - 0.25) / 100, the cliff doesn't change the rate
+    assert_eq!(helper.get_daily_emission()?, dec!("60"));
+
+    Ok(())
+}
+
+#[test]
+fn test_get_pre_claim_seconds_remaining() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    // Before setup the full pre-claim duration is still ahead
+    assert_eq!(helper.get_pre_claim_seconds_remaining()?, 604800);
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+    assert_eq!(helper.get_pre_claim_seconds_remaining()?, 604800);
+
+    helper.advance_time_seconds(86400);
+    assert_eq!(helper.get_pre_claim_seconds_remaining()?, 518400);
+
+    // Zero once vesting has begun
+    helper.advance_time_seconds(518400);
+    assert_eq!(helper.get_pre_claim_seconds_remaining()?, 0);
+
+    helper.advance_time_days(10);
+    assert_eq!(helper.get_pre_claim_seconds_remaining()?, 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_is_fully_vested() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    assert!(!helper.is_fully_vested()?);
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    // Pre-claim period
+    assert!(!helper.is_fully_vested()?);
+
+    // One second before vest_end
+    helper.advance_time_seconds(604800);
+    helper.advance_time_days(364);
+    helper.advance_time_seconds(86399);
+    assert!(!helper.is_fully_vested()?);
+
+    // Exactly at vest_end, without any refill
+    helper.advance_time_seconds(1);
+    assert!(helper.is_fully_vested()?);
+    assert_eq!(helper.get_locked_vault_amount()?, dec!("10000"));
+
+    Ok(())
+}
+
+#[test]
+fn test_get_state_snapshot() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+
+    // During setup everything is still in the pool
+    let snapshot = helper.get_state_snapshot()?;
+    assert_eq!(snapshot.pool_amount, dec!("10000"));
+    assert_eq!(snapshot.locked_amount, dec!("0"));
+    assert_eq!(snapshot.unclaimed_lp, dec!("10000"));
+    assert_eq!(snapshot.vested_tokens, dec!("0"));
+    assert_eq!(snapshot.total_tokens_to_vest, dec!("10000"));
+
+    helper.finish_setup()?;
+    let (_dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("3000"), account)?;
+
+    // Halfway through the vest, without any prior refill
+    helper.advance_time_seconds(604800);
+    helper.advance_time_days(182);
+    helper.advance_time_seconds(43200);
+
+    let snapshot = helper.get_state_snapshot()?;
+    helper::assert_approx_eq(
+        snapshot.pool_amount,
+        dec!("5500"),
+        helper::TOLERANCE,
+        "pool amount",
+    );
+    helper::assert_approx_eq(
+        snapshot.locked_amount,
+        dec!("4500"),
+        helper::TOLERANCE,
+        "locked amount",
+    );
+    assert_eq!(snapshot.unclaimed_lp, dec!("7000"));
+    assert_eq!(snapshot.vested_tokens, snapshot.pool_amount);
+    assert_eq!(snapshot.total_tokens_to_vest, dec!("10000"));
+
+    // Consistent with the individual getters at the same instant
+    assert_eq!(snapshot.pool_amount, helper.get_pool_vault_amount()?);
+    assert_eq!(snapshot.locked_amount, helper.get_locked_vault_amount()?);
+    assert_eq!(snapshot.unclaimed_lp, helper.get_lp_token_amount()?);
+
+    Ok(())
+}
+
+#[test]
+fn test_get_position() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    // Nothing is redeemable during the pre-claim period
+    let position = helper.get_position(dec!("2000"))?;
+    assert_eq!(position.redeemable_now, dec!("0"));
+    assert_eq!(position.value_at_maturity, dec!("2000"));
+    assert_eq!(position.forfeit_if_redeem_now, dec!("2000"));
+    assert_eq!(position.vested_fraction, dec!("0"));
+
+    // Advance to 50% linear progress (55% total vesting)
+    helper.advance_time_seconds(604800);
+    helper.advance_time_days(182);
+    helper.advance_time_seconds(43200);
+
+    // 20% of the LP supply: 20% of 5500 vested now, 20% of 10000 at maturity
+    let position = helper.get_position(dec!("2000"))?;
+    helper::assert_approx_eq(
+        position.redeemable_now,
+        dec!("1100"),
+        helper::TOLERANCE,
+        "redeemable now",
+    );
+    assert_eq!(
+        position.value_at_maturity,
+        helper.get_maturity_value()? * dec!("2000")
+    );
+    helper::assert_approx_eq(
+        position.value_at_maturity / dec!("2000"),
+        dec!("1"),
+        helper::TOLERANCE,
+        "value at maturity",
+    );
+    helper::assert_approx_eq(
+        position.forfeit_if_redeem_now / dec!("2000"),
+        dec!("0.45"),
+        helper::TOLERANCE,
+        "forfeit",
+    );
+    assert_eq!(
+        position.forfeit_if_redeem_now,
+        position.value_at_maturity - position.redeemable_now
+    );
+    helper::assert_approx_eq(
+        position.vested_fraction,
+        dec!("0.55"),
+        helper::TOLERANCE,
+        "vested fraction",
+    );
+    assert_eq!(
+        position.redeemable_now,
+        helper.preview_redeem(dec!("2000"))?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_get_pool_and_locker_address() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+    helper.advance_time_days(8);
+    helper.refill()?;
+
+    // The pool can be read directly
+    let pool = helper.get_pool_address()?;
+    let pool_amount = helper.env.call_method_typed::<_, _, Decimal>(
+        pool.0,
+        ONE_RESOURCE_POOL_GET_VAULT_AMOUNT_IDENT,
+        &OneResourcePoolGetVaultAmountInput {},
+    )?;
+    assert_eq!(pool_amount, helper.get_pool_vault_amount()?);
+
+    // The locker holds claims for accounts that reject deposits
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.reject_deposits(&mut dummy_account)?;
+    helper.claim(dec!("1000"), account)?;
+
+    let locker = helper.get_locker_address()?;
+    let lp_resource = helper.get_lp_resource_address();
+    let locked_lp = helper.env.call_method_typed::<_, _, Decimal>(
+        locker.0,
+        ACCOUNT_LOCKER_GET_AMOUNT_IDENT,
+        &(account, lp_resource),
+    )?;
+    assert_eq!(locked_lp, dec!("1000"));
+
+    Ok(())
+}
+
+#[test]
+fn test_claim_delivers_receipt_nft() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+    let receipt_resource = helper.get_receipt_resource_address()?;
+
+    let (dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("1000"), account)?;
+    helper.claim(dec!("500"), account)?;
+
+    // One receipt per claim, next to the LP tokens
+    assert_eq!(
+        helper.get_account_balance(&dummy_account, lp_resource)?,
+        dec!("1500")
+    );
+    assert_eq!(
+        helper.get_account_balance(&dummy_account, receipt_resource)?,
+        dec!("2")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_claim_records_receipt_data() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    let early = ReceiptData {
+        cohort: "early".to_string(),
+        multiplier: dec!("1.5"),
+    };
+    helper.claim_with_receipt_data(dec!("1000"), account, early.clone())?;
+
+    let receipt_ids = helper.get_receipt_ids(&mut dummy_account)?;
+    assert_eq!(receipt_ids.len(), 1);
+    assert_eq!(
+        helper.get_receipt_data(receipt_ids[0].clone())?,
+        Some(early)
+    );
+
+    // New data for the same account updates its earlier receipt too
+    let late = ReceiptData {
+        cohort: "late".to_string(),
+        multiplier: dec!("1.1"),
+    };
+    helper.claim_with_receipt_data(dec!("500"), account, late.clone())?;
+    // Without data, the new receipt carries over the current one
+    helper.claim(dec!("200"), account)?;
+
+    let later_receipt_ids = helper.get_receipt_ids(&mut dummy_account)?;
+    assert_eq!(later_receipt_ids.len(), 2);
+    for receipt_id in receipt_ids.into_iter().chain(later_receipt_ids) {
+        assert_eq!(helper.get_receipt_data(receipt_id)?, Some(late.clone()));
+    }
+
+    // Accounts claimed for without data have none on their receipts
+    let (mut other_dummy_account, other_account) = helper.create_dummy_account()?;
+    helper.claim(dec!("100"), other_account)?;
+    let other_receipt_ids = helper.get_receipt_ids(&mut other_dummy_account)?;
+    assert_eq!(helper.get_receipt_data(other_receipt_ids[0].clone())?, None);
+
+    Ok(())
+}
+
+#[test]
+fn test_claim_returns_claimed_amount() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let (_, account) = helper.create_dummy_account()?;
+    assert_eq!(helper.claim(dec!("1000"), account)?, dec!("1000"));
+
+    Ok(())
+}
+
+#[test]
+fn test_claim_up_to_partially_depleted_vault() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+    let (_, account_1) = helper.create_dummy_account()?;
+    let (dummy_account_2, account_2) = helper.create_dummy_account()?;
+    let (_, account_3) = helper.create_dummy_account()?;
+
+    assert_eq!(helper.claim_up_to(dec!("7000"), account_1)?, dec!("7000"));
+
+    // Only 3000 left in the vault
+    assert_eq!(helper.claim_up_to(dec!("5000"), account_2)?, dec!("3000"));
+    assert_eq!(
+        helper.get_account_balance(&dummy_account_2, lp_resource)?,
+        dec!("3000")
+    );
+    assert_eq!(helper.get_claimed_amount(account_2)?, dec!("3000"));
+
+    // Nothing left at all
+    assert_eq!(helper.claim_up_to(dec!("1000"), account_3)?, dec!("0"));
+    assert_eq!(helper.get_claimed_amount(account_3)?, dec!("0"));
+    assert_eq!(helper.get_claimants(0, 10)?, vec![account_1, account_2]);
+
+    Ok(())
+}
+
+#[test]
+fn test_has_claimed_at_least() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let (_, account) = helper.create_dummy_account()?;
+    assert!(helper.has_claimed_at_least(account, dec!("0"))?);
+    assert!(!helper.has_claimed_at_least(account, dec!("1"))?);
+
+    helper.claim(dec!("1000"), account)?;
+    helper.claim(dec!("500"), account)?;
+
+    assert!(helper.has_claimed_at_least(account, dec!("1000"))?);
+    assert!(helper.has_claimed_at_least(account, dec!("1500"))?);
+    assert!(!helper.has_claimed_at_least(account, dec!("1500.000000000000000001"))?);
+    assert!(!helper.has_claimed_at_least(account, dec!("2000"))?);
+
+    Ok(())
+}
+
+#[test]
+fn test_claim_bucket() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+    let receipt_resource = helper.get_receipt_resource_address()?;
+
+    // Withdraw all LP tokens and split them off-component
+    let lp_tokens = helper.remove_lp()?;
+    assert_eq!(helper.get_lp_token_amount()?, dec!("0"));
+    let split = lp_tokens.take(dec!("1500"), &mut helper.env)?;
+
+    let (dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim_bucket(split, account)?;
+
+    assert_eq!(
+        helper.get_account_balance(&dummy_account, lp_resource)?,
+        dec!("1500")
+    );
+    assert_eq!(
+        helper.get_account_balance(&dummy_account, receipt_resource)?,
+        dec!("1")
+    );
+    assert_eq!(helper.get_claimed_amount(account)?, dec!("1500"));
+    assert_eq!(lp_tokens.amount(&mut helper.env)?, dec!("8500"));
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "Bucket does not contain LP tokens of this component")]
+fn test_claim_bucket_with_wrong_resource_fails() {
+    let mut helper = Helper::new().unwrap();
+
+    helper.create_pool_units(dec!("10000")).unwrap();
+    helper.finish_setup().unwrap();
+
+    let tokens = helper
+        .token_to_vest
+        .take(dec!("100"), &mut helper.env)
+        .unwrap();
+    let (_, account) = helper.create_dummy_account().unwrap();
+
+    // This should panic
+    helper.claim_bucket(tokens, account).unwrap();
+}
+
+#[test]
+fn test_fund_and_reserve_delivers_at_finish_setup() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+    let (account1, addr1) = helper.create_dummy_account()?;
+    let (account2, addr2) = helper.create_dummy_account()?;
+
+    helper.create_pool_units(dec!("5000"))?;
+    helper.fund_and_reserve(dec!("1000"), addr1)?;
+    helper.fund_and_reserve(dec!("3000"), addr2)?;
+
+    assert_eq!(
+        helper.get_pending_claims()?,
+        vec![(addr1, dec!("1000")), (addr2, dec!("3000"))]
+    );
+    assert_eq!(helper.get_total_tokens_to_vest()?, dec!("9000"));
+    // Nothing is delivered before setup is finished
+    assert_eq!(
+        helper.get_account_balance(&account1, lp_resource)?,
+        dec!("0")
+    );
+    assert_eq!(helper.get_lp_token_amount()?, dec!("5000"));
+
+    helper.finish_setup()?;
+
+    assert_eq!(
+        helper.get_account_balance(&account1, lp_resource)?,
+        dec!("1000")
+    );
+    assert_eq!(
+        helper.get_account_balance(&account2, lp_resource)?,
+        dec!("3000")
+    );
+    assert_eq!(helper.get_claimed_amount(addr1)?, dec!("1000"));
+    assert_eq!(helper.get_claimed_amount(addr2)?, dec!("3000"));
+    assert_eq!(helper.get_pending_claims()?, vec![]);
+    assert_eq!(helper.get_lp_token_amount()?, dec!("5000"));
+    assert_eq!(helper.get_locked_vault_amount()?, dec!("9000"));
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "Vesting has already started")]
+fn test_fund_and_reserve_after_setup_fails() {
+    let mut helper = Helper::new().unwrap();
+
+    helper.create_pool_units(dec!("10000")).unwrap();
+    helper.finish_setup().unwrap();
+
+    let (_, addr1) = helper.create_dummy_account().unwrap();
+
+    // This should panic
+    helper.fund_and_reserve(dec!("1000"), addr1).unwrap();
+}
+
+#[test]
+fn test_claim_batch() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+
+    let (account1, addr1) = helper.create_dummy_account()?;
+    let (account2, addr2) = helper.create_dummy_account()?;
+    let (account3, addr3) = helper.create_dummy_account()?;
+
+    helper.claim_batch(vec![
+        (dec!("1000"), addr1),
+        (dec!("2000"), addr2),
+        (dec!("3000"), addr3),
+    ])?;
+
+    assert_eq!(
+        helper.get_account_balance(&account1, lp_resource)?,
+        dec!("1000")
+    );
+    assert_eq!(
+        helper.get_account_balance(&account2, lp_resource)?,
+        dec!("2000")
+    );
+    assert_eq!(
+        helper.get_account_balance(&account3, lp_resource)?,
+        dec!("3000")
+    );
+    assert_eq!(helper.get_lp_token_amount()?, dec!("4000"));
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "Not enough LP tokens in the vault for this batch")]
+fn test_claim_batch_exceeding_vault_fails() {
+    let mut helper = Helper::new().unwrap();
+
+    helper.create_pool_units(dec!("10000")).unwrap();
+    helper.finish_setup().unwrap();
+
+    let (_, addr1) = helper.create_dummy_account().unwrap();
+    let (_, addr2) = helper.create_dummy_account().unwrap();
+
+    // This should panic
+    helper
+        .claim_batch(vec![(dec!("6000"), addr1), (dec!("6000"), addr2)])
+        .unwrap();
+}
+
+#[test]
+fn test_claim_weighted_delivers_exact_total() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+
+    let (account1, addr1) = helper.create_dummy_account()?;
+    let (account2, addr2) = helper.create_dummy_account()?;
+    let (account3, addr3) = helper.create_dummy_account()?;
+
+    // 1000 split 1:2:4 doesn't divide evenly
+    helper.claim_weighted(
+        dec!("1000"),
+        vec![(addr1, dec!("1")), (addr2, dec!("2")), (addr3, dec!("4"))],
+    )?;
+
+    let balance1 = helper.get_account_balance(&account1, lp_resource)?;
+    let balance2 = helper.get_account_balance(&account2, lp_resource)?;
+    let balance3 = helper.get_account_balance(&account3, lp_resource)?;
+    assert_eq!(balance1, dec!("142.857142857142857142"));
+    assert_eq!(balance2, dec!("285.714285714285714285"));
+    // The last recipient gets the rounding dust
+    assert_eq!(balance3, dec!("571.428571428571428573"));
+
+    assert_eq!(balance1 + balance2 + balance3, dec!("1000"));
+    assert_eq!(helper.get_lp_token_amount()?, dec!("9000"));
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "Weight must be greater than zero")]
+fn test_claim_weighted_zero_weight_fails() {
+    let mut helper = Helper::new().unwrap();
+
+    helper.create_pool_units(dec!("10000")).unwrap();
+    helper.finish_setup().unwrap();
+
+    let (_, addr1) = helper.create_dummy_account().unwrap();
+    let (_, addr2) = helper.create_dummy_account().unwrap();
+
+    // This should panic
+    helper
+        .claim_weighted(dec!("1000"), vec![(addr1, dec!("1")), (addr2, dec!("0"))])
+        .unwrap();
+}
+
+#[test]
+fn test_migrate_single_user() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("1000"), account)?;
+
+    let mut new_vester = helper.instantiate_funded_vester(dec!("10000"))?;
+    let new_lp_resource = new_vester.get_pool_unit_resource_address(&mut helper.env)?;
+    helper.setup_migration_to(&mut new_vester)?;
+
+    // Migrating is possible from vest_start on, where 10% has vested
+    helper.advance_time_seconds(604800);
+    let new_lp_tokens = helper.migrate_lp_from_account(&mut dummy_account, dec!("400"))?;
+
+    // Both vesters follow the same schedule, so the LP tokens swap 1:1
+    assert_eq!(
+        new_lp_tokens.resource_address(&mut helper.env)?,
+        new_lp_resource
+    );
+    assert_eq!(new_lp_tokens.amount(&mut helper.env)?, dec!("400"));
+    assert_eq!(
+        helper.get_account_balance(&dummy_account, helper.lp_resource_address)?,
+        dec!("600")
+    );
+
+    // The old LP tokens are burned and their locked tokens moved over,
+    // without touching the new vester's unclaimed LP tokens
+    assert_eq!(helper.get_lp_total_supply()?, dec!("9600"));
+    assert_eq!(helper.get_locked_vault_amount()?, dec!("8640"));
+    assert_eq!(
+        new_vester.get_lp_token_amount(&mut helper.env)?,
+        dec!("10000")
+    );
+    assert_eq!(
+        new_vester.get_locked_vault_amount(&mut helper.env)?,
+        dec!("9360")
+    );
+    assert!(helper.verify_conservation()?);
+    assert!(new_vester.verify_conservation(&mut helper.env)?);
+
+    Ok(())
+}
+
+#[test]
+fn test_migrate_mid_vest_keeps_the_value() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("1000"), account)?;
+
+    let mut new_vester = helper.instantiate_funded_vester(dec!("10000"))?;
+    helper.setup_migration_to(&mut new_vester)?;
+
+    // 55% vested in both vesters
+    helper.advance_to_progress(dec!("0.5"))?;
+    let new_lp_tokens = helper.migrate_lp_from_account(&mut dummy_account, dec!("400"))?;
+    assert_eq!(new_lp_tokens.amount(&mut helper.env)?, dec!("400"));
+
+    // The 400 tokens behind the LP tokens leave the old vester from the
+    // pool and the locked vault alike...
+    assert_eq!(helper.get_pool_vault_amount()?, dec!("5280"));
+    assert_eq!(helper.get_locked_vault_amount()?, dec!("4320"));
+
+    // ...and arrive in the new one in the same ratio
+    assert_eq!(
+        new_vester.get_pool_vault_amount(&mut helper.env)?,
+        dec!("5720")
+    );
+    assert_eq!(
+        new_vester.get_locked_vault_amount(&mut helper.env)?,
+        dec!("4680")
+    );
+
+    // Nothing is stranded: both vest out in full
+    helper.advance_to_fully_vested()?;
+    helper.refill()?;
+    new_vester.refill(&mut helper.env)?;
+    assert_eq!(helper.get_pool_vault_amount()?, dec!("9600"));
+    assert_eq!(
+        new_vester.get_pool_vault_amount(&mut helper.env)?,
+        dec!("10400")
+    );
+    assert!(helper.verify_conservation()?);
+    assert!(new_vester.verify_conservation(&mut helper.env)?);
+
+    Ok(())
+}
+
+#[test]
+fn test_migrate_during_pre_claim_fails() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("1000"), account)?;
+
+    let mut new_vester = helper.instantiate_funded_vester(dec!("10000"))?;
+    helper.setup_migration_to(&mut new_vester)?;
+
+    helper::assert_vester_error(
+        helper.migrate_lp_from_account(&mut dummy_account, dec!("400")),
+        VesterError::StillInPreClaim,
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_migrate_while_paused_fails() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("1000"), account)?;
+
+    let mut new_vester = helper.instantiate_funded_vester(dec!("10000"))?;
+    helper.setup_migration_to(&mut new_vester)?;
+
+    helper.advance_time_seconds(604800);
+    helper.pause()?;
+    helper::assert_vester_error(
+        helper.migrate_lp_from_account(&mut dummy_account, dec!("400")),
+        VesterError::RedemptionsPaused,
+    );
+
+    // Nothing left the old vester
+    assert_eq!(helper.get_lp_total_supply()?, dec!("10000"));
+    assert_eq!(
+        new_vester.get_lp_total_supply(&mut helper.env)?,
+        dec!("10000")
+    );
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "No migration authorized")]
+fn test_migrate_without_authorization_fails() {
+    let mut helper = Helper::new().unwrap();
+
+    helper.create_pool_units(dec!("10000")).unwrap();
+    helper.finish_setup().unwrap();
+
+    let (mut dummy_account, account) = helper.create_dummy_account().unwrap();
+    helper.claim(dec!("1000"), account).unwrap();
+
+    // This should panic
+    let _ = helper
+        .migrate_lp_from_account(&mut dummy_account, dec!("400"))
+        .unwrap();
+}
+
+#[test]
+fn test_get_redemption_value_current_matches_redeem() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("1000"), account)?;
+    helper.advance_time_days(7 + 73);
+
+    // Nobody has refilled yet, so the pool alone does not reflect the vest
+    assert_eq!(helper.get_pool_redemption_value(dec!("1000"))?, dec!("0"));
+
+    // 10% + 90% * 73 / 365 = 28% vested
+    let quoted = helper.get_redemption_value_current(dec!("1000"))?;
+    assert_eq!(quoted, dec!("280"));
+
+    let redeemed_tokens =
+        helper.redeem_lp_from_account(&mut dummy_account, lp_resource, dec!("1000"))?;
+    assert_eq!(redeemed_tokens.amount(&mut helper.env)?, quoted);
+
+    Ok(())
+}
+
+#[test]
+fn test_get_total_redeemable_now_after_early_redemption() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("2000"), account)?;
+
+    // Nothing can be redeemed during the pre-claim period
+    assert_eq!(helper.get_total_redeemable_now()?, dec!("0"));
+
+    helper.advance_time_seconds(604800);
+    assert_eq!(helper.get_total_redeemable_now()?, dec!("1000"));
+
+    // 10% of the LP supply redeems 10% of the 1000 vested tokens
+    let _ = helper.redeem_lp_from_account(&mut dummy_account, lp_resource, dec!("1000"))?;
+    assert_eq!(helper.get_total_redeemable_now()?, dec!("900"));
+
+    // Later refills are included, covering all 9000 remaining LP tokens
+    helper.advance_time_days(365);
+    let total_redeemable = helper.get_total_redeemable_now()?;
+    assert_eq!(total_redeemable, helper.get_pool_vault_amount()?);
+    assert_eq!(total_redeemable, dec!("9900"));
+
+    Ok(())
+}
+
+#[test]
+fn test_get_lp_total_supply() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    assert_eq!(helper.get_lp_total_supply()?, dec!("0"));
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("4000"), account)?;
+
+    // Claiming moves LP tokens out of the vault but keeps the supply
+    assert_eq!(helper.get_lp_token_amount()?, dec!("6000"));
+    assert_eq!(helper.get_lp_total_supply()?, dec!("10000"));
+
+    helper.advance_time_seconds(604800);
+    let _ = helper.redeem_lp_from_account(&mut dummy_account, lp_resource, dec!("1000"))?;
+
+    // Redeeming burns the LP tokens: 6000 unclaimed + 3000 claimed
+    assert_eq!(helper.get_lp_total_supply()?, dec!("9000"));
+
+    Ok(())
+}
+
+#[test]
+fn test_get_distributed_lp() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+    assert_eq!(helper.get_distributed_lp()?, dec!("0"));
+
+    let lp_resource = helper.get_lp_resource_address();
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    let (_, other_account) = helper.create_dummy_account()?;
+    helper.claim(dec!("3000"), account)?;
+    helper.claim(dec!("1500"), other_account)?;
+    assert_eq!(helper.get_distributed_lp()?, dec!("4500"));
+
+    // Redeemed LP tokens are burned and no longer outstanding
+    helper.advance_time_seconds(604800);
+    let _ = helper.redeem_lp_from_account(&mut dummy_account, lp_resource, dec!("1000"))?;
+    assert_eq!(helper.get_distributed_lp()?, dec!("3500"));
+    assert_eq!(
+        helper.get_distributed_lp()?,
+        helper.get_lp_total_supply()? - helper.get_lp_token_amount()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_claimed_amount_is_tracked_per_account() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let (_dummy_account_1, account_1) = helper.create_dummy_account()?;
+    let (_dummy_account_2, account_2) = helper.create_dummy_account()?;
+
+    assert_eq!(helper.get_claimed_amount(account_1)?, dec!("0"));
+
+    helper.claim(dec!("1000"), account_1)?;
+    helper.claim_batch(vec![(dec!("500"), account_1), (dec!("2000"), account_2)])?;
+
+    assert_eq!(helper.get_claimed_amount(account_1)?, dec!("1500"));
+    assert_eq!(helper.get_claimed_amount(account_2)?, dec!("2000"));
+
+    Ok(())
+}
+
+#[test]
+fn test_get_claimants() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    assert!(helper.get_claimants(0, 10)?.is_empty());
+    assert_eq!(helper.get_claimant_count()?, 0);
+
+    let (_dummy_account_1, account_1) = helper.create_dummy_account()?;
+    let (_dummy_account_2, account_2) = helper.create_dummy_account()?;
+    let (_dummy_account_3, account_3) = helper.create_dummy_account()?;
+
+    helper.claim(dec!("1000"), account_1)?;
+    helper.claim(dec!("1000"), account_2)?;
+    helper.claim(dec!("500"), account_1)?;
+    helper.claim(dec!("1000"), account_3)?;
+
+    // Every account appears once, in order of its first claim
+    assert_eq!(helper.get_claimant_count()?, 3);
+    assert_eq!(
+        helper.get_claimants(0, 10)?,
+        vec![account_1, account_2, account_3]
+    );
+
+    // Pages stop at the last account
+    assert_eq!(helper.get_claimants(0, 2)?, vec![account_1, account_2]);
+    assert_eq!(helper.get_claimants(2, 2)?, vec![account_3]);
+    assert!(helper.get_claimants(3, 2)?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_claim_idempotent_only_tops_up() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+    let (dummy_account, account) = helper.create_dummy_account()?;
+
+    helper.claim_idempotent(dec!("1000"), account)?;
+    // Replaying the same claim does nothing
+    helper.claim_idempotent(dec!("1000"), account)?;
+    assert_eq!(
+        helper.get_account_balance(&dummy_account, lp_resource)?,
+        dec!("1000")
+    );
+
+    // A higher total only delivers the difference
+    helper.claim_idempotent(dec!("1500"), account)?;
+    assert_eq!(
+        helper.get_account_balance(&dummy_account, lp_resource)?,
+        dec!("1500")
+    );
+    assert_eq!(helper.get_claimed_amount(account)?, dec!("1500"));
+    assert_eq!(helper.get_lp_token_amount()?, dec!("8500"));
+
+    Ok(())
+}
+
+#[test]
+fn test_reserve_then_finalize_claim() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+    let (dummy_account, account) = helper.create_dummy_account()?;
+    let expiry = helper.env.get_current_time().add_seconds(3600).unwrap();
+
+    helper.reserve_claim(dec!("1000"), account, expiry)?;
+    assert_eq!(
+        helper.get_reservation(account)?,
+        Some(ClaimReservation {
+            lp_amount: dec!("1000"),
+            expires_at: expiry,
+        })
+    );
+    // The reserved LP tokens are out of the vault but not delivered yet
+    assert_eq!(helper.get_lp_token_amount()?, dec!("9000"));
+    assert_eq!(
+        helper.get_account_balance(&dummy_account, lp_resource)?,
+        dec!("0")
+    );
+    assert_eq!(helper.get_distributed_lp()?, dec!("0"));
+
+    helper.advance_time_seconds(1800);
+    assert_eq!(helper.finalize_claim(account)?, dec!("1000"));
+
+    assert_eq!(helper.get_reservation(account)?, None);
+    assert_eq!(
+        helper.get_account_balance(&dummy_account, lp_resource)?,
+        dec!("1000")
+    );
+    assert_eq!(helper.get_claimed_amount(account)?, dec!("1000"));
+    assert_eq!(helper.get_lp_token_amount()?, dec!("9000"));
+
+    Ok(())
+}
+
+#[test]
+fn test_reserve_then_release_expired_claim() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+    let (dummy_account, account) = helper.create_dummy_account()?;
+    let expiry = helper.env.get_current_time().add_seconds(3600).unwrap();
+
+    helper.reserve_claim(dec!("1000"), account, expiry)?;
+    helper.advance_time_seconds(3600);
+
+    assert_eq!(helper.release_expired(account)?, dec!("1000"));
+
+    assert_eq!(helper.get_reservation(account)?, None);
+    assert_eq!(helper.get_lp_token_amount()?, dec!("10000"));
+    assert_eq!(
+        helper.get_account_balance(&dummy_account, lp_resource)?,
+        dec!("0")
+    );
+    assert_eq!(helper.get_claimed_amount(account)?, dec!("0"));
+
+    // The account can be reserved for again
+    let expiry = helper.env.get_current_time().add_seconds(3600).unwrap();
+    helper.reserve_claim(dec!("500"), account, expiry)?;
+    assert_eq!(helper.get_lp_token_amount()?, dec!("9500"));
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "Reservation has expired.")]
+fn test_finalize_expired_claim_fails() {
+    let mut helper = Helper::new().unwrap();
+
+    helper.create_pool_units(dec!("10000")).unwrap();
+    helper.finish_setup().unwrap();
+
+    let (_dummy_account, account) = helper.create_dummy_account().unwrap();
+    let expiry = helper.env.get_current_time().add_seconds(3600).unwrap();
+
+    helper.reserve_claim(dec!("1000"), account, expiry).unwrap();
+    helper.advance_time_seconds(3600);
+
+    // This should panic
+    helper.finalize_claim(account).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Reservation has not expired yet.")]
+fn test_release_unexpired_claim_fails() {
+    let mut helper = Helper::new().unwrap();
+
+    helper.create_pool_units(dec!("10000")).unwrap();
+    helper.finish_setup().unwrap();
+
+    let (_dummy_account, account) = helper.create_dummy_account().unwrap();
+    let expiry = helper.env.get_current_time().add_seconds(3600).unwrap();
+
+    helper.reserve_claim(dec!("1000"), account, expiry).unwrap();
+    helper.advance_time_seconds(1800);
+
+    // This should panic
+    helper.release_expired(account).unwrap();
+}
+
+#[test]
+fn test_reserve_claim_for_account_not_on_allowlist_fails() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let (_dummy_account, account) = helper.create_dummy_account()?;
+    let expiry = helper.env.get_current_time().add_seconds(3600).unwrap();
+    helper.set_allowlist_enabled(true)?;
+
+    // Rejected when reserving, not only once the reservation is finalized
+    helper::assert_vester_error(
+        helper.reserve_claim(dec!("1000"), account, expiry),
+        VesterError::AccountNotAllowed,
+    );
+    assert_eq!(helper.get_lp_token_amount()?, dec!("10000"));
+
+    Ok(())
+}
+
+#[test]
+fn test_reserve_claim_counts_claimed_amount_towards_max_claim() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new_with(HelperOptions {
+        limits: VesterLimits {
+            max_claim_per_account: Some(dec!("1000")),
+            ..Default::default()
+        },
+        ..Default::default()
+    })?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let (_dummy_account, account) = helper.create_dummy_account()?;
+    let expiry = helper.env.get_current_time().add_seconds(3600).unwrap();
+    helper.claim(dec!("600"), account)?;
+
+    helper::assert_vester_error(
+        helper.reserve_claim(dec!("401"), account, expiry),
+        VesterError::ClaimLimitExceeded,
+    );
+    helper.reserve_claim(dec!("400"), account, expiry)?;
+    helper.finalize_claim(account)?;
+    assert_eq!(helper.get_claimed_amount(account)?, dec!("1000"));
+
+    Ok(())
+}
+
+#[test]
+fn test_claim_up_to_max_claim_per_account() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new_with(HelperOptions {
+        limits: VesterLimits {
+            max_claim_per_account: Some(dec!("1000")),
+            ..Default::default()
+        },
+        ..Default::default()
+    })?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let (_dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("600"), account)?;
+    helper.claim(dec!("400"), account)?;
+
+    assert_eq!(helper.get_claimed_amount(account)?, dec!("1000"));
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "Claim exceeds the maximum claim per account.")]
+fn test_claim_exceeding_max_claim_per_account_fails() {
+    let mut helper = Helper::new_with(HelperOptions {
+        limits: VesterLimits {
+            max_claim_per_account: Some(dec!("1000")),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .unwrap();
+
+    helper.create_pool_units(dec!("10000")).unwrap();
+    helper.finish_setup().unwrap();
+
+    let (_dummy_account, account) = helper.create_dummy_account().unwrap();
+    helper.claim(dec!("600"), account).unwrap();
+    helper
+        .claim_batch(vec![(dec!("300"), account), (dec!("101"), account)])
+        .unwrap();
+}
+
+#[test]
+fn test_can_claim() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new_with(HelperOptions {
+        limits: VesterLimits {
+            max_claim_per_account: Some(dec!("3000")),
+            ..Default::default()
+        },
+        ..Default::default()
+    })?;
+    let (_dummy_account, account) = helper.create_dummy_account()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+
+    // Setup not finished
+    assert!(!helper.can_claim(dec!("1000"), account)?);
+
+    helper.finish_setup()?;
+    assert!(helper.can_claim(dec!("1000"), account)?);
+
+    // Non-positive amounts
+    assert!(!helper.can_claim(dec!("0"), account)?);
+    assert!(!helper.can_claim(dec!("-1"), account)?);
+
+    // Cap: 2000 already claimed, 1000 more is fine but 1001 is not
+    helper.claim(dec!("2000"), account)?;
+    assert!(helper.can_claim(dec!("1000"), account)?);
+    assert!(!helper.can_claim(dec!("1001"), account)?);
+
+    // More than the vault holds: 2000 LP left after two more claims
+    let (_other_dummy_account, other_account) = helper.create_dummy_account()?;
+    helper.claim(dec!("3000"), other_account)?;
+    let (_third_dummy_account, third_account) = helper.create_dummy_account()?;
+    helper.claim(dec!("3000"), third_account)?;
+    let (_fourth_dummy_account, fourth_account) = helper.create_dummy_account()?;
+    assert!(helper.can_claim(dec!("2000"), fourth_account)?);
+    assert!(!helper.can_claim(dec!("2001"), fourth_account)?);
+
+    Ok(())
+}
+
+#[test]
+fn test_recover_from_locker() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    // The account refuses deposits, so the claim ends up in the locker
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.reject_deposits(&mut dummy_account)?;
+    helper.claim(dec!("1000"), account)?;
+
+    let lp_resource = helper.get_lp_resource_address();
+    assert_eq!(
+        helper.get_account_balance(&dummy_account, lp_resource)?,
+        dec!("0")
+    );
+
+    let recovered = helper.recover_from_locker(account, dec!("400"))?;
+    assert_eq!(recovered.resource_address(&mut helper.env)?, lp_resource);
+    assert_eq!(recovered.amount(&mut helper.env)?, dec!("400"));
+
+    // The rest is still in the locker
+    let rest = helper.recover_from_locker(account, dec!("600"))?;
+    assert_eq!(rest.amount(&mut helper.env)?, dec!("600"));
+
+    Ok(())
+}
+
+#[test]
+fn test_get_locker_balance() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    // An account accepting deposits receives its claim directly
+    let (_, open_account) = helper.create_dummy_account()?;
+    helper.claim(dec!("500"), open_account)?;
+    assert_eq!(helper.get_locker_balance(open_account)?, dec!("0"));
+
+    // An account refusing deposits has its claims waiting in the locker
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.reject_deposits(&mut dummy_account)?;
+    helper.claim(dec!("1000"), account)?;
+    helper.claim(dec!("250"), account)?;
+    assert_eq!(helper.get_locker_balance(account)?, dec!("1250"));
+
+    // Recovering from the locker reduces the balance
+    let _ = helper.recover_from_locker(account, dec!("400"))?;
+    assert_eq!(helper.get_locker_balance(account)?, dec!("850"));
+
+    Ok(())
+}
+
+#[test]
+fn test_recover_from_locker_requires_recovery_badge() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.reject_deposits(&mut dummy_account)?;
+    helper.claim(dec!("1000"), account)?;
+
+    // Neither the admin nor the super admin badge can recover
+    let admin_badge_address = helper.admin_badge_address;
+    let result = helper.recover_from_locker_with_badge(account, dec!("400"), admin_badge_address);
+    assert!(matches!(
+        result,
+        Err(RuntimeError::SystemModuleError(
+            SystemModuleError::AuthError(..)
+        ))
+    ));
+
+    let super_admin_badge_address = helper.super_admin_badge_address;
+    let result =
+        helper.recover_from_locker_with_badge(account, dec!("400"), super_admin_badge_address);
+    assert!(matches!(
+        result,
+        Err(RuntimeError::SystemModuleError(
+            SystemModuleError::AuthError(..)
+        ))
+    ));
+
+    // The recovery badge can
+    let recovery_badge_address = helper.recovery_badge_address;
+    let recovered =
+        helper.recover_from_locker_with_badge(account, dec!("400"), recovery_badge_address)?;
+    assert_eq!(recovered.amount(&mut helper.env)?, dec!("400"));
+
+    Ok(())
+}
+
+#[test]
+fn test_rescue_stray_tokens() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    let stray_tokens = ResourceBuilder::new_fungible(OwnerRole::None)
+        .divisibility(18)
+        .mint_initial_supply(500, &mut helper.env)?;
+    let stray_resource = stray_tokens.resource_address(&mut helper.env)?;
+
+    // Deposits of the same resource end up in the same vault
+    let first_part = stray_tokens.take(dec!("200"), &mut helper.env)?;
+    helper.deposit_stray(first_part.into())?;
+    helper.deposit_stray(stray_tokens.into())?;
+
+    let rescued = helper.rescue_tokens(stray_resource)?;
+    assert_eq!(rescued.resource_address(&mut helper.env)?, stray_resource);
+    assert_eq!(rescued.amount(&mut helper.env)?, dec!("500"));
+
+    // Nothing is left behind
+    let rescued_again = helper.rescue_tokens(stray_resource)?;
+    assert_eq!(rescued_again.amount(&mut helper.env)?, dec!("0"));
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "Use the dedicated methods to deposit the token to vest or LP tokens.")]
+fn test_deposit_stray_token_to_vest_fails() {
+    let mut helper = Helper::new().unwrap();
+
+    let tokens = helper
+        .token_to_vest
+        .take(dec!("100"), &mut helper.env)
+        .unwrap();
+    // This should panic
+    helper.deposit_stray(tokens).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "No stray tokens of this resource.")]
+fn test_rescue_tokens_of_unknown_resource_fails() {
+    let mut helper = Helper::new().unwrap();
+
+    let token_address = helper.token_address;
+    // This should panic
+    helper.rescue_tokens(token_address).unwrap();
+}
+
+// ==================== Maturity Value Tests ====================
+
+#[test]
+fn test_maturity_value_with_no_redemptions() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    helper.advance_to_vest_start()?;
+    helper.refill()?;
+
+    // With no redemptions, maturity value should be exactly 1
+    // Formula: (total_tokens / pool_unlocked) * (pool_unlocked / lp_supply) = total_tokens / lp_supply = 10000 / 10000 = 1
+    let maturity_value = helper.get_maturity_value()?;
+
+    helper::assert_approx_eq(
+        maturity_value,
+        dec!("1"),
+        helper::TOLERANCE,
+        "initial maturity value",
+    );
+
+    // Test at 50% vesting - should still be 1
+    helper.advance_to_progress(dec!("0.5"))?;
+    helper.refill()?;
+
+    let maturity_50 = helper.get_maturity_value()?;
+    helper::assert_approx_eq(
+        maturity_50,
+        dec!("1"),
+        helper::TOLERANCE,
+        "maturity at 50% vesting",
+    );
+
+    // Test at 100% vesting - should still be 1
+    helper.advance_to_fully_vested()?;
+    helper.refill()?;
+
+    let maturity_100 = helper.get_maturity_value()?;
+    helper::assert_approx_eq(
+        maturity_100,
+        dec!("1"),
+        helper::TOLERANCE,
+        "maturity at 100% vesting",
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_maturity_value_is_realized_at_full_vesting() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new_with(HelperOptions {
+        token_divisibility: 2,
+        rounding_mode: RoundingMode::ToZero,
+        ..Default::default()
+    })?;
+
+    helper.create_pool_units(dec!("100.03"))?;
+    helper.finish_setup()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+    let (mut holder_account, holder) = helper.create_dummy_account()?;
+    let (mut early_account, early) = helper.create_dummy_account()?;
+    helper.claim(dec!("30"), holder)?;
+    helper.claim(dec!("20"), early)?;
+
+    // An early redemption forfeits tokens to the remaining holders
+    helper.advance_time_seconds(604800);
+    helper.advance_time_days(50);
+    let _ = helper.redeem_lp_from_account(&mut early_account, lp_resource, dec!("10"))?;
+
+    let maturity_value = helper.get_maturity_value()?;
+    assert!(maturity_value > dec!("1"));
+
+    // Holding to full vesting realizes the reported maturity value, up to
+    // the rounding of the final redemption to whole cents
+    helper.advance_time_days(365);
+    let redeemed_tokens =
+        helper.redeem_lp_from_account(&mut holder_account, lp_resource, dec!("30"))?;
+    let realized_value = redeemed_tokens.amount(&mut helper.env)? / dec!("30");
+    helper::assert_approx_eq(
+        realized_value,
+        maturity_value,
+        dec!("0.01") / dec!("30"),
+        "realized value per LP token",
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_maturity_value_before_first_refill() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    // The pool is empty during the pre-claim period, this must not divide by zero
+    assert_eq!(helper.get_pool_vault_amount()?, dec!("0"));
+    let maturity_value = helper.get_maturity_value()?;
+    assert_eq!(maturity_value, dec!("1"));
+
+    Ok(())
+}
+
+#[test]
+fn test_maturity_value_during_cliff() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new_with_config(365, dec!("0.1"), 604800, 30)?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    // Advance into the cliff, where refill leaves the pool empty
+    helper.advance_time_seconds(604800);
+    helper.advance_time_days(10);
+
+    let maturity_value = helper.get_maturity_value()?;
+    assert_eq!(maturity_value, dec!("1"));
+
+    Ok(())
+}
+
+#[test]
+fn test_simulate_maturity_after_redemption_matches_redeem() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("4000"), account)?;
+    helper.advance_to_progress(dec!("0.5"))?;
+
+    // Redeeming nothing leaves the maturity value as it is
+    let current = helper.simulate_maturity_after_redemption(dec!("0"))?;
+    assert_eq!(current, helper.get_maturity_value()?);
+
+    // Half of the 4000 distributed LP tokens take 20% of the 5500 vested
+    let simulated = helper.simulate_maturity_after_redemption(dec!("0.5"))?;
+    helper::assert_approx_eq(
+        simulated,
+        dec!("8900") / dec!("8000"),
+        helper::TOLERANCE,
+        "simulated maturity value",
+    );
+
+    let _ = helper.redeem_lp_from_account(&mut dummy_account, lp_resource, dec!("2000"))?;
+    helper::assert_approx_eq(
+        helper.get_maturity_value()?,
+        simulated,
+        helper::TOLERANCE,
+        "maturity value after the redemption",
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_simulate_maturity_after_redeeming_all_distributed_lp() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new_with(HelperOptions {
+        forfeit_policy: ForfeitPolicy::Treasury,
+        ..Default::default()
+    })?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("4000"), account)?;
+    helper.advance_to_progress(dec!("0.5"))?;
+
+    // The unclaimed 6000 LP tokens remain, and the treasury policy keeps
+    // their maturity value unchanged
+    let simulated = helper.simulate_maturity_after_redemption(dec!("1"))?;
+    helper::assert_approx_eq(
+        simulated,
+        dec!("1"),
+        helper::TOLERANCE,
+        "simulated maturity value",
+    );
+
+    let _ = helper.redeem_lp_from_account(&mut dummy_account, lp_resource, dec!("4000"))?;
+    helper::assert_approx_eq(
+        helper.get_maturity_value()?,
+        simulated,
+        helper::TOLERANCE,
+        "maturity value after the redemption",
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_simulate_maturity_after_redemption_without_remaining_lp() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let (_, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("10000"), account)?;
+    helper.advance_to_progress(dec!("0.5"))?;
+
+    // No LP tokens would be left to share anything
+    assert_eq!(
+        helper.simulate_maturity_after_redemption(dec!("1"))?,
+        dec!("0")
+    );
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "fraction_redeemed_now must be between 0 and 1")]
+fn test_simulate_maturity_after_redemption_above_one_fails() {
+    let mut helper = Helper::new().unwrap();
+
+    helper.create_pool_units(dec!("10000")).unwrap();
+    // This should panic
+    helper
+        .simulate_maturity_after_redemption(dec!("1.1"))
+        .unwrap();
+}
+
+#[test]
+fn test_forfeit_policy_redistribute_raises_maturity() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new_with(HelperOptions {
+        forfeit_policy: ForfeitPolicy::Redistribute,
+        ..Default::default()
+    })?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("5000"), account)?;
+
+    // Half of the LP supply redeems at 55% vested for 2750 tokens
+    helper.advance_to_progress(dec!("0.5"))?;
+    let _ = helper.redeem_lp_from_account(&mut dummy_account, lp_resource, dec!("5000"))?;
+
+    // The forfeited 2250 locked tokens stay for the remaining 5000 LP tokens
+    helper::assert_approx_eq(
+        helper.get_maturity_value()?,
+        dec!("1.45"),
+        helper::TOLERANCE,
+        "maturity value",
+    );
+    assert_eq!(helper.get_treasury_amount()?, dec!("0"));
+
+    Ok(())
+}
+
+#[test]
+fn test_forfeit_policy_treasury_keeps_maturity() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new_with(HelperOptions {
+        forfeit_policy: ForfeitPolicy::Treasury,
+        ..Default::default()
+    })?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("5000"), account)?;
+
+    // Half of the LP supply redeems at 55% vested for 2750 tokens
+    helper.advance_to_progress(dec!("0.5"))?;
+    let _ = helper.redeem_lp_from_account(&mut dummy_account, lp_resource, dec!("5000"))?;
+
+    // The forfeited 2250 locked tokens go to the treasury instead
+    helper::assert_approx_eq(
+        helper.get_treasury_amount()?,
+        dec!("2250"),
+        helper::TOLERANCE,
+        "treasury",
+    );
+    helper::assert_approx_eq(
+        helper.get_maturity_value()?,
+        dec!("1"),
+        helper::TOLERANCE,
+        "maturity value",
+    );
+
+    // The rest keeps vesting on the original schedule: 77.5% of 5000
+    helper.advance_to_progress(dec!("0.75"))?;
+    helper.refill()?;
+    helper::assert_approx_eq(
+        helper.get_pool_vault_amount()?,
+        dec!("3875"),
+        helper::TOLERANCE,
+        "pool at 75% progress",
+    );
+
+    helper.advance_to_fully_vested()?;
+    helper.refill()?;
+    helper::assert_approx_eq(
+        helper.get_pool_vault_amount()?,
+        dec!("5000"),
+        helper::TOLERANCE,
+        "pool at full vesting",
+    );
+    assert_eq!(helper.get_locked_vault_amount()?, dec!("0"));
+
+    Ok(())
+}
+
+#[test]
+fn test_verify_conservation_across_redemptions() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new_with(HelperOptions {
+        redemption_fee_fraction: dec!("0.1"),
+        ..Default::default()
+    })?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    assert!(helper.verify_conservation()?);
+    helper.finish_setup()?;
+    assert!(helper.verify_conservation()?);
+
+    let lp_resource = helper.get_lp_resource_address();
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("6000"), account)?;
+
+    for (progress, lp_amount) in [
+        (dec!("0"), dec!("1000")),
+        (dec!("0.3"), dec!("1500")),
+        (dec!("0.6"), dec!("2000")),
+    ] {
+        helper.advance_to_progress(progress)?;
+        let _ = helper.redeem_lp_from_account(&mut dummy_account, lp_resource, lp_amount)?;
+        assert!(helper.verify_conservation()?);
+
+        helper.refill()?;
+        assert!(helper.verify_conservation()?);
+    }
+
+    helper.advance_to_fully_vested()?;
+    let _ = helper.redeem_lp_from_account(&mut dummy_account, lp_resource, dec!("1500"))?;
+    assert!(helper.verify_conservation()?);
+
+    Ok(())
+}
+
+#[test]
+fn test_verify_conservation_with_treasury_forfeits() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new_with(HelperOptions {
+        forfeit_policy: ForfeitPolicy::Treasury,
+        ..Default::default()
+    })?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("9000"), account)?;
+
+    for (progress, lp_amount) in [
+        (dec!("0.2"), dec!("3000")),
+        (dec!("0.5"), dec!("3000")),
+        (dec!("0.9"), dec!("2000")),
+    ] {
+        helper.advance_to_progress(progress)?;
+        let _ = helper.redeem_lp_from_account(&mut dummy_account, lp_resource, lp_amount)?;
+        assert!(helper.verify_conservation()?);
+    }
+
+    // Withdrawing the treasury does not disturb the accounting
+    let _ = helper.withdraw_treasury()?;
+    helper.advance_to_fully_vested()?;
+    assert!(helper.verify_conservation()?);
+
+    Ok(())
+}
+
+#[test]
+fn test_full_lifecycle_no_forfeiture() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+    helper.advance_to_vest_start()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+    let token_address = helper.token_address;
+    let (mut account1, addr1) = helper.create_dummy_account()?;
+    let (mut account2, addr2) = helper.create_dummy_account()?;
+    helper.claim(dec!("4000"), addr1)?;
+    helper.claim(dec!("1500"), addr2)?;
+
+    helper.advance_to_fully_vested()?;
+    helper.advance_time_days(1);
+    let total_tokens_to_vest = helper.get_total_tokens_to_vest()?;
+    let lp_supply = helper.get_lp_total_supply()?;
+
+    // Past vest_end, every LP token is worth its full share
+    let (redeemed_tokens, forfeited) =
+        helper.redeem_lp_from_account_with_forfeited(&mut account1, lp_resource, dec!("4000"))?;
+    assert_eq!(
+        redeemed_tokens.amount(&mut helper.env)?,
+        dec!("4000") / lp_supply * total_tokens_to_vest
+    );
+    assert_eq!(forfeited, dec!("0"));
+
+    let lp_tokens = helper.withdraw_from_account(&mut account2, lp_resource, dec!("1500"))?;
+    helper.redeem_to(lp_tokens, addr2)?;
+    assert_eq!(
+        helper.get_account_balance(&account2, token_address)?,
+        dec!("1500") / lp_supply * total_tokens_to_vest
+    );
+    assert_eq!(helper.get_forfeited_tokens()?, dec!("0"));
+
+    Ok(())
+}
+
+#[test]
+fn test_redeem_half_doubles_maturity() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    // Advance to vest_start (0% linear progress, 10% initial vest)
+    helper.advance_time_seconds(604800);
+    helper.refill()?;
+
+    let pool_initial = helper.get_pool_vault_amount()?;
+    let locked_initial = helper.get_locked_vault_amount()?;
+    assert_eq!(pool_initial + locked_initial, dec!("10000"));
+
+    let lp_resource = helper.get_lp_resource_address();
+
+    // Initial maturity = 1
+    let maturity_before = helper.get_maturity_value()?;
+    helper::assert_approx_eq(
+        maturity_before,
+        dec!("1"),
+        helper::TOLERANCE,
+        "maturity before redemption",
+    );
+
+    // Claim and redeem 50% of LP tokens
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("5000"), account)?;
+
+    let redeemed_tokens =
+        helper.redeem_lp_from_account(&mut dummy_account, lp_resource, dec!("5000"))?;
+
+    // Verify redeemed amount is exactly 50% of pool
+    let redeemed_amount = redeemed_tokens.amount(&mut helper.env)?;
+    let expected_redeemed = pool_initial / dec!("2");
+    helper::assert_approx_eq(
+        redeemed_amount,
+        expected_redeemed,
+        helper::TOLERANCE,
+        "redeemed amount",
+    );
+
+    // After redemption:
+    // - Pool had ~1000 tokens (10% initial vest)
+    // - Redeemer got ~500 tokens (50% of pool)
+    // - Pool now has ~500 tokens
+    // - Locked still has ~9000 tokens
+    // - LP tokens remaining: 5000
+    // - Total tokens: 500 + 9000 = 9500
+    // - Maturity = 9500 / 5000 = 1.9
+    let maturity_after = helper.get_maturity_value()?;
+    let pool_after = helper.get_pool_vault_amount()?;
+    let locked_after = helper.get_locked_vault_amount()?;
+
+    let expected_maturity = dec!("1.9");
+    let expected_maturity_2 = (pool_after + locked_after) / dec!("5000");
+
+    helper::assert_approx_eq(
+        maturity_after,
+        expected_maturity,
+        helper::TOLERANCE,
+        "maturity after 50% redemption",
+    );
+
+    helper::assert_approx_eq(
+        expected_maturity,
+        expected_maturity_2,
+        helper::TOLERANCE,
+        "maturity after 50% redemption",
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_redeem_half_early_tracks_forfeited_tokens() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    // Advance to vest_start (0% linear progress, 10% initial vest)
+    helper.advance_time_seconds(604800);
+    helper.refill()?;
+
+    assert_eq!(helper.get_forfeited_tokens()?, dec!("0"));
+
+    let lp_resource = helper.get_lp_resource_address();
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("5000"), account)?;
+
+    let redeemed_tokens =
+        helper.redeem_lp_from_account(&mut dummy_account, lp_resource, dec!("5000"))?;
+    let redeemed_amount = redeemed_tokens.amount(&mut helper.env)?;
+
+    // The redeemer held 50% of 10000 tokens, but only 10% of that had vested:
+    // they receive 500 and forfeit the unvested 4500
+    helper::assert_approx_eq(
+        redeemed_amount,
+        dec!("500"),
+        helper::TOLERANCE,
+        "redeemed amount",
+    );
+    helper::assert_approx_eq(
+        helper.get_forfeited_tokens()?,
+        dec!("4500"),
+        helper::TOLERANCE,
+        "forfeited amount",
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_redeem_returns_forfeited_amount_at_50_percent() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("2000"), account)?;
+
+    // Advance to 50% linear progress (55% total vesting)
+    helper.advance_time_seconds(604800);
+    helper.advance_time_days(182);
+    helper.advance_time_seconds(43200);
+
+    let (redeemed_tokens, forfeited) = helper.redeem_lp_from_account_with_forfeited(
+        &mut dummy_account,
+        lp_resource,
+        dec!("2000"),
+    )?;
+    let redeemed_amount = redeemed_tokens.amount(&mut helper.env)?;
+
+    // 20% of the LP supply: 20% of 5500 vested = 1100 received,
+    // 20% of 10000 at maturity = 2000, so 900 is forfeited
+    helper::assert_approx_eq(
+        redeemed_amount,
+        dec!("1100"),
+        helper::TOLERANCE,
+        "redeemed at 50% progress",
+    );
+    helper::assert_approx_eq(
+        forfeited,
+        dec!("900"),
+        helper::TOLERANCE,
+        "forfeited at 50% progress",
+    );
+    assert_eq!(forfeited, dec!("2000") - redeemed_amount);
+
+    Ok(())
+}
+
+#[test]
+fn test_is_redeemable_resource() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+    let token_address = helper.token_address;
+
+    assert!(helper.is_redeemable_resource(lp_resource)?);
+    assert!(!helper.is_redeemable_resource(token_address)?);
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "Bucket is not the LP token")]
+fn test_redeem_foreign_resource_fails() {
+    let mut helper = Helper::new().unwrap();
+
+    helper.create_pool_units(dec!("10000")).unwrap();
+    helper.finish_setup().unwrap();
+    helper.advance_time_seconds(604800);
+
+    let tokens = helper
+        .token_to_vest
+        .take(dec!("100"), &mut helper.env)
+        .unwrap();
+
+    // This should panic
+    let _ = helper.redeem(tokens).unwrap();
+}
+
+#[test]
+fn test_redeem_at_min_redeem_lp() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new_with(HelperOptions {
+        limits: VesterLimits {
+            min_redeem_lp: dec!("100"),
+            ..Default::default()
+        },
+        ..Default::default()
+    })?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("1000"), account)?;
+    helper.advance_time_seconds(604800);
+
+    // 10% of the LP supply share of the 10% initially vested
+    let redeemed_tokens =
+        helper.redeem_lp_from_account(&mut dummy_account, lp_resource, dec!("100"))?;
+    assert_eq!(redeemed_tokens.amount(&mut helper.env)?, dec!("10"));
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "LP bucket is below the minimum redemption amount")]
+fn test_redeem_below_min_redeem_lp_fails() {
+    let mut helper = Helper::new_with(HelperOptions {
+        limits: VesterLimits {
+            min_redeem_lp: dec!("100"),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .unwrap();
+
+    helper.create_pool_units(dec!("10000")).unwrap();
+    helper.finish_setup().unwrap();
+
+    let lp_resource = helper.get_lp_resource_address();
+    let (mut dummy_account, account) = helper.create_dummy_account().unwrap();
+    helper.claim(dec!("1000"), account).unwrap();
+    helper.advance_time_seconds(604800);
+
+    // This should panic
+    let _ = helper
+        .redeem_lp_from_account(&mut dummy_account, lp_resource, dec!("99.99"))
+        .unwrap();
+}
+
+#[test]
+fn test_redeem_above_min_redemption_value() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new_with(HelperOptions {
+        limits: VesterLimits {
+            min_redemption_value: Some(dec!("0.05")),
+            ..Default::default()
+        },
+        ..Default::default()
+    })?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("1000"), account)?;
+    helper.advance_time_seconds(604800);
+
+    // 1 LP token is worth 0.1 at the start of the vest, above the floor
+    let redeemed_tokens =
+        helper.redeem_lp_from_account(&mut dummy_account, lp_resource, dec!("100"))?;
+    assert_eq!(redeemed_tokens.amount(&mut helper.env)?, dec!("10"));
+
+    Ok(())
+}
+
+#[test]
+fn test_redeem_below_min_redemption_value_fails() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new_with(HelperOptions {
+        limits: VesterLimits {
+            min_redemption_value: Some(dec!("0.2")),
+            ..Default::default()
+        },
+        ..Default::default()
+    })?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("1000"), account)?;
+    helper.advance_time_seconds(604800);
+
+    // 1 LP token is only worth 0.1
+    helper::assert_vester_error(
+        helper.redeem_lp_from_account(&mut dummy_account, lp_resource, dec!("100")),
+        VesterError::RedemptionValueBelowFloor,
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_redemption_fee_is_zero_without_fee_fraction() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("2000"), account)?;
+
+    // Advance to 50% linear progress (55% total vesting)
+    helper.advance_time_seconds(604800);
+    helper.advance_time_days(182);
+    helper.advance_time_seconds(43200);
+
+    let redeemed_tokens =
+        helper.redeem_lp_from_account(&mut dummy_account, lp_resource, dec!("2000"))?;
+
+    helper::assert_approx_eq(
+        redeemed_tokens.amount(&mut helper.env)?,
+        dec!("1100"),
+        helper::TOLERANCE,
+        "redeemed without fee",
+    );
+    assert_eq!(helper.get_treasury_amount()?, dec!("0"));
+
+    Ok(())
+}
+
+#[test]
+fn test_redemption_fee_goes_to_treasury() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new_with(HelperOptions {
+        redemption_fee_fraction: dec!("0.1"),
+        ..Default::default()
+    })?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("2000"), account)?;
+
+    // Advance to 50% linear progress (55% total vesting)
+    helper.advance_time_seconds(604800);
+    helper.advance_time_days(182);
+    helper.advance_time_seconds(43200);
+
+    let preview = helper.preview_redeem(dec!("2000"))?;
+    let (redeemed_tokens, forfeited) = helper.redeem_lp_from_account_with_forfeited(
+        &mut dummy_account,
+        lp_resource,
+        dec!("2000"),
+    )?;
+    let redeemed_amount = redeemed_tokens.amount(&mut helper.env)?;
+    let fee = helper.get_treasury_amount()?;
+
+    // 1100 paid out of the pool, 10% of it kept as fee
+    helper::assert_approx_eq(
+        redeemed_amount,
+        dec!("990"),
+        helper::TOLERANCE,
+        "redeemed after fee",
+    );
+    helper::assert_approx_eq(fee, dec!("110"), helper::TOLERANCE, "fee in treasury");
+    assert_eq!(preview, redeemed_amount);
+
+    // The fee is not forfeited
+    helper::assert_approx_eq(forfeited, dec!("900"), helper::TOLERANCE, "forfeited");
+    assert_eq!(helper.get_total_redeemed()?, redeemed_amount + fee);
+
+    let fees = helper.withdraw_treasury()?;
+    assert_eq!(fees.amount(&mut helper.env)?, fee);
+    assert_eq!(helper.get_treasury_amount()?, dec!("0"));
+
+    Ok(())
+}
+
+#[test]
+fn test_no_redemption_fee_after_vest_end() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new_with(HelperOptions {
+        redemption_fee_fraction: dec!("0.1"),
+        ..Default::default()
+    })?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("2000"), account)?;
+
+    helper.advance_time_seconds(604800);
+    helper.advance_time_days(365);
+
+    let redeemed_tokens =
+        helper.redeem_lp_from_account(&mut dummy_account, lp_resource, dec!("2000"))?;
+
+    assert_eq!(redeemed_tokens.amount(&mut helper.env)?, dec!("2000"));
+    assert_eq!(helper.get_treasury_amount()?, dec!("0"));
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "redemption_fee_fraction must be between 0 and 1")]
+fn test_redemption_fee_above_one_fails() {
+    let _ = Helper::new_with(HelperOptions {
+        redemption_fee_fraction: dec!("1.1"),
+        ..Default::default()
+    })
+    .unwrap();
+}
+
+#[test]
+fn test_redeem_to_account() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+    let token_address = helper.token_address;
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    let (receiving_account, receiver) = helper.create_dummy_account()?;
+    helper.claim(dec!("2000"), account)?;
+
+    // Advance to 50% linear progress (55% total vesting)
+    helper.advance_time_seconds(604800);
+    helper.advance_time_days(182);
+    helper.advance_time_seconds(43200);
+
+    let lp_tokens = helper.withdraw_from_account(&mut dummy_account, lp_resource, dec!("2000"))?;
+    helper.redeem_to(lp_tokens, receiver)?;
+
+    // 20% of the LP supply: 20% of 5500 vested
+    helper::assert_approx_eq(
+        helper.get_account_balance(&receiving_account, token_address)?,
+        dec!("1100"),
+        helper::TOLERANCE,
+        "redeemed into account",
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_redeem_to_account_rejecting_deposits_uses_locker() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let token_address = helper.token_address;
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.reject_deposits(&mut dummy_account)?;
+    helper.claim(dec!("1000"), account)?;
+
+    // The claim ends up in the locker, so recover it first
+    let lp_tokens = helper.recover_from_locker(account, dec!("1000"))?;
+
+    helper.advance_time_seconds(604800);
+    helper.redeem_to(lp_tokens, account)?;
+
+    // 10% of the LP supply share of the 10% initially vested
+    assert_eq!(
+        helper.get_account_balance(&dummy_account, token_address)?,
+        dec!("0")
+    );
+    let locker = helper.get_locker_address()?;
+    let locked_tokens = helper.env.call_method_typed::<_, _, Decimal>(
+        locker.0,
+        ACCOUNT_LOCKER_GET_AMOUNT_IDENT,
+        &(account, token_address),
+    )?;
+    assert_eq!(locked_tokens, dec!("100"));
+
+    Ok(())
+}
+
+#[test]
+fn test_redeem_amount_returns_leftover_lp() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("5000"), account)?;
+
+    // Advance to 50% linear progress (55% total vesting)
+    helper.advance_time_seconds(604800);
+    helper.advance_time_days(182);
+    helper.advance_time_seconds(43200);
+
+    let lp_tokens = helper.withdraw_from_account(&mut dummy_account, lp_resource, dec!("5000"))?;
+    let (redeemed_tokens, leftover_lp) = helper.redeem_amount(lp_tokens, dec!("2000"))?;
+
+    // 20% of the LP supply: 20% of 5500 vested
+    assert_eq!(
+        redeemed_tokens.resource_address(&mut helper.env)?,
+        helper.token_address
+    );
+    helper::assert_approx_eq(
+        redeemed_tokens.amount(&mut helper.env)?,
+        dec!("1100"),
+        helper::TOLERANCE,
+        "redeemed tokens",
+    );
+    assert_eq!(leftover_lp.resource_address(&mut helper.env)?, lp_resource);
+    assert_eq!(leftover_lp.amount(&mut helper.env)?, dec!("3000"));
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "Amount exceeds the LP tokens in the bucket.")]
+fn test_redeem_amount_exceeding_bucket_fails() {
+    let mut helper = Helper::new().unwrap();
+
+    helper.create_pool_units(dec!("10000")).unwrap();
+    helper.finish_setup().unwrap();
+
+    let lp_resource = helper.get_lp_resource_address();
+    let (mut dummy_account, account) = helper.create_dummy_account().unwrap();
+    helper.claim(dec!("1000"), account).unwrap();
+    helper.advance_time_seconds(604800);
+
+    let lp_tokens = helper
+        .withdraw_from_account(&mut dummy_account, lp_resource, dec!("1000"))
+        .unwrap();
+    let _ = helper.redeem_amount(lp_tokens, dec!("1001")).unwrap();
+}
+
+#[test]
+fn test_redeem_with_min_out_at_quoted_amount() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("2000"), account)?;
+
+    // Advance to 50% linear progress (55% total vesting)
+    helper.advance_time_seconds(604800);
+    helper.advance_time_days(182);
+    helper.advance_time_seconds(43200);
+
+    let quoted = helper.preview_redeem(dec!("2000"))?;
+    let lp_tokens = helper.withdraw_from_account(&mut dummy_account, lp_resource, dec!("2000"))?;
+    let redeemed_tokens = helper.redeem_with_min_out(lp_tokens, quoted)?;
+
+    assert_eq!(
+        redeemed_tokens.resource_address(&mut helper.env)?,
+        helper.token_address
+    );
+    assert_eq!(redeemed_tokens.amount(&mut helper.env)?, quoted);
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "Redemption yields less than the minimum amount out.")]
+fn test_redeem_with_min_out_above_achievable_fails() {
+    let mut helper = Helper::new().unwrap();
+
+    helper.create_pool_units(dec!("10000")).unwrap();
+    helper.finish_setup().unwrap();
+
+    let lp_resource = helper.get_lp_resource_address();
+    let (mut dummy_account, account) = helper.create_dummy_account().unwrap();
+    helper.claim(dec!("2000"), account).unwrap();
+
+    helper.advance_time_seconds(604800);
+    helper.advance_time_days(182);
+    helper.advance_time_seconds(43200);
+
+    // 20% of the 5500 vested is 1100, so asking for more must abort
+    let lp_tokens = helper
+        .withdraw_from_account(&mut dummy_account, lp_resource, dec!("2000"))
+        .unwrap();
+    // This should panic
+    let _ = helper.redeem_with_min_out(lp_tokens, dec!("1101")).unwrap();
+}
+
+#[test]
+fn test_redeem_without_manual_refill() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("2000"), account)?;
+
+    // Advance to 60% linear progress (64% total vesting), never refilling
+    helper.advance_time_seconds(604800);
+    helper.advance_time_days(219);
+    assert_eq!(helper.get_vested_tokens()?, dec!("0"));
+    assert_eq!(helper.get_pool_vault_amount()?, dec!("0"));
+
+    // redeem refills on its own: 20% of the LP supply gets 20% of 6400
+    let redeemed_tokens =
+        helper.redeem_lp_from_account(&mut dummy_account, lp_resource, dec!("2000"))?;
+    assert_eq!(
+        redeemed_tokens.resource_address(&mut helper.env)?,
+        helper.token_address
+    );
+    assert_eq!(redeemed_tokens.amount(&mut helper.env)?, dec!("1280"));
+    assert_eq!(helper.get_vested_tokens()?, dec!("6400"));
+
+    Ok(())
+}
+
+#[test]
+fn test_total_redeemed_accumulates() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("4000"), account)?;
+
+    assert_eq!(helper.get_total_redeemed()?, dec!("0"));
+
+    // At vest_start only the initial 10% has vested
+    helper.advance_time_seconds(604800);
+    let first = helper.redeem_lp_from_account(&mut dummy_account, lp_resource, dec!("1000"))?;
+    let first_amount = first.amount(&mut helper.env)?;
+    assert_eq!(helper.get_total_redeemed()?, first_amount);
+
+    // Halfway through the vest
+    helper.advance_time_days(182);
+    helper.advance_time_seconds(43200);
+    let second = helper.redeem_lp_from_account(&mut dummy_account, lp_resource, dec!("1000"))?;
+    let second_amount = second.amount(&mut helper.env)?;
+
+    assert!(second_amount > first_amount);
+    assert_eq!(helper.get_total_redeemed()?, first_amount + second_amount);
+
+    Ok(())
+}
+
+#[test]
+fn test_redemption_amounts_at_vesting_stages() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+
+    // Stage 1: Redeem at vest_start (0% linear progress, 10% initial vest)
+    helper.advance_time_seconds(604800);
+    helper.refill()?;
+
+    let pool_at_0 = helper.get_pool_vault_amount()?;
+    // Pool should have exactly 1000 tokens (10% initial vest)
+    helper::assert_approx_eq_rel(
+        pool_at_0,
+        dec!("1000"),
+        helper::REL_TOLERANCE,
+        "pool at 0% progress",
+    );
+
+    let (mut account1, addr1) = helper.create_dummy_account()?;
+    helper.claim(dec!("2000"), addr1)?;
+
+    let redeemed_at_0 = helper.redeem_lp_from_account(&mut account1, lp_resource, dec!("2000"))?;
+    let redeemed_amount_0 = redeemed_at_0.amount(&mut helper.env)?;
+
+    // Should get 20% of pool: 1000 * 0.2 = 200 tokens
+    let expected_redeemed_0 = pool_at_0 * dec!("0.2");
+    helper::assert_approx_eq_rel(
+        redeemed_amount_0,
+        expected_redeemed_0,
+        helper::REL_TOLERANCE,
+        "redeemed at 0% progress",
+    );
+
+    // Stage 2: Redeem at 50% linear progress (55% total vesting)
+    helper.advance_time_days(182);
+    helper.advance_time_seconds(43200); // 0.5 days = 43200 seconds
+    helper.refill()?;
+
+    let pool_at_50 = helper.get_pool_vault_amount()?;
+    // Pool should have: original pool - redeemed + new vesting
+    // Started with 1000 (10% initial vest)
+    // Redeemed 200 (20% of pool), leaving 800
+    // New vesting from 10% to 55%: 45% of 10000 = 4500
+    // Pool = 800 + 4500 = 5300
+    helper::assert_approx_eq_rel(
+        pool_at_50,
+        dec!("5300"),
+        helper::REL_TOLERANCE,
+        "pool at 50% progress",
+    );
+
+    let (mut account2, addr2) = helper.create_dummy_account()?;
+    // 8000 LP remaining, claim 2000 (25% of remaining)
+    helper.claim(dec!("2000"), addr2)?;
+
+    let redeemed_at_50 = helper.redeem_lp_from_account(&mut account2, lp_resource, dec!("2000"))?;
+    let redeemed_amount_50 = redeemed_at_50.amount(&mut helper.env)?;
+
+    // Should get 25% of pool: ~5300 * 0.25 ≈ 1325 (with pool rounding)
+    let expected_redeemed_50 = pool_at_50 * dec!("0.25");
+    helper::assert_approx_eq_rel(
+        redeemed_amount_50,
+        expected_redeemed_50,
+        helper::REL_TOLERANCE,
+        "redeemed at 50% progress",
+    );
+
+    // Stage 3: Redeem at 100% linear progress (100% total vesting)
+    helper.advance_time_days(182);
+    helper.advance_time_seconds(43200); // 0.5 days = 43200 seconds
+    helper.refill()?;
+
+    let pool_at_100 = helper.get_pool_vault_amount()?;
+    let locked_at_100 = helper.get_locked_vault_amount()?;
+
+    // Pool should have: previous pool - redeemed + remaining vesting
+    // Due to pool rounding, exact amounts may vary slightly
+    // Approximately: ~3975 (after 2nd redeem) + ~4500 (remaining vest) ≈ 8475
+    helper::assert_approx_eq_rel(
+        pool_at_100,
+        dec!("8475"),
+        helper::REL_TOLERANCE,
+        "pool at 100% progress",
+    );
+
+    // Nearly all tokens should be vested
+    // Due to OneResourcePool rounding throughout the process, a small amount may remain locked
+    helper::assert_approx_eq(
+        locked_at_100,
+        dec!("0"),
+        helper::TOLERANCE,
+        "locked at 100% progress",
+    );
+
+    let (mut account3, addr3) = helper.create_dummy_account()?;
+    // 6000 LP remaining, claim 2000 (33.33% of remaining)
+    helper.claim(dec!("2000"), addr3)?;
+
+    let redeemed_at_100 =
+        helper.redeem_lp_from_account(&mut account3, lp_resource, dec!("2000"))?;
+    let redeemed_amount_100 = redeemed_at_100.amount(&mut helper.env)?;
+
+    // Should get 33.33% of pool
+    let expected_redeemed_100 = pool_at_100 * dec!("2000") / dec!("6000");
+    helper::assert_approx_eq_rel(
+        redeemed_amount_100,
+        expected_redeemed_100,
+        helper::REL_TOLERANCE,
+        "redeemed at 100% progress",
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_preview_redeem_matches_redeem() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("3000"), account)?;
+
+    // Nothing can be redeemed during the pre-claim period
+    assert_eq!(helper.preview_redeem(dec!("3000"))?, dec!("0"));
+
+    // Advance to vest_start + 100 days without calling refill
+    helper.advance_time_seconds(604800);
+    helper.advance_time_days(100);
+
+    let preview = helper.preview_redeem(dec!("3000"))?;
+
+    // The pool itself doesn't know about the pending refill yet
+    assert_eq!(helper.get_pool_vault_amount()?, dec!("0"));
+
+    let redeemed_tokens =
+        helper.redeem_lp_from_account(&mut dummy_account, lp_resource, dec!("3000"))?;
+    let redeemed_amount = redeemed_tokens.amount(&mut helper.env)?;
+
+    assert_eq!(preview, redeemed_amount);
+
+    Ok(())
+}
+
+#[test]
+fn test_vesting_math_after_redemption() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    // Advance to vest_start and refill
+    helper.advance_time_seconds(604800);
+    helper.refill()?;
+
+    let pool_before = helper.get_pool_vault_amount()?;
+    let vested_before = helper.get_locked_vault_amount()?;
+
+    // Should be exactly 1000 in pool, 9000 locked
+    assert_eq!(pool_before, dec!("1000"));
+    assert_eq!(vested_before, dec!("9000"));
+
+    // Advance to 50%
+    helper.advance_time_days(182);
+    helper.advance_time_seconds(43200); // 0.5 days = 43200 seconds
+    helper.refill()?;
+
+    let pool_at_50_no_redeem = helper.get_pool_vault_amount()?;
+    let locked_at_50_no_redeem = helper.get_locked_vault_amount()?;
+
+    // Should be 5500 in pool, 4500 locked
+    helper::assert_approx_eq(
+        pool_at_50_no_redeem,
+        dec!("5500"),
+        helper::TOLERANCE,
+        "pool at 50% without redemption",
+    );
+    assert_eq!(pool_at_50_no_redeem + locked_at_50_no_redeem, dec!("10000"));
+
+    Ok(())
+}
+
+#[test]
+fn test_redeem_75_percent_quadruples_maturity() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    // Advance to vest_start
+    helper.advance_time_seconds(604800);
+    helper.refill()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+
+    // Claim and redeem 75% of LP tokens (7500)
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("7500"), account)?;
+
+    let _ = helper.redeem_lp_from_account(&mut dummy_account, lp_resource, dec!("7500"))?;
+
+    // After redemption:
+    // - Pool had ~1000 tokens (10% initial vest)
+    // - Redeemer got ~750 tokens (75% of pool)
+    // - Pool now has ~250 tokens
+    // - Locked still has ~9000 tokens
+    // - LP tokens remaining: 2500
+    // - Total tokens: 250 + 9000 = 9250
+    // - Maturity = 9250 / 2500 = 3.7
+    let maturity_after = helper.get_maturity_value()?;
+    let pool_after = helper.get_pool_vault_amount()?;
+    let locked_after = helper.get_locked_vault_amount()?;
+
+    let expected_maturity = (pool_after + locked_after) / dec!("2500");
+
+    helper::assert_approx_eq_rel(
+        maturity_after,
+        expected_maturity,
+        helper::REL_TOLERANCE,
+        "maturity after 75% redemption",
+    );
+
+    Ok(())
+}
+
+// ==================== Test Helper Tests ====================
+
+#[test]
+fn test_assert_approx_eq_rel_scales_with_expected() {
+    // 1e-12 of 1e9 allows for 0.001
+    helper::assert_approx_eq_rel(
+        dec!("1000000000.0009"),
+        dec!("1000000000"),
+        helper::REL_TOLERANCE,
+        "large amount above",
+    );
+    helper::assert_approx_eq_rel(
+        dec!("999999999.9991"),
+        dec!("1000000000"),
+        helper::REL_TOLERANCE,
+        "large amount below",
+    );
+    // Negative expected values use their magnitude
+    helper::assert_approx_eq_rel(
+        dec!("-1000000000.0009"),
+        dec!("-1000000000"),
+        helper::REL_TOLERANCE,
+        "negative amount",
+    );
+    // An expected zero still allows for the absolute tolerance
+    helper::assert_approx_eq_rel(
+        dec!("0.000000000000001"),
+        dec!("0"),
+        helper::REL_TOLERANCE,
+        "zero",
+    );
+}
+
+#[test]
+#[should_panic(expected = "large amount")]
+fn test_assert_approx_eq_rel_rejects_beyond_tolerance() {
+    // This should panic
+    helper::assert_approx_eq_rel(
+        dec!("1000000000.0011"),
+        dec!("1000000000"),
+        helper::REL_TOLERANCE,
+        "large amount",
+    );
+}
+
+#[test]
+#[should_panic(expected = "small amount")]
+fn test_assert_approx_eq_rel_is_tight_for_small_amounts() {
+    // 1e-12 of 1 is below the difference of 1e-11
+    // This should panic
+    helper::assert_approx_eq_rel(
+        dec!("1.00000000001"),
+        dec!("1"),
+        helper::REL_TOLERANCE,
+        "small amount",
+    );
+}
+
+#[test]
+#[should_panic(expected = "advance_time_days must move the clock forward")]
+fn test_advance_time_days_backwards_fails() {
+    let mut helper = Helper::new().unwrap();
+
+    // This should panic
+    helper.advance_time_days(-1);
+}
+
+#[test]
+#[should_panic(expected = "advance_time_seconds must move the clock forward")]
+fn test_advance_time_seconds_zero_fails() {
+    let mut helper = Helper::new().unwrap();
+
+    // This should panic
+    helper.advance_time_seconds(0);
+}
+
+// ==================== Cliff Tests ====================
+
+#[test]
+fn test_cliff_end_set_on_finish_setup() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new_with_config(365, dec!("0.1"), 604800, 30)?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    assert_eq!(helper.get_cliff_end()?, None);
+
+    let setup_time = helper.env.get_current_time();
+    helper.finish_setup()?;
+
+    let expected_cliff_end = setup_time
+        .add_seconds(604800)
+        .unwrap()
+        .add_days(30)
+        .unwrap();
+    assert_eq!(
+        helper
+            .get_cliff_end()?
+            .map(|instant| instant.seconds_since_unix_epoch),
+        Some(expected_cliff_end.seconds_since_unix_epoch)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_refill_during_cliff_vests_nothing() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new_with_config(365, dec!("0.1"), 604800, 73)?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    // Advance to vest_start, the cliff has just begun
+    helper.advance_time_seconds(604800);
+    helper.refill()?;
+
+    assert_eq!(helper.get_pool_vault_amount()?, dec!("0"));
+    assert_eq!(helper.get_locked_vault_amount()?, dec!("10000"));
+
+    // Advance to 1 second before the cliff ends
+    helper.advance_time_days(72);
+    helper.advance_time_seconds(86399);
+    helper.refill()?;
+
+    assert_eq!(helper.get_pool_vault_amount()?, dec!("0"));
+    assert_eq!(helper.get_vested_tokens()?, dec!("0"));
+
+    Ok(())
+}
+
+#[test]
+fn test_refill_at_cliff_end_releases_accrued_tokens() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new_with_config(365, dec!("0.1"), 604800, 73)?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    // Advance to the end of the cliff (73 days = 20% linear progress)
+    helper.advance_time_seconds(604800);
+    helper.advance_time_days(73);
+    helper.refill()?;
+
+    // vested_fraction = 0.1 + (1 - 0.1) * 0.2 = 0.28
+    // Expected pool: 10000 * 0.28 = 2800
+    let pool_amount = helper.get_pool_vault_amount()?;
+    let locked_amount = helper.get_locked_vault_amount()?;
+
+    helper::assert_approx_eq(
+        pool_amount,
+        dec!("2800"),
+        helper::TOLERANCE,
+        "pool at cliff end",
+    );
+    assert_eq!(pool_amount + locked_amount, dec!("10000"));
+
+    Ok(())
+}
+
+#[test]
+fn test_redeem_during_cliff_returns_lp_tokens() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new_with_config(365, dec!("0.1"), 604800, 30)?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("5000"), account)?;
+
+    // Advance into the cliff
+    helper.advance_time_seconds(604800);
+    helper.advance_time_days(10);
+
+    let lp_tokens = helper.withdraw_from_account(&mut dummy_account, lp_resource, dec!("5000"))?;
+    let (redeemed_tokens, forfeited, unredeemed_lp) =
+        helper.redeem_with_unredeemed_lp(lp_tokens)?;
+
+    // Nothing has vested, so no tokens come out, nothing is forfeited and the
+    // LP tokens are handed back untouched
+    assert_eq!(
+        redeemed_tokens.resource_address(&mut helper.env)?,
+        helper.token_address
+    );
+    assert_eq!(redeemed_tokens.amount(&mut helper.env)?, Decimal::ZERO);
+    assert_eq!(forfeited, Decimal::ZERO);
+    assert_eq!(
+        unredeemed_lp.resource_address(&mut helper.env)?,
+        lp_resource
+    );
+    assert_eq!(unredeemed_lp.amount(&mut helper.env)?, dec!("5000"));
+    assert_eq!(helper.get_forfeited_tokens()?, Decimal::ZERO);
+    assert_eq!(helper.get_total_tokens_to_vest()?, dec!("10000"));
+
+    Ok(())
+}
+
+#[test]
+fn test_redeem_amount_during_cliff_returns_lp_tokens() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new_with_config(365, dec!("0.1"), 604800, 30)?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("5000"), account)?;
+
+    // Advance into the cliff
+    helper.advance_time_seconds(604800);
+    helper.advance_time_days(10);
+
+    let lp_tokens = helper.withdraw_from_account(&mut dummy_account, lp_resource, dec!("5000"))?;
+    let (redeemed_tokens, leftover_lp) = helper.redeem_amount(lp_tokens, dec!("2000"))?;
+
+    // Nothing has vested, so no tokens come out and no LP tokens are burned
+    assert_eq!(redeemed_tokens.amount(&mut helper.env)?, Decimal::ZERO);
+    assert_eq!(leftover_lp.resource_address(&mut helper.env)?, lp_resource);
+    assert_eq!(leftover_lp.amount(&mut helper.env)?, dec!("5000"));
+
+    Ok(())
+}
+
+#[test]
+fn test_preview_redeem_during_cliff_is_zero() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new_with_config(365, dec!("0.1"), 604800, 30)?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let (_, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("5000"), account)?;
+
+    // Advance into the cliff
+    helper.advance_time_seconds(604800);
+    helper.advance_time_days(10);
+
+    assert_eq!(helper.preview_redeem(dec!("5000"))?, Decimal::ZERO);
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "Cliff must not be longer than the vest duration")]
+fn test_instantiate_with_cliff_longer_than_vest_fails() {
+    Helper::new_with_config(365, dec!("0.1"), 604800, 366).unwrap();
+}
+
+// ==================== Config Tests ====================
+
+#[test]
+fn test_get_config_before_and_after_setup() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new_with_config(365, dec!("0.1"), 604800, 30)?;
+
+    let config = helper.get_config()?;
+    assert_eq!(config.vest_start, None);
+    assert_eq!(config.vest_end, None);
+    assert_eq!(config.cliff_end, None);
+    assert_eq!(config.vest_duration_days, 365);
+    assert_eq!(config.pre_claim_duration_seconds, 604800);
+    assert_eq!(config.cliff_duration_days, 30);
+    assert_eq!(config.initial_vested_fraction, dec!("0.1"));
+    assert_eq!(config.max_claim_per_account, None);
+    assert_eq!(config.redemption_fee_fraction, dec!("0"));
+    assert_eq!(config.min_redeem_lp, dec!("0"));
+    assert_eq!(config.min_redemption_value, None);
+    assert_eq!(config.forfeit_policy, ForfeitPolicy::Redistribute);
+    assert_eq!(config.claim_grace_days, None);
+    assert_eq!(config.vest_granularity, VestGranularity::Second);
+    assert_eq!(config.max_total_to_vest, None);
+    assert_eq!(config.token_to_vest, helper.token_address);
+    assert_eq!(config.pool_unit, helper.get_lp_resource_address());
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let config = helper.get_config()?;
+    let vest_start = config.vest_start.unwrap();
+    assert_eq!(config.vest_end, vest_start.add_days(365));
+    assert_eq!(config.cliff_end, vest_start.add_days(30));
+    assert_eq!(config.claim_deadline, None);
+
+    Ok(())
+}
+
+#[test]
+fn test_claim_just_before_deadline() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new_with(HelperOptions {
+        claim_grace_days: Some(30),
+        ..Default::default()
+    })?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let config = helper.get_config()?;
+    let deadline = config.claim_deadline.unwrap();
+    assert_eq!(Some(deadline), config.vest_end.unwrap().add_days(30));
+
+    let lp_resource = helper.get_lp_resource_address();
+    let (dummy_account, account) = helper.create_dummy_account()?;
+
+    helper
+        .env
+        .set_current_time(deadline.add_seconds(-1).unwrap());
+    assert!(helper.can_claim(dec!("1000"), account)?);
+    helper.claim(dec!("1000"), account)?;
+    assert_eq!(
+        helper.get_account_balance(&dummy_account, lp_resource)?,
+        dec!("1000")
+    );
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "The claim deadline has passed.")]
+fn test_claim_just_after_deadline_fails() {
+    let mut helper = Helper::new_with(HelperOptions {
+        claim_grace_days: Some(30),
+        ..Default::default()
+    })
+    .unwrap();
+
+    helper.create_pool_units(dec!("10000")).unwrap();
+    helper.finish_setup().unwrap();
+
+    let deadline = helper.get_config().unwrap().claim_deadline.unwrap();
+    let (_dummy_account, account) = helper.create_dummy_account().unwrap();
+
+    helper.env.set_current_time(deadline);
+    assert!(!helper.can_claim(dec!("1000"), account).unwrap());
+
+    // This should panic
+    helper.claim(dec!("1000"), account).unwrap();
+}
+
+#[test]
+fn test_reclaim_unclaimed_after_deadline() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new_with(HelperOptions {
+        claim_grace_days: Some(30),
+        ..Default::default()
+    })?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let (_dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("4000"), account)?;
+
+    let deadline = helper.get_config()?.claim_deadline.unwrap();
+    helper.env.set_current_time(deadline);
+
+    let reclaimed = helper.reclaim_unclaimed()?;
+    assert_eq!(reclaimed.amount(&mut helper.env)?, dec!("6000"));
+    assert_eq!(helper.get_lp_token_amount()?, dec!("0"));
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "The claim deadline has not passed yet.")]
+fn test_reclaim_unclaimed_before_deadline_fails() {
+    let mut helper = Helper::new_with(HelperOptions {
+        claim_grace_days: Some(30),
+        ..Default::default()
+    })
+    .unwrap();
+
+    helper.create_pool_units(dec!("10000")).unwrap();
+    helper.finish_setup().unwrap();
+    helper.advance_to_fully_vested().unwrap();
+
+    // This should panic
+    helper.reclaim_unclaimed().unwrap();
+}
+
+#[test]
+fn test_get_timeline_is_consistent() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    let timeline = helper.get_timeline()?;
+    assert_eq!(timeline.setup_finished_at, None);
+    assert_eq!(timeline.vest_start, None);
+    assert_eq!(timeline.vest_end, None);
+
+    helper.create_pool_units(dec!("10000"))?;
+    let finished_at = helper.env.get_current_time();
+    helper.finish_setup()?;
+
+    let timeline = helper.get_timeline()?;
+    let setup_finished_at = timeline.setup_finished_at.unwrap();
+    let vest_start = timeline.vest_start.unwrap();
+    assert_eq!(
+        setup_finished_at.seconds_since_unix_epoch,
+        finished_at.seconds_since_unix_epoch
+    );
+    assert_eq!(setup_finished_at.add_seconds(604800), Some(vest_start));
+    assert_eq!(timeline.vest_end, vest_start.add_days(365));
+
+    let config = helper.get_config()?;
+    assert_eq!(timeline.vest_start, config.vest_start);
+    assert_eq!(timeline.vest_end, config.vest_end);
+
+    Ok(())
+}
+
+#[test]
+fn test_get_schedule_descriptor() -> Result<(), RuntimeError> {
+    let mut helper =
+        Helper::new_with_config_and_curve(365, dec!("0.1"), 604800, 30, VestingCurve::Quadratic)?;
+
+    let descriptor = helper.get_schedule_descriptor()?;
+    assert_eq!(descriptor.initial_fraction, dec!("0.1"));
+    assert_eq!(descriptor.curve, VestingCurve::Quadratic);
+    assert_eq!(descriptor.duration_days, 365);
+    assert_eq!(descriptor.cliff_days, 30);
+    assert_eq!(descriptor.granularity, VestGranularity::Second);
+
+    // The shape doesn't change when the vest starts or time passes
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+    helper.advance_time_days(100);
+    helper.refill()?;
+    assert_eq!(helper.get_schedule_descriptor()?, descriptor);
+
+    let config = helper.get_config()?;
+    assert_eq!(descriptor.initial_fraction, config.initial_vested_fraction);
+    assert_eq!(descriptor.curve, config.vesting_curve);
+    assert_eq!(descriptor.duration_days, config.vest_duration_days);
+    assert_eq!(descriptor.cliff_days, config.cliff_duration_days);
+
+    Ok(())
+}
+
+#[test]
+fn test_set_vest_duration_days_before_setup() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.set_vest_duration_days(100)?;
+    assert_eq!(helper.get_config()?.vest_duration_days, 100);
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let config = helper.get_config()?;
+    assert_eq!(config.vest_end, config.vest_start.unwrap().add_days(100));
+
+    // Half of the new duration: 0.1 + 0.9 * 0.5
+    helper.advance_time_seconds(604800);
+    helper.advance_time_days(50);
+    helper.refill()?;
+    helper::assert_approx_eq(
+        helper.get_pool_vault_amount()?,
+        dec!("5500"),
+        helper::TOLERANCE,
+        "pool at 50% of the extended duration",
+    );
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "Vesting has already started")]
+fn test_set_vest_duration_days_after_setup_fails() {
+    let mut helper = Helper::new().unwrap();
+
+    helper.create_pool_units(dec!("10000")).unwrap();
+    helper.finish_setup().unwrap();
+
+    helper.set_vest_duration_days(100).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Vest duration must be positive")]
+fn test_set_vest_duration_days_zero_fails() {
+    let mut helper = Helper::new().unwrap();
+    helper.set_vest_duration_days(0).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Vest duration must not exceed 100 years")]
+fn test_set_vest_duration_days_above_maximum_fails() {
+    let mut helper = Helper::new().unwrap();
+    helper
+        .set_vest_duration_days(MAX_VEST_DURATION_DAYS + 1)
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Vest duration must not exceed 100 years")]
+fn test_instantiate_with_vest_duration_above_maximum_fails() {
+    Helper::new_with_config(MAX_VEST_DURATION_DAYS + 1, dec!("0.1"), 604800, 0).unwrap();
+}
+
+#[test]
+fn test_instantiate_with_minimum_pre_claim_duration() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new_with_config(365, dec!("0.1"), MIN_PRE_CLAIM_DURATION_SECONDS, 0)?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+    assert_eq!(
+        helper.get_pre_claim_seconds_remaining()?,
+        MIN_PRE_CLAIM_DURATION_SECONDS
+    );
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "Pre-claim period must last at least one hour")]
+fn test_instantiate_with_pre_claim_below_minimum_fails() {
+    Helper::new_with_config(365, dec!("0.1"), MIN_PRE_CLAIM_DURATION_SECONDS - 1, 0).unwrap();
+}
+
+#[test]
+fn test_set_initial_vested_fraction_to_zero() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.set_initial_vested_fraction(dec!("0"))?;
+    assert_eq!(helper.get_config()?.initial_vested_fraction, dec!("0"));
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    // Nothing is vested at vest_start
+    helper.advance_time_seconds(604800);
+    helper.refill()?;
+    assert_eq!(helper.get_pool_vault_amount()?, dec!("0"));
+
+    // Purely linear afterwards
+    helper.advance_time_days(182);
+    helper.advance_time_seconds(43200);
+    helper.refill()?;
+    helper::assert_approx_eq(
+        helper.get_pool_vault_amount()?,
+        dec!("5000"),
+        helper::TOLERANCE,
+        "pool at 50% progress",
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_set_initial_vested_fraction_to_one() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.set_initial_vested_fraction(dec!("1"))?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    // Everything is vested at vest_start
+    helper.advance_time_seconds(604800);
+    helper.refill()?;
+    assert_eq!(helper.get_pool_vault_amount()?, dec!("10000"));
+    assert_eq!(helper.get_locked_vault_amount()?, dec!("0"));
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "initial_vested_fraction must be between 0 and 1")]
+fn test_set_initial_vested_fraction_above_one_fails() {
+    let mut helper = Helper::new().unwrap();
+    helper.set_initial_vested_fraction(dec!("1.01")).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Vesting has already started")]
+fn test_set_initial_vested_fraction_after_setup_fails() {
+    let mut helper = Helper::new().unwrap();
+
+    helper.create_pool_units(dec!("10000")).unwrap();
+    helper.finish_setup().unwrap();
+
+    helper.set_initial_vested_fraction(dec!("0.2")).unwrap();
+}
+
+#[test]
+fn test_configure_lp_metadata() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+    assert_eq!(helper.get_metadata(lp_resource, "symbol")?, None);
+
+    helper.configure_lp_metadata("Incentives LP", "ILP", "https://example.com/ilp.png")?;
+
+    assert_eq!(
+        helper.get_metadata(lp_resource, "name")?,
+        Some(MetadataValue::String("Incentives LP".to_string()))
+    );
+    assert_eq!(
+        helper.get_metadata(lp_resource, "symbol")?,
+        Some(MetadataValue::String("ILP".to_string()))
+    );
+    assert_eq!(
+        helper.get_metadata(lp_resource, "icon_url")?,
+        Some(MetadataValue::Url(UncheckedUrl::of(
+            "https://example.com/ilp.png"
+        )))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_accelerate_vest_start() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new_with_config(365, dec!("0.1"), 604800, 30)?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    // Start vesting after one day instead of seven
+    helper.advance_time_days(1);
+    let new_start = helper.env.get_current_time();
+    helper.accelerate_vest_start(new_start)?;
+
+    let config = helper.get_config()?;
+    assert_eq!(config.vest_start, Some(new_start));
+    assert_eq!(config.vest_end, new_start.add_days(365));
+    assert_eq!(config.cliff_end, new_start.add_days(30));
+
+    // Redemptions are possible right away
+    helper.refill()?;
+    assert_eq!(helper.get_pre_claim_seconds_remaining()?, 0);
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "New vest start must be earlier than the current vest start")]
+fn test_accelerate_vest_start_later_fails() {
+    let mut helper = Helper::new().unwrap();
+
+    helper.create_pool_units(dec!("10000")).unwrap();
+    helper.finish_setup().unwrap();
+
+    let vest_start = helper.get_config().unwrap().vest_start.unwrap();
+
+    // This should panic
+    helper
+        .accelerate_vest_start(vest_start.add_days(1).unwrap())
+        .unwrap();
+}
+
+#[test]
+fn test_set_dapp_definition() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+    let original = helper.get_dapp_definition()?;
+    assert!(original.is_some());
+
+    let new_dapp_def = helper.create_account()?;
+    helper.set_dapp_definition(new_dapp_def)?;
+
+    assert_eq!(
+        helper.get_dapp_definition()?,
+        Some(GlobalAddress::from(new_dapp_def))
+    );
+    assert_ne!(helper.get_dapp_definition()?, original);
+
+    Ok(())
+}
+
+#[test]
+fn test_create_pool_units_up_to_max_total_to_vest() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new_with(HelperOptions {
+        limits: VesterLimits {
+            max_total_to_vest: Some(dec!("10000")),
+            ..Default::default()
+        },
+        ..Default::default()
+    })?;
+
+    helper.create_pool_units(dec!("6000"))?;
+    helper.create_pool_units(dec!("4000"))?;
+    assert_eq!(helper.get_total_tokens_to_vest()?, dec!("10000"));
+    assert_eq!(helper.get_config()?.max_total_to_vest, Some(dec!("10000")));
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "Total to vest would exceed the maximum.")]
+fn test_create_pool_units_above_max_total_to_vest_fails() {
+    let mut helper = Helper::new_with(HelperOptions {
+        limits: VesterLimits {
+            max_total_to_vest: Some(dec!("10000")),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .unwrap();
+
+    helper.create_pool_units(dec!("6000")).unwrap();
+    // This should panic
+    helper.create_pool_units(dec!("4001")).unwrap();
+}
+
+#[test]
+fn test_rejected_contribution_leaves_total_unchanged() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new_with(HelperOptions {
+        limits: VesterLimits {
+            max_total_to_vest: Some(dec!("10000")),
+            ..Default::default()
+        },
+        ..Default::default()
+    })?;
+
+    helper.create_pool_units(dec!("6000"))?;
+    let result = helper.create_pool_units(dec!("5000"));
+    assert!(result.is_err());
+    assert_eq!(helper.get_total_tokens_to_vest()?, dec!("6000"));
+    assert_eq!(helper.get_pool_vault_amount()?, dec!("6000"));
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "Total to vest would exceed the maximum.")]
+fn test_add_tranche_above_max_total_to_vest_fails() {
+    let mut helper = Helper::new_with(HelperOptions {
+        limits: VesterLimits {
+            max_total_to_vest: Some(dec!("10000")),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .unwrap();
+
+    helper.create_pool_units(dec!("10000")).unwrap();
+    helper.finish_setup().unwrap();
+    // This should panic
+    helper.add_tranche(dec!("1")).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Maximum total to vest must be positive.")]
+fn test_instantiate_with_zero_max_total_to_vest_fails() {
+    Helper::new_with(HelperOptions {
+        limits: VesterLimits {
+            max_total_to_vest: Some(dec!("0")),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .unwrap();
+}
+
+// ==================== Allowlist Tests ====================
+
+#[test]
+fn test_claim_to_allowed_account() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+    let (dummy_account, account) = helper.create_dummy_account()?;
+    let (_, other_account) = helper.create_dummy_account()?;
+
+    helper.add_allowed_account(account)?;
+    helper.set_allowlist_enabled(true)?;
+    assert!(helper.is_allowed_account(account)?);
+    assert!(!helper.is_allowed_account(other_account)?);
+    assert!(helper.can_claim(dec!("1000"), account)?);
+    assert!(!helper.can_claim(dec!("1000"), other_account)?);
+
+    helper.claim(dec!("1000"), account)?;
+    assert_eq!(
+        helper.get_account_balance(&dummy_account, lp_resource)?,
+        dec!("1000")
+    );
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "Account is not on the allowlist.")]
+fn test_claim_to_account_not_on_allowlist_fails() {
+    let mut helper = Helper::new().unwrap();
+
+    helper.create_pool_units(dec!("10000")).unwrap();
+    helper.finish_setup().unwrap();
+
+    let (_, account) = helper.create_dummy_account().unwrap();
+    helper.set_allowlist_enabled(true).unwrap();
+
+    // This should panic
+    helper.claim(dec!("1000"), account).unwrap();
+}
+
+#[test]
+fn test_claim_batch_rejects_account_not_on_allowlist() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let (_, allowed_account) = helper.create_dummy_account()?;
+    let (_, removed_account) = helper.create_dummy_account()?;
+    helper.add_allowed_account(allowed_account)?;
+    helper.add_allowed_account(removed_account)?;
+    helper.remove_allowed_account(removed_account)?;
+    helper.set_allowlist_enabled(true)?;
+
+    // One account off the list aborts the whole batch
+    let result = helper.claim_batch(vec![
+        (dec!("1000"), allowed_account),
+        (dec!("1000"), removed_account),
+    ]);
+    assert!(result.is_err());
+    assert_eq!(helper.get_lp_token_amount()?, dec!("10000"));
+
+    // Without the allowlist, any account can be claimed to again
+    helper.set_allowlist_enabled(false)?;
+    helper.claim_batch(vec![
+        (dec!("1000"), allowed_account),
+        (dec!("1000"), removed_account),
+    ])?;
+    assert_eq!(helper.get_lp_token_amount()?, dec!("8000"));
+
+    Ok(())
+}
+
+// ==================== Pause Tests ====================
+
+#[test]
+#[should_panic(expected = "Redemptions are paused")]
+fn test_redeem_while_paused_fails() {
+    let mut helper = Helper::new().unwrap();
+
+    helper.create_pool_units(dec!("10000")).unwrap();
+    helper.finish_setup().unwrap();
+
+    let lp_resource = helper.get_lp_resource_address();
+    let (mut dummy_account, account) = helper.create_dummy_account().unwrap();
+    helper.claim(dec!("1000"), account).unwrap();
+
+    helper.advance_time_seconds(604800);
+    helper.pause().unwrap();
+
+    // This should panic
+    let _ = helper
+        .redeem_lp_from_account(&mut dummy_account, lp_resource, dec!("1000"))
+        .unwrap();
+}
+
+#[test]
+fn test_refill_works_while_paused_and_redeem_after_unpause() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("1000"), account)?;
+
+    helper.advance_time_seconds(604800);
+    helper.pause()?;
+    assert!(helper.is_paused()?);
+
+    // Vesting continues while paused
+    helper.refill()?;
+    assert_eq!(helper.get_pool_vault_amount()?, dec!("1000"));
+
+    helper.unpause()?;
+    assert!(!helper.is_paused()?);
+
+    let redeemed_tokens =
+        helper.redeem_lp_from_account(&mut dummy_account, lp_resource, dec!("1000"))?;
+    assert_eq!(redeemed_tokens.amount(&mut helper.env)?, dec!("100"));
+
+    Ok(())
+}
+
+#[test]
+fn test_guardian_can_pause_and_unpause() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let guardian_badge_address = helper.guardian_badge_address;
+    helper.pause_with_badge(guardian_badge_address)?;
+    assert!(helper.is_paused()?);
+
+    helper.unpause_with_badge(guardian_badge_address)?;
+    assert!(!helper.is_paused()?);
+
+    Ok(())
+}
+
+#[test]
+fn test_guardian_cannot_claim_or_remove_lp() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let guardian_badge_address = helper.guardian_badge_address;
+    let (_, account) = helper.create_dummy_account()?;
+
+    let result = helper.claim_with_badge(dec!("1000"), account, guardian_badge_address);
+    assert!(matches!(
+        result,
+        Err(RuntimeError::SystemModuleError(
+            SystemModuleError::AuthError(..)
+        ))
+    ));
+
+    let result = helper.remove_lp_with_badge(guardian_badge_address);
+    assert!(matches!(
+        result,
+        Err(RuntimeError::SystemModuleError(
+            SystemModuleError::AuthError(..)
+        ))
+    ));
+
+    assert_eq!(helper.get_lp_token_amount()?, dec!("10000"));
+
+    Ok(())
+}
+
+#[test]
+fn test_admin_cannot_pause() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let admin_badge_address = helper.admin_badge_address;
+    let result = helper.pause_with_badge(admin_badge_address);
+    assert!(matches!(
+        result,
+        Err(RuntimeError::SystemModuleError(
+            SystemModuleError::AuthError(..)
+        ))
+    ));
+    assert!(!helper.is_paused()?);
+
+    Ok(())
+}
+
+// ==================== Vesting Curve Tests ====================
+
+/// Refills at 25%, 50% and 75% of a 100 day vest with a 10% initial fraction
+/// and checks the pool against the expected vested fractions.
+fn assert_curve_checkpoints(
+    curve: VestingCurve,
+    expected_fractions: [Decimal; 3],
+) -> Result<(), RuntimeError> {
+    let mut helper = Helper::new_with_config_and_curve(100, dec!("0.1"), 604800, 0, curve)?;
+    let tolerance = dec!("0.000001");
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+    helper.advance_time_seconds(604800);
+
+    for (checkpoint, expected_fraction) in expected_fractions.into_iter().enumerate() {
+        helper.advance_time_days(25);
+        helper.refill()?;
+
+        let message = format!("{:?} at {}% elapsed", curve, (checkpoint + 1) * 25);
+        helper::assert_approx_eq(
+            helper.get_vested_fraction()?,
+            expected_fraction,
+            tolerance,
+            &message,
+        );
+        helper::assert_approx_eq(
+            helper.get_pool_vault_amount()?,
+            dec!("10000") * expected_fraction,
+            tolerance,
+            &message,
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_linear_curve_checkpoints() -> Result<(), RuntimeError> {
+    // 0.1 + 0.9 * progress
+    assert_curve_checkpoints(
+        VestingCurve::Linear,
+        [dec!("0.325"), dec!("0.55"), dec!("0.775")],
+    )
+}
+
+#[test]
+fn test_quadratic_curve_checkpoints() -> Result<(), RuntimeError> {
+    // 0.1 + 0.9 * progress^2
+    assert_curve_checkpoints(
+        VestingCurve::Quadratic,
+        [dec!("0.15625"), dec!("0.325"), dec!("0.60625")],
+    )
+}
+
+#[test]
+fn test_square_root_curve_checkpoints() -> Result<(), RuntimeError> {
+    // 0.1 + 0.9 * progress^0.5
+    assert_curve_checkpoints(
+        VestingCurve::SquareRoot,
+        [
+            dec!("0.55"),
+            dec!("0.736396103067892771"),
+            dec!("0.879422863405994782"),
+        ],
+    )
+}
+
+// ==================== Vest Granularity Tests ====================
+
+#[test]
+fn test_hourly_granularity_unlocks_on_step_boundaries() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new_with(HelperOptions {
+        vest_granularity: VestGranularity::Hour,
+        ..Default::default()
+    })?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    // At vest start only the initial 10% is vested
+    helper.advance_time_seconds(604800);
+    helper.refill()?;
+    assert_eq!(helper.get_pool_vault_amount()?, dec!("1000"));
+
+    // Nothing more unlocks within the first hour
+    helper.advance_time_seconds(3599);
+    helper.refill()?;
+    assert_eq!(helper.get_pool_vault_amount()?, dec!("1000"));
+
+    // One hour of a 365 day vest unlocks at the boundary
+    helper.advance_time_seconds(1);
+    helper.refill()?;
+    let after_one_hour = dec!("1000") + dec!("9000") / dec!("8760");
+    helper::assert_approx_eq(
+        helper.get_pool_vault_amount()?,
+        after_one_hour,
+        helper::TOLERANCE,
+        "pool after the first hour",
+    );
+
+    // And stays there until the next boundary
+    helper.advance_time_seconds(1800);
+    helper.refill()?;
+    helper::assert_approx_eq(
+        helper.get_pool_vault_amount()?,
+        after_one_hour,
+        helper::TOLERANCE,
+        "pool halfway through the second hour",
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_minute_granularity_unlocks_on_step_boundaries() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new_with(HelperOptions {
+        vest_granularity: VestGranularity::Minute,
+        ..Default::default()
+    })?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    helper.advance_time_seconds(604800);
+    helper.advance_time_seconds(59);
+    helper.refill()?;
+    assert_eq!(helper.get_pool_vault_amount()?, dec!("1000"));
+
+    // One minute of a 365 day vest unlocks at the boundary
+    helper.advance_time_seconds(1);
+    helper.refill()?;
+    helper::assert_approx_eq(
+        helper.get_pool_vault_amount()?,
+        dec!("1000") + dec!("9000") / dec!("525600"),
+        helper::TOLERANCE,
+        "pool after the first minute",
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_hourly_granularity_fully_vests_at_vest_end() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new_with(HelperOptions {
+        vest_granularity: VestGranularity::Hour,
+        ..Default::default()
+    })?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    helper.advance_time_seconds(604800);
+    helper.advance_time_days(365);
+    helper.refill()?;
+    assert_eq!(helper.get_pool_vault_amount()?, dec!("10000"));
+    assert_eq!(helper.get_locked_vault_amount()?, dec!("0"));
+
+    Ok(())
+}
+
+// ==================== Track Tests ====================
+
+#[test]
+fn test_two_tracks_vest_at_different_rates() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    // Track 0 vests over a year with 10% up front, track 1 over two years
+    // with nothing up front
+    let track_id = helper.create_track(TrackParams {
+        vest_duration_days: 730,
+        initial_vested_fraction: dec!("0"),
+        cliff_duration_days: 0,
+    })?;
+    assert_eq!(track_id, 1);
+    assert_eq!(helper.get_track_count()?, 1);
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.create_track_pool_units(track_id, dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let track = helper.get_track(track_id)?;
+    assert_eq!(track.total_tokens_to_vest, dec!("10000"));
+    assert_eq!(track.locked_vault_amount, dec!("10000"));
+    assert_eq!(track.pool_vault_amount, dec!("0"));
+    assert_ne!(
+        track.pool_unit_resource_address,
+        helper.get_lp_resource_address()
+    );
+
+    // Half a year in: 55% of track 0, 25% of track 1
+    helper.advance_to_progress(dec!("0.5"))?;
+    helper.refill()?;
+    helper.refill_track(track_id)?;
+    assert_eq!(helper.get_pool_vault_amount()?, dec!("5500"));
+    helper::assert_approx_eq(
+        helper.get_track(track_id)?.pool_vault_amount,
+        dec!("2500"),
+        helper::TOLERANCE,
+        "track 1 pool at a quarter of its vest",
+    );
+
+    // LP tokens of track 1 redeem against its own pool only
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim_track(track_id, dec!("1000"), account)?;
+    let lp_tokens = helper.withdraw_from_account(
+        &mut dummy_account,
+        track.pool_unit_resource_address,
+        dec!("1000"),
+    )?;
+    let (redeemed_tokens, forfeited, unredeemed_lp) = helper.redeem_track(track_id, lp_tokens)?;
+    helper::assert_approx_eq(
+        redeemed_tokens.amount(&mut helper.env)?,
+        dec!("250"),
+        helper::TOLERANCE,
+        "tokens redeemed from track 1",
+    );
+    helper::assert_approx_eq(
+        forfeited,
+        dec!("750"),
+        helper::TOLERANCE,
+        "tokens forfeited on track 1",
+    );
+    assert_eq!(unredeemed_lp.amount(&mut helper.env)?, dec!("0"));
+    assert_eq!(helper.get_pool_vault_amount()?, dec!("5500"));
+
+    // Track 0 has fully vested after a year, track 1 only halfway
+    helper.advance_to_progress(dec!("1"))?;
+    helper.refill()?;
+    helper.refill_track(track_id)?;
+    assert_eq!(helper.get_pool_vault_amount()?, dec!("10000"));
+    let track = helper.get_track(track_id)?;
+    helper::assert_approx_eq(
+        track.pool_vault_amount + track.locked_vault_amount,
+        dec!("9750"),
+        helper::TOLERANCE,
+        "track 1 after the redemption",
+    );
+    assert!(track.locked_vault_amount > Decimal::ZERO);
+
+    Ok(())
+}
+
+#[test]
+fn test_create_track_after_finish_setup_fails() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    helper::assert_vester_error(
+        helper.create_track(TrackParams {
+            vest_duration_days: 730,
+            initial_vested_fraction: dec!("0"),
+            cliff_duration_days: 0,
+        }),
+        VesterError::SetupAlreadyFinished,
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_unknown_track_fails() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper::assert_vester_error(
+        helper.create_track_pool_units(1, dec!("1000")),
+        VesterError::UnknownTrack,
+    );
+
+    Ok(())
+}
+
+// ==================== Error Tests ====================
+
+#[test]
+fn test_setup_not_finished_error() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper::assert_vester_error(helper.refill(), VesterError::SetupNotFinished);
+    helper::assert_vester_error(
+        helper.add_tranche(dec!("1000")),
+        VesterError::SetupNotFinished,
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_still_in_pre_claim_error() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+    helper::assert_vester_error(helper.refill(), VesterError::StillInPreClaim);
+
+    Ok(())
+}
+
+#[test]
+fn test_setup_already_finished_error() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+    helper::assert_vester_error(helper.finish_setup(), VesterError::SetupAlreadyFinished);
+    helper::assert_vester_error(
+        helper.set_vest_duration_days(100),
+        VesterError::SetupAlreadyFinished,
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_empty_bucket_error() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+    helper::assert_vester_error(helper.add_tranche(dec!("0")), VesterError::EmptyBucket);
+
+    Ok(())
+}
+
+#[test]
+fn test_redemptions_paused_error() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let lp_resource = helper.get_lp_resource_address();
+    let (mut dummy_account, account) = helper.create_dummy_account()?;
+    helper.claim(dec!("1000"), account)?;
+
+    helper.advance_time_seconds(604800);
+    helper.pause()?;
+    helper::assert_vester_error(
+        helper.redeem_lp_from_account(&mut dummy_account, lp_resource, dec!("1000")),
+        VesterError::RedemptionsPaused,
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_initial_tokens_not_token_to_vest_error() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    let other_tokens = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(10000, &mut helper.env)?;
+    helper::assert_vester_error(
+        helper
+            .instantiate_vester_with_funds(other_tokens.into())
+            .map(|_| ()),
+        VesterError::InitialTokensNotTokenToVest,
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_negative_cliff_error() -> Result<(), RuntimeError> {
+    helper::assert_vester_error(
+        Helper::new_with_config(365, dec!("0.1"), 604800, -1).map(|_| ()),
+        VesterError::NegativeCliff,
+    );
+
+    let mut helper = Helper::new()?;
+    helper::assert_vester_error(
+        helper.create_track(TrackParams {
+            vest_duration_days: 730,
+            initial_vested_fraction: dec!("0"),
+            cliff_duration_days: -1,
+        }),
+        VesterError::NegativeCliff,
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_non_positive_max_claim_error() {
+    let result = Helper::new_with(HelperOptions {
+        limits: VesterLimits {
+            max_claim_per_account: Some(dec!("0")),
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    helper::assert_vester_error(result.map(|_| ()), VesterError::NonPositiveMaxClaim);
+}
+
+#[test]
+fn test_negative_min_redemption_amount_error() {
+    let result = Helper::new_with(HelperOptions {
+        limits: VesterLimits {
+            min_redeem_lp: dec!("-1"),
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    helper::assert_vester_error(result.map(|_| ()), VesterError::NegativeMinRedemptionAmount);
+}
+
+#[test]
+fn test_non_positive_min_redemption_value_error() {
+    let result = Helper::new_with(HelperOptions {
+        limits: VesterLimits {
+            min_redemption_value: Some(dec!("0")),
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    helper::assert_vester_error(
+        result.map(|_| ()),
+        VesterError::NonPositiveMinRedemptionValue,
+    );
+}
+
+#[test]
+fn test_negative_claim_grace_period_error() {
+    let result = Helper::new_with(HelperOptions {
+        claim_grace_days: Some(-1),
+        ..Default::default()
+    });
+    helper::assert_vester_error(result.map(|_| ()), VesterError::NegativeClaimGracePeriod);
+}
+
+#[test]
+fn test_vest_start_cannot_move_error() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+    helper.advance_time_seconds(604800);
+
+    let new_start = helper.env.get_current_time().add_seconds(3600).unwrap();
+    helper::assert_vester_error(
+        helper.accelerate_vest_start(new_start),
+        VesterError::VestStartCannotMove,
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_new_vest_start_in_past_error() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let new_start = helper.env.get_current_time().add_seconds(-1).unwrap();
+    helper::assert_vester_error(
+        helper.accelerate_vest_start(new_start),
+        VesterError::NewVestStartInPast,
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_non_positive_lp_amount_error() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let (_dummy_account, account) = helper.create_dummy_account()?;
+    helper::assert_vester_error(
+        helper.claim(dec!("0"), account),
+        VesterError::NonPositiveLpAmount,
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_no_recipients_error() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+    helper::assert_vester_error(
+        helper.claim_weighted(dec!("1000"), vec![]),
+        VesterError::NoRecipients,
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_no_claim_deadline_error() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+    helper.advance_to_fully_vested()?;
+    helper::assert_vester_error(helper.reclaim_unclaimed(), VesterError::NoClaimDeadline);
+
+    Ok(())
+}
+
+#[test]
+fn test_reservation_expiry_not_in_future_error() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let (_dummy_account, account) = helper.create_dummy_account()?;
+    let expiry = helper.env.get_current_time();
+    helper::assert_vester_error(
+        helper.reserve_claim(dec!("1000"), account, expiry),
+        VesterError::ReservationExpiryNotInFuture,
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_reservation_already_open_error() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let (_dummy_account, account) = helper.create_dummy_account()?;
+    let expiry = helper.env.get_current_time().add_seconds(3600).unwrap();
+    helper.reserve_claim(dec!("1000"), account, expiry)?;
+    helper::assert_vester_error(
+        helper.reserve_claim(dec!("500"), account, expiry),
+        VesterError::ReservationAlreadyOpen,
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_no_open_reservation_error() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+
+    let (_dummy_account, account) = helper.create_dummy_account()?;
+    helper::assert_vester_error(
+        helper.finalize_claim(account),
+        VesterError::NoOpenReservation,
+    );
+    helper::assert_vester_error(
+        helper.release_expired(account),
+        VesterError::NoOpenReservation,
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_non_positive_amount_error() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    helper.create_pool_units(dec!("10000"))?;
+    helper.finish_setup()?;
+    helper::assert_vester_error(
+        helper.remove_lp_amount(dec!("0")),
+        VesterError::NonPositiveAmount,
+    );
+    helper::assert_vester_error(
+        helper.remove_locked_tokens_amount(dec!("0")),
+        VesterError::NonPositiveAmount,
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_migration_to_self_error() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    let vester = Reference(helper.vester.0);
+    helper::assert_vester_error(
+        helper.authorize_migration(vester),
+        VesterError::MigrationToSelf,
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_migration_from_self_error() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    let vester = Reference(helper.vester.0);
+    helper::assert_vester_error(
+        helper.accept_migration_from(vester),
+        VesterError::MigrationFromSelf,
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_no_migration_source_error() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    let tokens = helper.token_to_vest.take(dec!("1000"), &mut helper.env)?;
+    helper::assert_vester_error(
+        helper.accept_migrated_tokens(tokens),
+        VesterError::NoMigrationSource,
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_migrated_tokens_not_token_to_vest_error() -> Result<(), RuntimeError> {
+    let mut helper = Helper::new()?;
+
+    let old_vester = helper.instantiate_funded_vester(dec!("10000"))?;
+    helper.accept_migration_from(Reference(old_vester.0))?;
+
+    // Auth is disabled in the helper, so the call passes as the old vester's
+    let other_tokens = ResourceBuilder::new_fungible(OwnerRole::None)
+        .mint_initial_supply(1000, &mut helper.env)?;
+    helper::assert_vester_error(
+        helper.accept_migrated_tokens(other_tokens.into()),
+        VesterError::MigratedTokensNotTokenToVest,
     );
 
     Ok(())